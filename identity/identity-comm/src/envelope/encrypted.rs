@@ -18,6 +18,7 @@ use libjose::jwe::JweHeader;
 use libjose::jwe::Token;
 
 use crate::envelope::EnvelopeExt;
+use crate::envelope::EnvelopeFormat;
 use crate::envelope::Plaintext;
 use crate::envelope::Signed;
 use crate::error::Result;
@@ -101,6 +102,7 @@ impl Encrypted {
 impl EnvelopeExt for Encrypted {
   const FEXT: &'static str = "dcem";
   const MIME: &'static str = "application/didcomm-encrypted+json";
+  const FORMAT: EnvelopeFormat = EnvelopeFormat::Encrypted;
 
   fn as_bytes(&self) -> &[u8] {
     self.0.as_bytes()
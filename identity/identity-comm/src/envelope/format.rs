@@ -0,0 +1,173 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+//! Media type negotiation for packed DIDComm messages.
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use crate::envelope::Encrypted;
+use crate::envelope::EnvelopeExt;
+use crate::envelope::Plaintext;
+use crate::envelope::Signed;
+use crate::error::Error;
+use crate::error::Result;
+
+/// The envelope format of a packed DIDComm message.
+///
+/// [Reference](https://identity.foundation/didcomm-messaging/spec/#iana-media-types)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EnvelopeFormat {
+  Plaintext,
+  Signed,
+  Encrypted,
+}
+
+impl EnvelopeFormat {
+  /// Returns the media type of the envelope format.
+  pub const fn media_type(&self) -> &'static str {
+    match self {
+      Self::Plaintext => Plaintext::MIME,
+      Self::Signed => Signed::MIME,
+      Self::Encrypted => Encrypted::MIME,
+    }
+  }
+
+  /// Returns the file extension of the envelope format.
+  pub const fn file_extension(&self) -> &'static str {
+    match self {
+      Self::Plaintext => Plaintext::FEXT,
+      Self::Signed => Signed::FEXT,
+      Self::Encrypted => Encrypted::FEXT,
+    }
+  }
+
+  /// Returns the envelope format identified by the given media type.
+  ///
+  /// Media type parameters are ignored and the comparison is case-insensitive.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the media type is not a DIDComm envelope media type.
+  pub fn from_media_type(media_type: &str) -> Result<Self> {
+    let essence: &str = media_type.split(';').next().unwrap_or_default().trim();
+
+    [Self::Plaintext, Self::Signed, Self::Encrypted]
+      .into_iter()
+      .find(|format| format.media_type().eq_ignore_ascii_case(essence))
+      .ok_or_else(|| Error::UnknownMediaType(media_type.to_owned()))
+  }
+}
+
+impl Display for EnvelopeFormat {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_str(self.media_type())
+  }
+}
+
+/// A packed DIDComm message of any [`EnvelopeFormat`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Envelope {
+  Plaintext(Plaintext),
+  Signed(Signed),
+  Encrypted(Encrypted),
+}
+
+impl Envelope {
+  /// Wraps received `data` in the envelope type identified by `media_type`, so it
+  /// can be unpacked with the matching routine.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the media type is not a DIDComm envelope media type.
+  pub fn from_media_type(media_type: &str, data: impl Into<String>) -> Result<Self> {
+    let data: String = data.into();
+
+    match EnvelopeFormat::from_media_type(media_type)? {
+      EnvelopeFormat::Plaintext => Ok(Self::Plaintext(Plaintext(data))),
+      EnvelopeFormat::Signed => Ok(Self::Signed(Signed(data))),
+      EnvelopeFormat::Encrypted => Ok(Self::Encrypted(Encrypted(data))),
+    }
+  }
+
+  /// Returns the [`EnvelopeFormat`] of the envelope.
+  pub fn format(&self) -> EnvelopeFormat {
+    match self {
+      Self::Plaintext(envelope) => envelope.format(),
+      Self::Signed(envelope) => envelope.format(),
+      Self::Encrypted(envelope) => envelope.format(),
+    }
+  }
+
+  /// Returns the media type of the envelope.
+  pub fn media_type(&self) -> &'static str {
+    self.format().media_type()
+  }
+
+  /// Returns the packed message as a slice of bytes.
+  pub fn as_bytes(&self) -> &[u8] {
+    match self {
+      Self::Plaintext(envelope) => envelope.as_bytes(),
+      Self::Signed(envelope) => envelope.as_bytes(),
+      Self::Encrypted(envelope) => envelope.as_bytes(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Object;
+  use identity_core::crypto::KeyPair;
+
+  use super::*;
+  use crate::envelope::SignatureAlgorithm;
+  use crate::message::DIDCommMessage;
+
+  #[test]
+  fn test_media_types() {
+    assert_eq!(EnvelopeFormat::Plaintext.media_type(), "application/didcomm-plain+json");
+    assert_eq!(EnvelopeFormat::Signed.media_type(), "application/didcomm-signed+json");
+    assert_eq!(EnvelopeFormat::Encrypted.media_type(), "application/didcomm-encrypted+json");
+
+    for format in [EnvelopeFormat::Plaintext, EnvelopeFormat::Signed, EnvelopeFormat::Encrypted] {
+      assert_eq!(EnvelopeFormat::from_media_type(format.media_type()).unwrap(), format);
+    }
+
+    assert_eq!(
+      EnvelopeFormat::from_media_type("Application/DIDComm-Signed+JSON; charset=utf-8").unwrap(),
+      EnvelopeFormat::Signed
+    );
+    assert!(matches!(
+      EnvelopeFormat::from_media_type("application/json"),
+      Err(Error::UnknownMediaType(_))
+    ));
+  }
+
+  #[test]
+  fn test_envelope_round_trip() {
+    let signer: KeyPair = KeyPair::new_ed25519().unwrap();
+    let message: DIDCommMessage<Object> = DIDCommMessage::new("https://didcomm.org/test/1.0/test", Object::new());
+
+    let plaintext: Plaintext = Plaintext::pack_message(&message).unwrap();
+    let signed: Signed = Signed::pack(&message, SignatureAlgorithm::EdDSA, &signer).unwrap();
+
+    assert_eq!(plaintext.format(), EnvelopeFormat::Plaintext);
+    assert_eq!(signed.format(), EnvelopeFormat::Signed);
+
+    for (media_type, data) in [(plaintext.media_type(), plaintext.0), (signed.media_type(), signed.0)] {
+      let envelope: Envelope = Envelope::from_media_type(media_type, data).unwrap();
+      assert_eq!(envelope.media_type(), media_type);
+
+      let unpacked: DIDCommMessage<Object> = match envelope {
+        Envelope::Plaintext(envelope) => envelope.unpack_message().unwrap(),
+        Envelope::Signed(envelope) => envelope.unpack(SignatureAlgorithm::EdDSA, signer.public()).unwrap(),
+        Envelope::Encrypted(_) => panic!("unexpected encrypted envelope"),
+      };
+
+      assert_eq!(unpacked, message);
+    }
+
+    let envelope: Envelope = Envelope::from_media_type(Encrypted::MIME, "{}").unwrap();
+    assert!(matches!(envelope, Envelope::Encrypted(_)));
+    assert_eq!(envelope.format(), EnvelopeFormat::Encrypted);
+  }
+}
@@ -3,11 +3,13 @@
 //! Provides DIDComm message packing utilities
 
 mod encrypted;
+mod format;
 mod plaintext;
 mod signed;
 mod traits;
 
 pub use self::encrypted::*;
+pub use self::format::*;
 pub use self::plaintext::*;
 pub use self::signed::*;
 pub use self::traits::*;
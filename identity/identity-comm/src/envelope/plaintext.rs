@@ -4,9 +4,13 @@
 
 use identity_core::convert::FromJson;
 use identity_core::convert::ToJson;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use crate::envelope::EnvelopeExt;
+use crate::envelope::EnvelopeFormat;
 use crate::error::Result;
+use crate::message::DIDCommMessage;
 
 /// A DIDComm Plaintext Message
 ///
@@ -26,11 +30,22 @@ impl Plaintext {
   pub fn unpack<T: FromJson>(&self) -> Result<T> {
     T::from_json(&self.0).map_err(Into::into)
   }
+
+  /// Packs a typed [`DIDCommMessage`] into a plaintext envelope.
+  pub fn pack_message<T: Serialize>(message: &DIDCommMessage<T>) -> Result<Self> {
+    Self::pack(message)
+  }
+
+  /// Unpacks the envelope as a [`DIDCommMessage`] with a body of type `T`.
+  pub fn unpack_message<T: DeserializeOwned>(&self) -> Result<DIDCommMessage<T>> {
+    self.unpack()
+  }
 }
 
 impl EnvelopeExt for Plaintext {
   const FEXT: &'static str = "dcpm";
   const MIME: &'static str = "application/didcomm-plain+json";
+  const FORMAT: EnvelopeFormat = EnvelopeFormat::Plaintext;
 
   fn as_bytes(&self) -> &[u8] {
     self.0.as_bytes()
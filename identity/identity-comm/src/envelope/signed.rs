@@ -15,6 +15,7 @@ use libjose::jws::JwsFormat;
 use libjose::jws::JwsHeader;
 
 use crate::envelope::EnvelopeExt;
+use crate::envelope::EnvelopeFormat;
 use crate::envelope::Plaintext;
 use crate::error::Result;
 
@@ -70,6 +71,7 @@ impl Signed {
 impl EnvelopeExt for Signed {
   const FEXT: &'static str = "dcsm";
   const MIME: &'static str = "application/didcomm-signed+json";
+  const FORMAT: EnvelopeFormat = EnvelopeFormat::Signed;
 
   fn as_bytes(&self) -> &[u8] {
     self.0.as_bytes()
@@ -1,9 +1,22 @@
 // Copyright 2020-2021 Runnerc
 // SPDX-License-Identifier: Apache-2.0
 //! Media type and file extension constants for DIDComm messages.
+use crate::envelope::EnvelopeFormat;
+
 pub trait EnvelopeExt {
   const FEXT: &'static str;
   const MIME: &'static str;
+  const FORMAT: EnvelopeFormat;
 
   fn as_bytes(&self) -> &[u8];
+
+  /// Returns the [`EnvelopeFormat`] of the packed message.
+  fn format(&self) -> EnvelopeFormat {
+    Self::FORMAT
+  }
+
+  /// Returns the media type of the packed message.
+  fn media_type(&self) -> &'static str {
+    Self::MIME
+  }
 }
@@ -15,4 +15,6 @@ pub enum Error {
   JoseError(#[from] libjose::Error),
   #[error(transparent)]
   Utf8Error(#[from] std::string::FromUtf8Error),
+  #[error("Unknown Media Type: {0}")]
+  UnknownMediaType(String),
 }
@@ -0,0 +1,237 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+//! A typed [DIDComm plaintext message](https://identity.foundation/didcomm-messaging/spec/#plaintext-message-structure)
+
+use identity_core::common::Object;
+use identity_core::common::Url;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::types::Uuid;
+
+/// A DIDComm plaintext message with a typed `body`.
+///
+/// [Reference](https://identity.foundation/didcomm-messaging/spec/#plaintext-message-structure)
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DIDCommMessage<T = Object> {
+  id: Uuid,
+  #[serde(rename = "type")]
+  type_: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  from: Option<Url>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  to: Vec<Url>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  thid: Option<Uuid>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pthid: Option<Uuid>,
+  body: T,
+}
+
+impl<T> DIDCommMessage<T> {
+  /// Creates a new `DIDCommMessage` with the given message `type` URI and `body`.
+  ///
+  /// A random `id` is generated for the message.
+  pub fn new(type_: impl Into<String>, body: T) -> Self {
+    Self {
+      id: Uuid::new_v4(),
+      type_: type_.into(),
+      from: None,
+      to: Vec::new(),
+      thid: None,
+      pthid: None,
+      body,
+    }
+  }
+
+  /// Creates a reply to this message, continuing its thread.
+  ///
+  /// The reply inherits the [`thread id`][Self::thread_id] and parent thread id of this
+  /// message and is addressed to its sender.
+  pub fn reply<U>(&self, type_: impl Into<String>, body: U) -> DIDCommMessage<U> {
+    let mut reply: DIDCommMessage<U> = DIDCommMessage::new(type_, body);
+    reply.thid = Some(*self.thread_id());
+    reply.pthid = self.pthid;
+    reply.to = self.from.iter().cloned().collect();
+    reply
+  }
+
+  /// Creates a message starting a new thread, nested within the thread of this message.
+  ///
+  /// The parent thread id of the new message is set to the [`thread id`][Self::thread_id]
+  /// of this message.
+  pub fn start_thread<U>(&self, type_: impl Into<String>, body: U) -> DIDCommMessage<U> {
+    let mut message: DIDCommMessage<U> = DIDCommMessage::new(type_, body);
+    message.pthid = Some(*self.thread_id());
+    message
+  }
+
+  /// Returns the unique id of the message.
+  pub fn id(&self) -> &Uuid {
+    &self.id
+  }
+
+  /// Returns the message type URI.
+  pub fn type_(&self) -> &str {
+    &self.type_
+  }
+
+  /// Returns the sender of the message.
+  pub fn from(&self) -> Option<&Url> {
+    self.from.as_ref()
+  }
+
+  /// Sets the sender of the message.
+  pub fn set_from(&mut self, value: impl Into<Url>) {
+    self.from = Some(value.into());
+  }
+
+  /// Returns the intended recipients of the message.
+  pub fn to(&self) -> &[Url] {
+    &self.to
+  }
+
+  /// Sets the intended recipients of the message.
+  pub fn set_to(&mut self, value: impl IntoIterator<Item = impl Into<Url>>) {
+    self.to = value.into_iter().map(Into::into).collect();
+  }
+
+  /// Returns the thread id of the message.
+  pub fn thid(&self) -> Option<&Uuid> {
+    self.thid.as_ref()
+  }
+
+  /// Sets the thread id of the message.
+  pub fn set_thid(&mut self, value: impl Into<Uuid>) {
+    self.thid = Some(value.into());
+  }
+
+  /// Returns the parent thread id of the message.
+  pub fn pthid(&self) -> Option<&Uuid> {
+    self.pthid.as_ref()
+  }
+
+  /// Sets the parent thread id of the message.
+  pub fn set_pthid(&mut self, value: impl Into<Uuid>) {
+    self.pthid = Some(value.into());
+  }
+
+  /// Returns the id of the thread this message belongs to.
+  ///
+  /// A message without a `thid` starts a new thread identified by its own `id`.
+  pub fn thread_id(&self) -> &Uuid {
+    self.thid.as_ref().unwrap_or(&self.id)
+  }
+
+  /// Returns a reference to the message body.
+  pub fn body(&self) -> &T {
+    &self.body
+  }
+
+  /// Returns a mutable reference to the message body.
+  pub fn body_mut(&mut self) -> &mut T {
+    &mut self.body
+  }
+
+  /// Consumes the message and returns the body.
+  pub fn into_body(self) -> T {
+    self.body
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use serde_json::Value;
+
+  use super::*;
+  use crate::envelope::EnvelopeExt;
+  use crate::envelope::Plaintext;
+
+  #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+  struct Greeting {
+    text: String,
+  }
+
+  #[test]
+  fn test_plaintext_round_trip() {
+    let mut message: DIDCommMessage<Greeting> = DIDCommMessage::new(
+      "https://didcomm.org/greeting/1.0/hello",
+      Greeting {
+        text: "Hello".to_string(),
+      },
+    );
+    message.set_from(Url::parse("did:runnerc:alice").unwrap());
+    message.set_to(vec![Url::parse("did:runnerc:bob").unwrap()]);
+
+    let plaintext: Plaintext = Plaintext::pack_message(&message).unwrap();
+
+    let json: Value = Value::from_json_slice(plaintext.as_bytes()).unwrap();
+    assert_eq!(json["id"], message.id().to_string());
+    assert_eq!(json["type"], "https://didcomm.org/greeting/1.0/hello");
+    assert_eq!(json["from"], "did:runnerc:alice");
+    assert_eq!(json["to"][0], "did:runnerc:bob");
+    assert_eq!(json["body"]["text"], "Hello");
+
+    let unpacked: DIDCommMessage<Greeting> = plaintext.unpack_message().unwrap();
+    assert_eq!(unpacked, message);
+    assert_eq!(unpacked.body().text, "Hello");
+
+    // An untyped body is deserialized as an `Object`.
+    let untyped: DIDCommMessage = DIDCommMessage::from_json(&message.to_json().unwrap()).unwrap();
+    assert_eq!(untyped.body()["text"], "Hello");
+  }
+
+  #[test]
+  fn test_optional_fields_omitted() {
+    let message: DIDCommMessage<Object> = DIDCommMessage::new("https://didcomm.org/empty/1.0/empty", Object::new());
+    let json: Value = Value::from_json(&message.to_json().unwrap()).unwrap();
+
+    assert!(json.get("from").is_none());
+    assert!(json.get("to").is_none());
+    assert!(json.get("thid").is_none());
+    assert!(json.get("pthid").is_none());
+  }
+
+  #[test]
+  fn test_reply_inherits_thread() {
+    let mut request: DIDCommMessage<Object> =
+      DIDCommMessage::new("https://didcomm.org/test/1.0/request", Object::new());
+    request.set_from(Url::parse("did:runnerc:alice").unwrap());
+
+    // The first message of a thread is identified by its own id.
+    assert_eq!(request.thid(), None);
+    assert_eq!(request.thread_id(), request.id());
+
+    let response: DIDCommMessage<Object> = request.reply("https://didcomm.org/test/1.0/response", Object::new());
+    assert_ne!(response.id(), request.id());
+    assert_eq!(response.thid(), Some(request.id()));
+    assert_eq!(response.thread_id(), request.thread_id());
+    assert_eq!(response.to(), &[Url::parse("did:runnerc:alice").unwrap()]);
+
+    // Replies to replies stay in the same thread.
+    let ack: DIDCommMessage<Object> = response.reply("https://didcomm.org/test/1.0/ack", Object::new());
+    assert_eq!(ack.thid(), Some(request.id()));
+
+    let json: Value = Value::from_json(&response.to_json().unwrap()).unwrap();
+    assert_eq!(json["thid"], request.id().to_string());
+    let decoded: DIDCommMessage<Object> = DIDCommMessage::from_json_value(json).unwrap();
+    assert_eq!(decoded.thid(), Some(request.id()));
+  }
+
+  #[test]
+  fn test_start_thread() {
+    let parent: DIDCommMessage<Object> = DIDCommMessage::new("https://didcomm.org/test/1.0/request", Object::new());
+    let child: DIDCommMessage<Object> = parent.start_thread("https://didcomm.org/test/1.0/nested", Object::new());
+
+    assert_eq!(child.thid(), None);
+    assert_eq!(child.pthid(), Some(parent.id()));
+    assert_eq!(child.thread_id(), child.id());
+
+    // Replies within the nested thread keep the parent thread id.
+    let reply: DIDCommMessage<Object> = child.reply("https://didcomm.org/test/1.0/reply", Object::new());
+    assert_eq!(reply.thid(), Some(child.id()));
+    assert_eq!(reply.pthid(), Some(parent.id()));
+  }
+}
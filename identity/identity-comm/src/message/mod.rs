@@ -1,6 +1,10 @@
 // Copyright 2020-2021 Runnerc
 // SPDX-License-Identifier: Apache-2.0
 
+mod didcomm_message;
 mod traits;
+mod trust_ping;
 
+pub use self::didcomm_message::*;
 pub use self::traits::*;
+pub use self::trust_ping::*;
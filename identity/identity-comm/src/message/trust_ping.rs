@@ -0,0 +1,106 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+//! Message types of the [Trust Ping protocol](https://identity.foundation/didcomm-messaging/spec/#trust-ping-protocol-20)
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::message::DIDCommMessage;
+
+/// The body of a trust-ping message.
+///
+/// [Reference](https://identity.foundation/didcomm-messaging/spec/#trust-ping-protocol-20)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TrustPing {
+  #[serde(default = "TrustPing::default_response_requested")]
+  response_requested: bool,
+}
+
+impl TrustPing {
+  /// The message type URI of a trust-ping.
+  pub const TYPE: &'static str = "https://didcomm.org/trust-ping/2.0/ping";
+
+  /// Creates a new trust-ping message.
+  pub fn message(response_requested: bool) -> DIDCommMessage<Self> {
+    DIDCommMessage::new(Self::TYPE, Self { response_requested })
+  }
+
+  /// Returns `true` if the sender requests a [`TrustPingResponse`].
+  pub fn response_requested(&self) -> bool {
+    self.response_requested
+  }
+
+  /// Sets whether the sender requests a [`TrustPingResponse`].
+  pub fn set_response_requested(&mut self, value: bool) {
+    self.response_requested = value;
+  }
+
+  const fn default_response_requested() -> bool {
+    true
+  }
+}
+
+/// The body of a trust-ping response message.
+///
+/// [Reference](https://identity.foundation/didcomm-messaging/spec/#trust-ping-protocol-20)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TrustPingResponse {}
+
+impl TrustPingResponse {
+  /// The message type URI of a trust-ping response.
+  pub const TYPE: &'static str = "https://didcomm.org/trust-ping/2.0/ping-response";
+
+  /// Creates a response to the given trust-ping, continuing its thread.
+  pub fn reply_to(ping: &DIDCommMessage<TrustPing>) -> DIDCommMessage<Self> {
+    ping.reply(Self::TYPE, Self {})
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Url;
+  use identity_core::crypto::KeyPair;
+
+  use super::*;
+  use crate::envelope::SignatureAlgorithm;
+  use crate::envelope::Signed;
+
+  #[test]
+  fn test_trust_ping_signed() {
+    let alice: KeyPair = KeyPair::new_ed25519().unwrap();
+    let bob: KeyPair = KeyPair::new_ed25519().unwrap();
+
+    let mut ping: DIDCommMessage<TrustPing> = TrustPing::message(true);
+    ping.set_from(Url::parse("did:runnerc:alice").unwrap());
+    ping.set_to(vec![Url::parse("did:runnerc:bob").unwrap()]);
+
+    let signed: Signed = Signed::pack(&ping, SignatureAlgorithm::EdDSA, &alice).unwrap();
+    let received: DIDCommMessage<TrustPing> = signed.unpack(SignatureAlgorithm::EdDSA, alice.public()).unwrap();
+
+    assert_eq!(received, ping);
+    assert_eq!(received.type_(), TrustPing::TYPE);
+    assert!(received.body().response_requested());
+
+    let response: DIDCommMessage<TrustPingResponse> = TrustPingResponse::reply_to(&received);
+    let signed: Signed = Signed::pack(&response, SignatureAlgorithm::EdDSA, &bob).unwrap();
+    let received: DIDCommMessage<TrustPingResponse> = signed.unpack(SignatureAlgorithm::EdDSA, bob.public()).unwrap();
+
+    assert_eq!(received.type_(), TrustPingResponse::TYPE);
+    assert_eq!(received.thid(), Some(ping.id()));
+    assert_eq!(received.to(), &[Url::parse("did:runnerc:alice").unwrap()]);
+
+    // The signature does not verify with another key.
+    assert!(signed
+      .unpack::<DIDCommMessage<TrustPingResponse>>(SignatureAlgorithm::EdDSA, alice.public())
+      .is_err());
+  }
+
+  #[test]
+  fn test_response_requested_default() {
+    let ping: TrustPing = serde_json::from_str("{}").unwrap();
+    assert!(ping.response_requested());
+
+    let ping: TrustPing = serde_json::from_str(r#"{"response_requested":false}"#).unwrap();
+    assert!(!ping.response_requested());
+  }
+}
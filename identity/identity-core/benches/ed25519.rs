@@ -0,0 +1,48 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use identity_core::crypto::Ed25519;
+use identity_core::crypto::KeyPair;
+use identity_core::crypto::Sign;
+use identity_core::crypto::Verify;
+
+fn bench_verify(c: &mut Criterion) {
+  let mut group = c.benchmark_group("ed25519 verify");
+
+  for size in [8, 64, 256] {
+    let keypairs: Vec<KeyPair> = (0..size).map(|_| KeyPair::new_ed25519().unwrap()).collect();
+    let messages: Vec<Vec<u8>> = (0..size).map(|index: usize| index.to_be_bytes().to_vec()).collect();
+    let signatures: Vec<[u8; 64]> = keypairs
+      .iter()
+      .zip(messages.iter())
+      .map(|(keypair, message)| Ed25519::sign(message, keypair.private()).unwrap())
+      .collect();
+    let items: Vec<(&[u8], &[u8], &[u8])> = messages
+      .iter()
+      .zip(signatures.iter())
+      .zip(keypairs.iter())
+      .map(|((message, signature), keypair)| (&message[..], &signature[..], keypair.public().as_ref()))
+      .collect();
+
+    group.bench_with_input(BenchmarkId::new("single", size), &items, |b, items| {
+      b.iter(|| {
+        for (message, signature, key) in items {
+          Ed25519::verify(message, signature, *key).unwrap();
+        }
+      })
+    });
+
+    group.bench_with_input(BenchmarkId::new("batch", size), &items, |b, items| {
+      b.iter(|| Ed25519::verify_batch(items).unwrap())
+    });
+  }
+
+  group.finish();
+}
+
+criterion_group!(benches, bench_verify);
+criterion_main!(benches);
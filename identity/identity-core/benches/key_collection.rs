@@ -0,0 +1,53 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BatchSize;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use identity_core::crypto::merkle_key::Sha256;
+use identity_core::crypto::merkle_tree::MerkleFrontier;
+use identity_core::crypto::KeyCollection;
+use identity_core::crypto::PrivateKey;
+use identity_core::crypto::PublicKey;
+
+fn bench_append(c: &mut Criterion) {
+  let mut group = c.benchmark_group("key collection append");
+
+  for size in [256, 1024, 2048] {
+    let keys: KeyCollection = KeyCollection::new_ed25519(size).unwrap();
+    let frontier: MerkleFrontier<Sha256> = keys.merkle_frontier();
+    let (public, private): (PublicKey, PrivateKey) = KeyCollection::new_ed25519(1).unwrap().into_iter().next().unwrap();
+
+    group.bench_with_input(BenchmarkId::new("rebuild", size), &keys, |b, keys| {
+      b.iter_batched(
+        || (keys.clone(), public.clone(), private.clone()),
+        |(mut keys, public, private)| {
+          // Without a retained frontier, the whole tree is hashed to append and to compute the root.
+          let mut frontier: MerkleFrontier<Sha256> = keys.merkle_frontier();
+          keys.append(public, private, &mut frontier).unwrap();
+          black_box(keys.merkle_root::<Sha256>())
+        },
+        BatchSize::SmallInput,
+      )
+    });
+
+    group.bench_with_input(BenchmarkId::new("incremental", size), &keys, |b, keys| {
+      b.iter_batched(
+        || (keys.clone(), frontier.clone(), public.clone(), private.clone()),
+        |(mut keys, mut frontier, public, private)| {
+          keys.append(public, private, &mut frontier).unwrap();
+          black_box(frontier.root())
+        },
+        BatchSize::SmallInput,
+      )
+    });
+  }
+
+  group.finish();
+}
+
+criterion_group!(benches, bench_append);
+criterion_main!(benches);
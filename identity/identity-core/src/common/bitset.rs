@@ -2,7 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::fmt::Formatter;
+use std::io::Read;
+use std::io::Write;
 
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use roaring::RoaringBitmap;
 use serde::de;
 use serde::de::Deserializer;
@@ -17,6 +22,15 @@ use crate::error::Result;
 use crate::utils::decode_b64;
 use crate::utils::encode_b64;
 
+/// The minimum size in bytes of an encoded status list bitstring (16KB), as required by
+/// [StatusList2021](https://w3c-ccg.github.io/vc-status-list-2021/#bitstring-encoding) for
+/// group privacy.
+const STATUS_LIST_MIN_BYTES: usize = 16 * 1024;
+
+/// The maximum size in bytes of a decoded status list bitstring (16MB), bounding the memory
+/// used for a compressed `encodedList`.
+const STATUS_LIST_MAX_BYTES: usize = 16 * 1024 * 1024;
+
 /// A general-purpose compressed bitset.
 #[derive(Clone, Debug, PartialEq)]
 pub struct BitSet(RoaringBitmap);
@@ -92,6 +106,74 @@ impl BitSet {
       .map_err(Error::DecodeBitmap)
       .map(Self)
   }
+
+  /// Encodes the [`BitSet`] as a [StatusList2021](https://w3c-ccg.github.io/vc-status-list-2021/)
+  /// `encodedList`: a GZIP-compressed, base64url-encoded bitstring of at least 16KB, where
+  /// index `0` is the left-most bit of the first byte.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the bitstring would exceed the maximum size of 16MB, i.e. the set contains an
+  /// index of `2^27` or above.
+  pub fn to_encoded_list(&self) -> Result<String> {
+    let len: usize = self
+      .0
+      .max()
+      .map(|index| index as usize / 8 + 1)
+      .unwrap_or_default()
+      .max(STATUS_LIST_MIN_BYTES);
+
+    if len > STATUS_LIST_MAX_BYTES {
+      return Err(Error::EncodeStatusList(std::io::ErrorKind::InvalidInput.into()));
+    }
+
+    let mut bitstring: Vec<u8> = vec![0; len];
+
+    for index in self.0.iter() {
+      bitstring[index as usize / 8] |= 0x80 >> (index % 8);
+    }
+
+    let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&bitstring).map_err(Error::EncodeStatusList)?;
+    let compressed: Vec<u8> = encoder.finish().map_err(Error::EncodeStatusList)?;
+
+    Ok(base64::encode_config(&compressed, base64::URL_SAFE_NO_PAD))
+  }
+
+  /// Decodes a [`BitSet`] from a [StatusList2021](https://w3c-ccg.github.io/vc-status-list-2021/)
+  /// `encodedList`. See [`BitSet::to_encoded_list`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the list is not valid or its bitstring exceeds the maximum size of 16MB.
+  pub fn from_encoded_list(data: &str) -> Result<Self> {
+    let compressed: Vec<u8> = base64::decode_config(data.trim_end_matches('='), base64::URL_SAFE_NO_PAD)?;
+
+    // Read one byte past the limit to detect oversized bitstrings without decompressing them.
+    let mut bitstring: Vec<u8> = Vec::new();
+    GzDecoder::new(compressed.as_slice())
+      .take(STATUS_LIST_MAX_BYTES as u64 + 1)
+      .read_to_end(&mut bitstring)
+      .map_err(Error::DecodeStatusList)?;
+
+    if bitstring.len() > STATUS_LIST_MAX_BYTES {
+      return Err(Error::DecodeStatusList(std::io::ErrorKind::InvalidData.into()));
+    }
+
+    let mut this: Self = Self::new();
+
+    for (position, byte) in bitstring.iter().enumerate().filter(|(_, byte)| **byte != 0) {
+      for bit in 0..8 {
+        if byte & (0x80 >> bit) != 0 {
+          let index: u32 = u32::try_from(position * 8 + bit)
+            .map_err(|_| Error::DecodeStatusList(std::io::ErrorKind::InvalidData.into()))?;
+          this.0.insert(index);
+        }
+      }
+    }
+
+    Ok(this)
+  }
 }
 
 impl Default for BitSet {
@@ -194,6 +276,62 @@ mod tests {
     );
   }
 
+  // Validate that a `from_encoded_list` ∘ `to_encoded_list` round-trip results in the original bitset.
+  #[test]
+  fn test_encoded_list_round_trip() {
+    let mut set = BitSet::new();
+    set.insert_all([0, 1, 7, 8, 42, 1_000, 65_535, 131_071, 131_072, 1_000_000]);
+
+    let encoded: String = set.to_encoded_list().unwrap();
+    assert!(!encoded.contains('='));
+    assert_eq!(BitSet::from_encoded_list(&encoded).unwrap(), set);
+
+    // An empty set is encoded as a bitstring of the minimum size.
+    let empty: BitSet = BitSet::new();
+    let encoded: String = empty.to_encoded_list().unwrap();
+    assert_eq!(BitSet::from_encoded_list(&encoded).unwrap(), empty);
+  }
+
+  // Validate the bit order and size of the decoded bitstring.
+  #[test]
+  fn test_encoded_list_bitstring() {
+    let mut set = BitSet::new();
+    set.insert_all([0, 9, 15]);
+
+    let compressed: Vec<u8> = base64::decode_config(set.to_encoded_list().unwrap(), base64::URL_SAFE_NO_PAD).unwrap();
+    let mut bitstring: Vec<u8> = Vec::new();
+    GzDecoder::new(compressed.as_slice()).read_to_end(&mut bitstring).unwrap();
+
+    assert_eq!(bitstring.len(), STATUS_LIST_MIN_BYTES);
+    assert_eq!(&bitstring[..3], &[0b1000_0000, 0b0100_0001, 0]);
+    assert!(bitstring[3..].iter().all(|byte| *byte == 0));
+  }
+
+  #[test]
+  fn test_encoded_list_invalid() {
+    assert!(BitSet::from_encoded_list("not a status list").is_err());
+    assert!(BitSet::from_encoded_list(&base64::encode_config(b"not gzip", base64::URL_SAFE_NO_PAD)).is_err());
+  }
+
+  #[test]
+  fn test_encoded_list_max_size() {
+    let max_index: u32 = (STATUS_LIST_MAX_BYTES * 8 - 1) as u32;
+
+    let mut set = BitSet::new();
+    set.insert(max_index);
+    assert_eq!(BitSet::from_encoded_list(&set.to_encoded_list().unwrap()).unwrap(), set);
+
+    set.insert(max_index + 1);
+    assert!(set.to_encoded_list().is_err());
+
+    // A small list that decompresses to an oversized bitstring.
+    let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&vec![0; STATUS_LIST_MAX_BYTES + 1]).unwrap();
+    let compressed: Vec<u8> = encoder.finish().unwrap();
+
+    assert!(BitSet::from_encoded_list(&base64::encode_config(&compressed, base64::URL_SAFE_NO_PAD)).is_err());
+  }
+
   // Validate that a `deserialize_slice` ∘ `serialize_vec` round-trip results in the original bitset.
   #[test]
   fn test_serialize_slice_round_trip() {
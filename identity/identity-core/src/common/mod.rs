@@ -15,7 +15,9 @@ pub use self::bitset::BitSet;
 pub use self::context::Context;
 pub use self::fragment::Fragment;
 pub use self::object::Object;
+pub use self::object::ObjectExt;
 pub use self::object::Value;
+pub use self::one_or_many::DiffOneOrMany;
 pub use self::one_or_many::OneOrMany;
 pub use self::timestamp::Timestamp;
 pub use self::url::Url;
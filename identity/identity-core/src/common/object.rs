@@ -3,8 +3,140 @@
 
 use std::collections::BTreeMap;
 
+use crate::utils::split_pointer;
+
 #[doc(inline)]
 pub use serde_json::Value;
 
 /// An alias for an ordered map of key-[value][`Value`] pairs.
 pub type Object = BTreeMap<String, Value>;
+
+/// Extension methods for [`Object`].
+pub trait ObjectExt {
+  /// Recursively merges `other` into `self`.
+  ///
+  /// Nested objects present in both are merged key by key. In all other cases, including
+  /// arrays and scalars, the value from `other` replaces the value in `self`.
+  fn deep_merge(&mut self, other: Object);
+
+  /// Looks up a value by a JSON Pointer ([RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901)),
+  /// e.g. `"/address/geo/lat"`.
+  ///
+  /// Returns `None` if `pointer` is malformed or does not reference a value. The empty pointer is
+  /// not supported since an [`Object`] is not a [`Value`].
+  fn pointer(&self, pointer: &str) -> Option<&Value>;
+}
+
+impl ObjectExt for Object {
+  fn deep_merge(&mut self, other: Object) {
+    for (key, value) in other {
+      match self.get_mut(&key) {
+        Some(current) => merge_value(current, value),
+        None => {
+          self.insert(key, value);
+        }
+      }
+    }
+  }
+
+  fn pointer(&self, pointer: &str) -> Option<&Value> {
+    let (key, pointer): (String, &str) = split_pointer(pointer)?;
+
+    self.get(&key)?.pointer(pointer)
+  }
+}
+
+fn merge_value(target: &mut Value, patch: Value) {
+  match (target, patch) {
+    (Value::Object(target), Value::Object(patch)) => {
+      for (key, value) in patch {
+        match target.get_mut(&key) {
+          Some(current) => merge_value(current, value),
+          None => {
+            target.insert(key, value);
+          }
+        }
+      }
+    }
+    (target, patch) => *target = patch,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::convert::FromJson;
+  use crate::json;
+
+  fn object(value: Value) -> Object {
+    Object::from_json_value(value).unwrap()
+  }
+
+  #[test]
+  fn test_deep_merge_nested() {
+    let mut base: Object = object(json!({
+      "name": "Alice",
+      "address": { "city": "Berlin", "geo": { "lat": 52, "lon": 13 } },
+    }));
+    let patch: Object = object(json!({
+      "age": 42,
+      "address": { "zip": "10115", "geo": { "lat": 53 } },
+    }));
+
+    base.deep_merge(patch);
+
+    assert_eq!(
+      base,
+      object(json!({
+        "name": "Alice",
+        "age": 42,
+        "address": { "city": "Berlin", "zip": "10115", "geo": { "lat": 53, "lon": 13 } },
+      }))
+    );
+  }
+
+  #[test]
+  fn test_pointer() {
+    let object: Object = object(json!({
+      "address": { "geo": { "lat": 52 }, "lines": ["a", "b"] },
+      "a/b": { "c~d": true },
+    }));
+
+    assert_eq!(object.pointer("/address/geo/lat"), Some(&json!(52)));
+    assert_eq!(object.pointer("/address/lines/1"), Some(&json!("b")));
+    assert_eq!(object.pointer("/address"), Some(&object["address"]));
+    assert_eq!(object.pointer("/a~1b/c~0d"), Some(&json!(true)));
+
+    assert_eq!(object.pointer("/address/lines/2"), None);
+    assert_eq!(object.pointer("/missing"), None);
+    assert_eq!(object.pointer("address"), None);
+    assert_eq!(object.pointer(""), None);
+  }
+
+  #[test]
+  fn test_deep_merge_overwrite() {
+    let mut base: Object = object(json!({
+      "tags": ["a", "b", "c"],
+      "scalar": { "nested": true },
+      "nested": "scalar",
+      "keep": null,
+    }));
+    let patch: Object = object(json!({
+      "tags": ["d"],
+      "scalar": 1,
+      "nested": { "value": 2 },
+    }));
+
+    base.deep_merge(patch);
+
+    assert_eq!(
+      base,
+      object(json!({
+        "tags": ["d"],
+        "scalar": 1,
+        "nested": { "value": 2 },
+        "keep": null,
+      }))
+    );
+  }
+}
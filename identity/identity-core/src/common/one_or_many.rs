@@ -9,6 +9,12 @@ use core::hash::Hash;
 use core::mem::replace;
 use core::ops::Deref;
 use core::slice::from_ref;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::diff;
+use crate::diff::Diff;
+use crate::diff::DiffVec;
 
 /// A generic container that stores exactly one or many (0+) values of a given type.
 #[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
@@ -168,6 +174,68 @@ impl<T> From<OneOrMany<T>> for Vec<T> {
   }
 }
 
+// =============================================================================
+// Diff
+// =============================================================================
+
+/// The [`Diff`] type of [`OneOrMany`].
+///
+/// The elements are diffed as a `Vec`, tagged with the variant of the updated value so that a
+/// [`Many`][OneOrMany::Many] with a single element is merged as such.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum DiffOneOrMany<T: Diff> {
+  /// The diff of the element of an updated [`One`][OneOrMany::One].
+  One(DiffVec<T>),
+  /// The diff of the elements of an updated [`Many`][OneOrMany::Many].
+  Many(DiffVec<T>),
+}
+
+/// `One` and `Many` are diffed uniformly as a `Vec`; the variant of the updated value is kept.
+impl<T> Diff for OneOrMany<T>
+where
+  T: Diff + Serialize + for<'de> Deserialize<'de>,
+{
+  type Type = DiffOneOrMany<T>;
+
+  fn diff(&self, other: &Self) -> diff::Result<Self::Type> {
+    let diff: DiffVec<T> = self.to_vec().diff(&other.to_vec())?;
+
+    match other {
+      Self::One(_) => Ok(DiffOneOrMany::One(diff)),
+      Self::Many(_) => Ok(DiffOneOrMany::Many(diff)),
+    }
+  }
+
+  fn merge(&self, diff: Self::Type) -> diff::Result<Self> {
+    match diff {
+      DiffOneOrMany::One(diff) => self.to_vec().merge(diff).and_then(try_one),
+      DiffOneOrMany::Many(diff) => self.to_vec().merge(diff).map(Self::Many),
+    }
+  }
+
+  fn from_diff(diff: Self::Type) -> diff::Result<Self> {
+    match diff {
+      DiffOneOrMany::One(diff) => Vec::from_diff(diff).and_then(try_one),
+      DiffOneOrMany::Many(diff) => Vec::from_diff(diff).map(Self::Many),
+    }
+  }
+
+  fn into_diff(self) -> diff::Result<Self::Type> {
+    match self {
+      Self::One(value) => vec![value].into_diff().map(DiffOneOrMany::One),
+      Self::Many(values) => values.into_diff().map(DiffOneOrMany::Many),
+    }
+  }
+}
+
+/// Converts the merged `values` of a [`DiffOneOrMany::One`] to a [`OneOrMany::One`].
+fn try_one<T>(mut values: Vec<T>) -> diff::Result<OneOrMany<T>> {
+  match (values.pop(), values.is_empty()) {
+    (Some(value), true) => Ok(OneOrMany::One(value)),
+    _ => Err(diff::Error::merge("OneOrMany::One requires exactly one element")),
+  }
+}
+
 // =============================================================================
 // Iterator
 // =============================================================================
@@ -191,3 +259,91 @@ impl<'a, T> Iterator for OneOrManyIter<'a, T> {
     self.inner.get(self.index - 1)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn one() -> OneOrMany<String> {
+    OneOrMany::One("did:example:123".into())
+  }
+
+  fn many() -> OneOrMany<String> {
+    OneOrMany::Many(vec!["did:example:123".into(), "did:example:456".into()])
+  }
+
+  /// Returns the number of recorded changes of `diff`.
+  fn changes(diff: &DiffOneOrMany<String>) -> usize {
+    match diff {
+      DiffOneOrMany::One(diff) | DiffOneOrMany::Many(diff) => diff.0.len(),
+    }
+  }
+
+  #[test]
+  fn test_diff_one_to_many() {
+    let diff: DiffOneOrMany<String> = one().diff(&many()).unwrap();
+    // Only the added element is recorded.
+    assert_eq!(changes(&diff), 1);
+    assert_eq!(one().merge(diff).unwrap(), many());
+
+    let diff: DiffOneOrMany<String> = many().diff(&one()).unwrap();
+    assert_eq!(many().merge(diff).unwrap(), one());
+  }
+
+  #[test]
+  fn test_diff_many_add_element() {
+    let mut updated: OneOrMany<String> = many();
+    updated.push("did:example:789".into());
+
+    let diff: DiffOneOrMany<String> = many().diff(&updated).unwrap();
+    assert_eq!(changes(&diff), 1);
+    assert_eq!(many().merge(diff).unwrap(), updated);
+  }
+
+  #[test]
+  fn test_diff_mutate_element() {
+    let mut updated: OneOrMany<String> = many();
+    *updated.get_mut(1).unwrap() = "did:example:abc".into();
+
+    let diff: DiffOneOrMany<String> = many().diff(&updated).unwrap();
+    assert_eq!(changes(&diff), 1);
+    assert_eq!(many().merge(diff).unwrap(), updated);
+    assert_eq!(
+      OneOrMany::from_diff(updated.clone().into_diff().unwrap()).unwrap(),
+      updated
+    );
+
+    let diff: DiffOneOrMany<String> = one().diff(&OneOrMany::One("did:example:abc".into())).unwrap();
+    assert_eq!(one().merge(diff).unwrap(), OneOrMany::One("did:example:abc".into()));
+  }
+
+  #[test]
+  fn test_diff_many_single_element() {
+    let single: OneOrMany<String> = OneOrMany::Many(vec!["did:example:123".into()]);
+
+    // A `Many` with a single element is not merged into a `One`.
+    for original in [one(), many(), OneOrMany::Many(Vec::new())] {
+      let diff: DiffOneOrMany<String> = original.diff(&single).unwrap();
+      assert_eq!(original.merge(diff).unwrap(), single);
+
+      let diff: DiffOneOrMany<String> = single.diff(&original).unwrap();
+      assert_eq!(single.merge(diff).unwrap(), original);
+    }
+
+    assert_eq!(
+      OneOrMany::from_diff(single.clone().into_diff().unwrap()).unwrap(),
+      single
+    );
+    assert_eq!(OneOrMany::from_diff(one().into_diff().unwrap()).unwrap(), one());
+
+    // The merged value is still serialized as an array.
+    let merged: OneOrMany<String> = one().merge(one().diff(&single).unwrap()).unwrap();
+    assert_eq!(serde_json::to_string(&merged).unwrap(), r#"["did:example:123"]"#);
+  }
+
+  #[test]
+  fn test_diff_same() {
+    assert_eq!(changes(&many().diff(&many()).unwrap()), 0);
+    assert_eq!(changes(&one().diff(&one()).unwrap()), 0);
+  }
+}
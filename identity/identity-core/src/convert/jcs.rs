@@ -0,0 +1,338 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+//! An implementation of the [JSON Canonicalization Scheme (JCS)][RFC8785].
+//!
+//! [RFC8785]: https://datatracker.ietf.org/doc/html/rfc8785
+
+use core::fmt::Write;
+
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Number;
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Serializes `data` as a JSON byte vector canonicalized according to [RFC 8785][RFC8785].
+///
+/// [RFC8785]: https://datatracker.ietf.org/doc/html/rfc8785
+pub(crate) fn to_vec<T>(data: &T) -> Result<Vec<u8>>
+where
+  T: Serialize + ?Sized,
+{
+  let value: Value = serde_json::to_value(data).map_err(Error::EncodeJSON)?;
+  let mut output: String = String::new();
+
+  write_value(&mut output, &value);
+
+  Ok(output.into_bytes())
+}
+
+fn write_value(output: &mut String, value: &Value) {
+  match value {
+    Value::Null => output.push_str("null"),
+    Value::Bool(true) => output.push_str("true"),
+    Value::Bool(false) => output.push_str("false"),
+    Value::Number(number) => write_number(output, number),
+    Value::String(string) => write_string(output, string),
+    Value::Array(array) => {
+      output.push('[');
+
+      for (index, item) in array.iter().enumerate() {
+        if index > 0 {
+          output.push(',');
+        }
+
+        write_value(output, item);
+      }
+
+      output.push(']');
+    }
+    Value::Object(object) => write_object(output, object),
+  }
+}
+
+/// Writes the members of `object` sorted by the UTF-16 code units of their names (RFC 8785, 3.2.3).
+fn write_object(output: &mut String, object: &Map<String, Value>) {
+  let mut members: Vec<(&String, &Value)> = object.iter().collect();
+  members.sort_by(|(lhs, _), (rhs, _)| lhs.encode_utf16().cmp(rhs.encode_utf16()));
+
+  output.push('{');
+
+  for (index, (key, value)) in members.into_iter().enumerate() {
+    if index > 0 {
+      output.push(',');
+    }
+
+    write_string(output, key);
+    output.push(':');
+    write_value(output, value);
+  }
+
+  output.push('}');
+}
+
+/// Writes `string` with only the mandatory JSON escapes (RFC 8785, 3.2.2.2).
+fn write_string(output: &mut String, string: &str) {
+  output.push('"');
+
+  for char in string.chars() {
+    match char {
+      '"' => output.push_str("\\\""),
+      '\\' => output.push_str("\\\\"),
+      '\u{0008}' => output.push_str("\\b"),
+      '\u{0009}' => output.push_str("\\t"),
+      '\u{000a}' => output.push_str("\\n"),
+      '\u{000c}' => output.push_str("\\f"),
+      '\u{000d}' => output.push_str("\\r"),
+      '\u{0000}'..='\u{001f}' => {
+        // Writing to a `String` cannot fail.
+        let _ = write!(output, "\\u{:04x}", char as u32);
+      }
+      _ => output.push(char),
+    }
+  }
+
+  output.push('"');
+}
+
+/// Writes `number` as an IEEE 754 double in the format of the ECMAScript
+/// `Number.prototype.toString` method (RFC 8785, 3.2.2.3).
+///
+/// Integers are converted to doubles, so integers beyond 2^53 lose precision like in ECMAScript.
+fn write_number(output: &mut String, number: &Number) {
+  // `serde_json` numbers are always finite.
+  let value: f64 = number.as_f64().unwrap_or_default();
+
+  if value == 0.0 {
+    // Includes negative zero.
+    output.push('0');
+    return;
+  }
+
+  if value.is_sign_negative() {
+    output.push('-');
+  }
+
+  // The shortest representation that round-trips, as `<digit>[.<digits>]e<exponent>`.
+  let scientific: String = format!("{:e}", value.abs());
+  let (mantissa, exponent): (&str, &str) = scientific.split_once('e').expect("infallible");
+  let digits: String = round_half_even(value.abs(), mantissa.replace('.', ""));
+  let exponent: i32 = exponent.parse().expect("infallible");
+
+  // The position of the decimal point relative to the start of `digits`.
+  let k: i32 = digits.len() as i32;
+  let n: i32 = exponent + 1;
+
+  if k <= n && n <= 21 {
+    output.push_str(&digits);
+    output.extend((0..n - k).map(|_| '0'));
+  } else if 0 < n && n <= 21 {
+    output.push_str(&digits[..n as usize]);
+    output.push('.');
+    output.push_str(&digits[n as usize..]);
+  } else if -6 < n && n <= 0 {
+    output.push_str("0.");
+    output.extend((0..-n).map(|_| '0'));
+    output.push_str(&digits);
+  } else {
+    output.push_str(&digits[..1]);
+
+    if k > 1 {
+      output.push('.');
+      output.push_str(&digits[1..]);
+    }
+
+    output.push('e');
+    output.push(if n > 0 { '+' } else { '-' });
+    output.push_str(&(n - 1).abs().to_string());
+  }
+}
+
+/// Adjusts the shortest `digits` of `value` produced by the standard library, which rounds ties
+/// away from zero, to the even candidate required by ECMAScript.
+fn round_half_even(value: f64, mut digits: String) -> String {
+  let last: u8 = digits.as_bytes()[digits.len() - 1];
+
+  if digits.len() < 2 || matches!(last, b'0' | b'2' | b'4' | b'6' | b'8') || !may_tie(value) {
+    return digits;
+  }
+
+  // The exact decimal expansion of `value`; every finite double has fewer than 800 significant digits.
+  let exact: String = format!("{:.800e}", value);
+  let exact: String = exact[..exact.find('e').expect("infallible")].replace('.', "");
+
+  digits.pop();
+  digits.push(char::from(last - 1));
+
+  // `value` lies exactly halfway between the two candidates.
+  let tie: bool = exact.starts_with(&digits)
+    && exact.as_bytes()[digits.len()] == b'5'
+    && exact[digits.len() + 1..].bytes().all(|byte| byte == b'0');
+
+  if !tie {
+    digits.pop();
+    digits.push(char::from(last));
+  }
+
+  digits
+}
+
+/// Returns `false` if `value` cannot lie exactly halfway between two candidates of the shortest length.
+///
+/// Integers up to 2^53 are printed exactly. Otherwise a tie requires an exact decimal expansion of at
+/// most 18 significant digits, while `m * 2^e` with an odd `m` and `e < 0` has as many as `m * 5^-e`,
+/// i.e. at least 19 once `e <= -26`.
+fn may_tie(value: f64) -> bool {
+  const FRACTION: u64 = (1 << 52) - 1;
+  const MAX_EXACT: f64 = 9007199254740992.0;
+
+  let bits: u64 = value.to_bits();
+  let biased: i32 = ((bits >> 52) & 0x7ff) as i32;
+  let mantissa: u64 = if biased == 0 {
+    bits & FRACTION
+  } else {
+    (bits & FRACTION) | (1 << 52)
+  };
+  let exponent: i32 = biased.max(1) - 1075 + mantissa.trailing_zeros() as i32;
+
+  if exponent >= 0 {
+    value > MAX_EXACT
+  } else {
+    exponent >= -25
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+  use serde_json::Value;
+
+  use super::may_tie;
+  use super::to_vec;
+
+  fn number(bits: u64) -> String {
+    String::from_utf8(to_vec(&f64::from_bits(bits)).unwrap()).unwrap()
+  }
+
+  fn canonicalize(json: &str) -> String {
+    let value: Value = serde_json::from_str(json).unwrap();
+    String::from_utf8(to_vec(&value).unwrap()).unwrap()
+  }
+
+  #[test]
+  fn test_numbers() {
+    // RFC 8785, Appendix B.
+    let vectors: &[(u64, &str)] = &[
+      (0x0000000000000000, "0"),
+      (0x8000000000000000, "0"),
+      (0x0000000000000001, "5e-324"),
+      (0x8000000000000001, "-5e-324"),
+      (0x7fefffffffffffff, "1.7976931348623157e+308"),
+      (0xffefffffffffffff, "-1.7976931348623157e+308"),
+      (0x4340000000000000, "9007199254740992"),
+      (0xc340000000000000, "-9007199254740992"),
+      (0x4430000000000000, "295147905179352830000"),
+      (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+      (0x44b52d02c7e14af6, "1e+23"),
+      (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+      (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+      (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+      (0x444b1ae4d6e2ef50, "1e+21"),
+      (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+      (0x3eb0c6f7a0b5ed8d, "0.000001"),
+      (0x41b3de4355555553, "333333333.3333332"),
+      (0x41b3de4355555554, "333333333.33333325"),
+      (0x41b3de4355555555, "333333333.3333333"),
+      (0x41b3de4355555556, "333333333.3333334"),
+      (0x41b3de4355555557, "333333333.33333343"),
+      (0xbecbf647612f3696, "-0.0000033333333333333333"),
+      (0x43143ff3c1cb0959, "1424953923781206.2"),
+      // 1000000000000000.25 is a tie, rounded to even.
+      (0x430c6bf526340002, "1000000000000000.2"),
+    ];
+
+    for (bits, expected) in vectors {
+      assert_eq!(number(*bits), *expected, "{:#018x}", bits);
+    }
+
+    // Integers and doubles with the same value are canonicalized the same.
+    assert_eq!(canonicalize("1.0"), "1");
+    assert_eq!(canonicalize("1"), "1");
+    assert_eq!(canonicalize("-1.50"), "-1.5");
+    assert_eq!(canonicalize("1E2"), "100");
+  }
+
+  #[test]
+  fn test_may_tie() {
+    assert!(!may_tie(13.0));
+    assert!(!may_tie(9007199254740992.0));
+    assert!(may_tie(9007199254740994.0));
+    assert!(may_tie(f64::from_bits(0x430c6bf526340002)));
+    assert!(!may_tie(0.1));
+    assert!(!may_tie(5e-324));
+  }
+
+  #[test]
+  fn test_strings() {
+    // RFC 8785, 3.2.2.2.
+    assert_eq!(
+      canonicalize(r#""\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/""#),
+      "\"\u{20ac}$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\""
+    );
+    assert_eq!(
+      canonicalize(r#""\b\t\n\f\r\u001f\u007f""#),
+      "\"\\b\\t\\n\\f\\r\\u001f\u{7f}\""
+    );
+  }
+
+  #[test]
+  fn test_sorting() {
+    // RFC 8785, 3.2.3.
+    let input: &str = r#"{
+      "\u20ac": "Euro Sign",
+      "\r": "Carriage Return",
+      "\ufb33": "Hebrew Letter Dalet With Dagesh",
+      "1": "One",
+      "\ud83d\ude00": "Emoji: Grinning Face",
+      "\u0080": "Control",
+      "\u00f6": "Latin Small Letter O With Diaeresis"
+    }"#;
+
+    let expected: String = [
+      "{",
+      "\"\\r\":\"Carriage Return\",",
+      "\"1\":\"One\",",
+      "\"\u{80}\":\"Control\",",
+      "\"\u{f6}\":\"Latin Small Letter O With Diaeresis\",",
+      "\"\u{20ac}\":\"Euro Sign\",",
+      "\"\u{1f600}\":\"Emoji: Grinning Face\",",
+      "\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"",
+      "}",
+    ]
+    .concat();
+
+    assert_eq!(canonicalize(input), expected);
+  }
+
+  #[test]
+  #[allow(clippy::excessive_precision)]
+  fn test_rfc_example() {
+    // RFC 8785, 3.2.2. The numbers are Rust literals since the default `serde_json` parser is not
+    // guaranteed to round to the nearest double.
+    let input: Value = json!({
+      "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+      "string": "\u{20ac}$\u{000f}\u{000a}A'\u{0042}\u{0022}\u{005c}\\\"/",
+      "literals": [null, true, false]
+    });
+
+    let expected: &str = concat!(
+      r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"#,
+      r#""string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+
+    assert_eq!(String::from_utf8(to_vec(&input).unwrap()).unwrap(), expected);
+  }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::fmt::Formatter;
+use std::io::Write;
 
 use crypto::hashes::sha::Sha256;
 use crypto::hashes::Digest;
@@ -9,6 +10,7 @@ use crypto::hashes::Output;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::convert::jcs;
 use crate::error::Error;
 use crate::error::Result;
 
@@ -34,10 +36,21 @@ pub trait ToJson: Serialize + Sized {
     serde_json::to_string_pretty(self).map_err(Error::EncodeJSON)
   }
 
+  /// Returns the length in bytes of the compact JSON representation of `self`.
+  ///
+  /// This is equivalent to `self.to_json_vec()?.len()` without allocating the output.
+  fn to_json_size(&self) -> Result<usize> {
+    let mut counter: ByteCounter = ByteCounter(0);
+    serde_json::to_writer(&mut counter, self).map_err(Error::EncodeJSON)?;
+    Ok(counter.0)
+  }
+
   /// Serialize `self` as a JSON byte vector, normalized using JSON
-  /// Canonicalization Scheme (JCS).
+  /// Canonicalization Scheme (JCS) as specified in [RFC 8785][RFC8785].
+  ///
+  /// [RFC8785]: https://datatracker.ietf.org/doc/html/rfc8785
   fn to_jcs(&self) -> Result<Vec<u8>> {
-    serde_jcs::to_vec(self).map_err(Error::EncodeJSON)
+    jcs::to_vec(self)
   }
 
   /// Returns the given `data` serialized using JSON Canonicalization Scheme and
@@ -49,6 +62,20 @@ pub trait ToJson: Serialize + Sized {
 
 impl<T> ToJson for T where T: Serialize {}
 
+/// A [`Write`] implementation that discards its input and counts the bytes written.
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    self.0 += buf.len();
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
 // =============================================================================
 // =============================================================================
 
@@ -89,3 +116,40 @@ pub trait FmtJson: ToJson {
 }
 
 impl<T> FmtJson for T where T: ToJson {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::common::Object;
+  use crate::json;
+
+  fn value() -> serde_json::Value {
+    json!({
+      "id": "did:example:123",
+      "nested": { "list": [1, 2, 3], "flag": true },
+      "unicode": "\u{00e9}\u{1f600}",
+    })
+  }
+
+  #[test]
+  fn test_to_json_pretty() {
+    let value: serde_json::Value = value();
+    let compact: String = value.to_json().unwrap();
+    let pretty: String = value.to_json_pretty().unwrap();
+
+    assert_ne!(pretty, compact);
+    assert!(pretty.contains('\n'));
+    assert_eq!(serde_json::Value::from_json(&pretty).unwrap(), value);
+    assert_eq!(serde_json::Value::from_json(&compact).unwrap(), value);
+  }
+
+  #[test]
+  fn test_to_json_size() {
+    let value: serde_json::Value = value();
+    assert_eq!(value.to_json_size().unwrap(), value.to_json().unwrap().len());
+    assert_eq!(value.to_json_size().unwrap(), value.to_json_vec().unwrap().len());
+
+    let empty: Object = Object::new();
+    assert_eq!(empty.to_json_size().unwrap(), 2);
+  }
+}
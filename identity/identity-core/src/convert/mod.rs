@@ -8,5 +8,6 @@ pub use self::json::FromJson;
 pub use self::json::ToJson;
 pub use self::serde_into::SerdeInto;
 
+mod jcs;
 mod json;
 mod serde_into;
@@ -14,6 +14,7 @@ use crate::crypto::merkle_tree::compute_merkle_proof;
 use crate::crypto::merkle_tree::compute_merkle_root;
 use crate::crypto::merkle_tree::DigestExt;
 use crate::crypto::merkle_tree::Hash;
+use crate::crypto::merkle_tree::MerkleFrontier;
 use crate::crypto::merkle_tree::Proof;
 use crate::crypto::KeyPair;
 use crate::crypto::KeyRef;
@@ -22,6 +23,7 @@ use crate::crypto::PrivateKey;
 use crate::crypto::PublicKey;
 use crate::error::Error;
 use crate::error::Result;
+use crate::utils::encode_multibase;
 use crate::utils::generate_ed25519_keypairs;
 
 /// Defines an upper limit to the amount of keys that can be created (2^12)
@@ -32,8 +34,8 @@ const MAX_KEYS_ALLOWED: usize = 4_096;
 #[derive(Clone, Debug)]
 pub struct KeyCollection {
   type_: KeyType,
-  public: Box<[PublicKey]>,
-  private: Box<[PrivateKey]>,
+  public: Vec<PublicKey>,
+  private: Vec<PrivateKey>,
 }
 
 impl KeyCollection {
@@ -53,11 +55,7 @@ impl KeyCollection {
       return Err(Error::InvalidKeyCollectionSize(private.len()));
     }
 
-    Ok(Self {
-      type_,
-      public: public.into_boxed_slice(),
-      private: private.into_boxed_slice(),
-    })
+    Ok(Self { type_, public, private })
   }
 
   /// Creates a new [`KeyCollection`] with [`Ed25519`][`KeyType::Ed25519`] keys.
@@ -155,6 +153,69 @@ impl KeyCollection {
     compute_merkle_root(&self.public)
   }
 
+  /// Returns a [`MerkleFrontier`] of the public keys in the collection, which allows updating
+  /// the Merkle root hash incrementally when keys are [appended][Self::append].
+  pub fn merkle_frontier<D>(&self) -> MerkleFrontier<D>
+  where
+    D: DigestExt,
+  {
+    MerkleFrontier::from_leaves(&self.public)
+  }
+
+  /// Appends a [`PublicKey`]/[`PrivateKey`] pair to the collection and to `frontier`, the
+  /// [`MerkleFrontier`] of the collection.
+  ///
+  /// Only the hashes on the path of the new key are recomputed, so [`MerkleFrontier::root`]
+  /// is the updated Merkle root without rebuilding the tree.
+  ///
+  /// NOTE: proofs-of-inclusion are only available for collections with a power of two keys,
+  /// see [`KeyCollection::merkle_proof`]. Growing a collection from `n` to `2n` keys keeps the
+  /// proofs of the existing keys, extended by the root of the appended keys.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `frontier` does not cover the keys in the collection or if the collection would
+  /// exceed the maximum number of keys.
+  pub fn append<D>(&mut self, public: PublicKey, private: PrivateKey, frontier: &mut MerkleFrontier<D>) -> Result<()>
+  where
+    D: DigestExt,
+  {
+    if frontier.len() != self.len() {
+      return Err(Error::InvalidMerkleFrontierSize(frontier.len(), self.len()));
+    }
+
+    if self.len() >= MAX_KEYS_ALLOWED {
+      return Err(Error::InvalidKeyCollectionSize(self.len() + 1));
+    }
+
+    frontier.push(&public);
+    self.public.push(public);
+    self.private.push(private);
+
+    Ok(())
+  }
+
+  /// Appends all [`PublicKey`]/[`PrivateKey`] pairs of `iter` to the collection and to
+  /// `frontier`, the [`MerkleFrontier`] of the collection.
+  ///
+  /// See [`KeyCollection::append`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if `frontier` does not cover the keys in the collection or if the collection would
+  /// exceed the maximum number of keys, in which case the keys appended so far are retained.
+  pub fn extend<D, I>(&mut self, iter: I, frontier: &mut MerkleFrontier<D>) -> Result<()>
+  where
+    D: DigestExt,
+    I: IntoIterator<Item = (PublicKey, PrivateKey)>,
+  {
+    for (public, private) in iter {
+      self.append(public, private, frontier)?;
+    }
+
+    Ok(())
+  }
+
   /// Returns a proof-of-inclusion for the public key at the specified index.
   pub fn merkle_proof<D>(&self, index: usize) -> Option<Proof<D>>
   where
@@ -184,6 +245,17 @@ impl KeyCollection {
   {
     self.type_.encode_merkle_key::<D>(&self.merkle_root())
   }
+
+  /// Returns the [Multibase]-encoded DID Document public key value for the Merkle root of the
+  /// key collection, suitable as `MerkleKeyCollection2021` method key data.
+  ///
+  /// [Multibase]: https://datatracker.ietf.org/doc/html/draft-multiformats-multibase-03
+  pub fn merkle_root_encoded<D>(&self) -> String
+  where
+    D: MerkleDigest,
+  {
+    encode_multibase(&self.encode_merkle_key::<D>(), None)
+  }
 }
 
 impl<I> Index<I> for KeyCollection
@@ -211,13 +283,18 @@ impl IntoIterator for KeyCollection {
   type IntoIter = Zip<IntoIter<PublicKey>, IntoIter<PrivateKey>>;
 
   fn into_iter(self) -> Self::IntoIter {
-    self.public.to_vec().into_iter().zip(self.private.to_vec().into_iter())
+    self.public.into_iter().zip(self.private.into_iter())
   }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::crypto::merkle_key::MerkleKey;
+  use crate::crypto::merkle_key::Sha256;
+  use crate::crypto::merkle_tree::Node;
+  use crate::crypto::Ed25519;
+  use crate::utils::decode_multibase;
 
   #[test]
   fn test_ed25519() {
@@ -251,6 +328,79 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_merkle_root_encoded() {
+    let keys: KeyCollection = KeyCollection::new_ed25519(8).unwrap();
+    let root: Hash<Sha256> = keys.merkle_root();
+    let encoded: String = keys.merkle_root_encoded::<Sha256>();
+
+    assert_eq!(
+      decode_multibase(&encoded).unwrap(),
+      MerkleKey::encode_key::<Sha256, Ed25519>(&root)
+    );
+  }
+
+  #[test]
+  fn test_append() {
+    let mut keys: KeyCollection = KeyCollection::new_ed25519(8).unwrap();
+    let mut frontier: MerkleFrontier<Sha256> = keys.merkle_frontier();
+    let proofs: Vec<Proof<Sha256>> = (0..8).map(|index| keys.merkle_proof(index).unwrap()).collect();
+
+    let next: KeyCollection = KeyCollection::new_ed25519(8).unwrap();
+    let mut iter: _ = next.clone().into_iter();
+
+    // The root is updated incrementally.
+    let (public, private): (PublicKey, PrivateKey) = iter.next().unwrap();
+    keys.append(public, private, &mut frontier).unwrap();
+    assert_eq!(keys.len(), 9);
+    assert_eq!(frontier.root(), keys.merkle_root::<Sha256>());
+    assert_eq!(keys.public(8).unwrap().as_ref(), next.public(0).unwrap().as_ref());
+    assert_eq!(keys.private(8).unwrap().as_ref(), next.private(0).unwrap().as_ref());
+    // Proofs require a power of two keys.
+    assert!(keys.merkle_proof::<Sha256>(0).is_none());
+
+    keys.extend(iter, &mut frontier).unwrap();
+    assert_eq!(keys.len(), 16);
+    assert_eq!(frontier.root(), keys.merkle_root::<Sha256>());
+
+    let root: Hash<Sha256> = frontier.root();
+    let appended: Hash<Sha256> = next.merkle_root();
+
+    for index in 0..keys.len() {
+      let proof: Proof<Sha256> = keys.merkle_proof(index).unwrap();
+      assert_eq!(proof.index(), index);
+      assert!(proof.verify(&root, keys.public(index).unwrap()));
+    }
+
+    // The proofs of the existing keys are extended by the root of the appended keys.
+    for (index, proof) in proofs.iter().enumerate() {
+      let next: Proof<Sha256> = keys.merkle_proof(index).unwrap();
+      let prefix: Vec<&Hash<Sha256>> = next.nodes()[..3].iter().map(Node::get).collect();
+
+      assert_eq!(prefix, proof.nodes().iter().map(Node::get).collect::<Vec<_>>());
+      assert!(matches!(&next.nodes()[3], Node::R(hash) if hash == &appended));
+    }
+  }
+
+  #[test]
+  fn test_append_invalid() {
+    let mut keys: KeyCollection = KeyCollection::new_ed25519(4).unwrap();
+    let (public, private): (PublicKey, PrivateKey) = KeyCollection::new_ed25519(1).unwrap().into_iter().next().unwrap();
+
+    // The frontier must cover the keys in the collection.
+    let mut frontier: MerkleFrontier<Sha256> = MerkleFrontier::new();
+    assert!(matches!(
+      keys.append(public.clone(), private.clone(), &mut frontier),
+      Err(Error::InvalidMerkleFrontierSize(0, 4))
+    ));
+    assert_eq!(keys.len(), 4);
+
+    let mut keys: KeyCollection = KeyCollection::new_ed25519(MAX_KEYS_ALLOWED).unwrap();
+    let mut frontier: MerkleFrontier<Sha256> = keys.merkle_frontier();
+    assert!(keys.append(public, private, &mut frontier).is_err());
+    assert_eq!(frontier.len(), MAX_KEYS_ALLOWED);
+  }
+
   #[test]
   fn test_key_collection_size() {
     // Key Collection can not exceed 4_096 keys
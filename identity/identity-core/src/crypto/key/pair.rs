@@ -2,18 +2,51 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::convert::TryInto;
+use std::ops::RangeInclusive;
 
+use crypto::ciphers::chacha::XChaCha20Poly1305;
+use crypto::ciphers::traits::Aead;
+use crypto::keys::pbkdf::PBKDF2_HMAC_SHA512;
 use crypto::signatures::ed25519;
+use crypto::utils::rand;
 use zeroize::Zeroize;
 
+use crate::convert::FromJson;
+use crate::convert::ToJson;
 use crate::crypto::KeyRef;
 use crate::crypto::KeyType;
 use crate::crypto::PrivateKey;
 use crate::crypto::PublicKey;
+use crate::error::Error;
 use crate::error::Result;
+use crate::utils::decode_b58;
+use crate::utils::encode_b58;
 use crate::utils::generate_ed25519_keypair;
 use crate::utils::keypair_from_ed25519_private_key;
 
+/// The number of PBKDF2 iterations used to derive the key encrypting a private key.
+const PBKDF2_ITERATIONS: usize = 100_000;
+
+/// The range of PBKDF2 iterations accepted when decrypting a private key.
+const PBKDF2_ITERATIONS_RANGE: RangeInclusive<usize> = 10_000..=10_000_000;
+
+/// The length in bytes of the PBKDF2 salt.
+const PBKDF2_SALT_LENGTH: usize = 16;
+
+/// The serialized form of a [`KeyPair`] with an encrypted private key.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EncryptedKeyPair {
+  #[serde(rename = "type")]
+  type_: KeyType,
+  public_key: String,
+  salt: String,
+  iterations: usize,
+  nonce: String,
+  tag: String,
+  ciphertext: String,
+}
+
 /// A convenient type for representing a pair of cryptographic keys.
 #[derive(Clone, Debug)]
 pub struct KeyPair {
@@ -42,7 +75,7 @@ impl KeyPair {
   ///  The private key must be a 32-byte seed in compliance with [RFC 8032](https://datatracker.ietf.org/doc/html/rfc8032#section-3.2).
   /// Other implementations often use another format. See [this blog post](https://blog.mozilla.org/warner/2011/11/29/ed25519-keys/) for further explanation.
   pub fn try_from_ed25519_bytes(private_key_bytes: &[u8]) -> Result<Self, crypto::Error> {
-    let private_key_bytes: [u8; ed25519::SECRET_KEY_LENGTH] = private_key_bytes
+    let private_key_bytes: [u8; KeyType::Ed25519.private_key_len()] = private_key_bytes
       .try_into()
       .map_err(|_| crypto::Error::PrivateKeyError)?;
 
@@ -81,6 +114,104 @@ impl KeyPair {
   pub fn private_ref(&self) -> KeyRef<'_> {
     KeyRef::new(self.type_, self.private.as_ref())
   }
+
+  /// Serializes the [`KeyPair`] as JSON with the private key encrypted under `passphrase`.
+  ///
+  /// The encryption key is derived from `passphrase` with PBKDF2-HMAC-SHA512 and a random
+  /// salt, the private key is encrypted with XChaCha20-Poly1305. The public key is stored in
+  /// the clear and authenticated along with the private key.
+  pub fn to_encrypted_json(&self, passphrase: &str) -> Result<String> {
+    let mut salt: [u8; PBKDF2_SALT_LENGTH] = [0; PBKDF2_SALT_LENGTH];
+    let mut nonce: [u8; XChaCha20Poly1305::NONCE_LENGTH] = [0; XChaCha20Poly1305::NONCE_LENGTH];
+    let mut tag: [u8; XChaCha20Poly1305::TAG_LENGTH] = [0; XChaCha20Poly1305::TAG_LENGTH];
+    let mut ciphertext: Vec<u8> = vec![0; self.private.as_ref().len()];
+
+    rand::fill(&mut salt)?;
+    rand::fill(&mut nonce)?;
+
+    let mut key: [u8; XChaCha20Poly1305::KEY_LENGTH] = derive_key(passphrase, &salt, PBKDF2_ITERATIONS)?;
+    let result: Result<usize, crypto::Error> = XChaCha20Poly1305::try_encrypt(
+      &key,
+      &nonce,
+      self.public.as_ref(),
+      self.private.as_ref(),
+      &mut ciphertext,
+      &mut tag,
+    );
+
+    key.zeroize();
+    result?;
+
+    EncryptedKeyPair {
+      type_: self.type_,
+      public_key: encode_b58(self.public.as_ref()),
+      salt: encode_b58(&salt),
+      iterations: PBKDF2_ITERATIONS,
+      nonce: encode_b58(&nonce),
+      tag: encode_b58(&tag),
+      ciphertext: encode_b58(&ciphertext),
+    }
+    .to_json()
+  }
+
+  /// Deserializes a [`KeyPair`] created by [`to_encrypted_json`][Self::to_encrypted_json],
+  /// decrypting the private key with `passphrase`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `passphrase` is wrong or the encrypted data has been tampered with, and with
+  /// [`Error::InvalidKeyFormat`] if the key derivation parameters are out of range.
+  pub fn from_encrypted_json(json: &str, passphrase: &str) -> Result<Self> {
+    let data: EncryptedKeyPair = EncryptedKeyPair::from_json(json)?;
+
+    let public: Vec<u8> = decode_b58(&data.public_key)?;
+    let salt: Vec<u8> = decode_b58(&data.salt)?;
+    let nonce: Vec<u8> = decode_b58(&data.nonce)?;
+    let tag: Vec<u8> = decode_b58(&data.tag)?;
+    let ciphertext: Vec<u8> = decode_b58(&data.ciphertext)?;
+
+    data.type_.check_public_key(&public)?;
+
+    if ciphertext.len() != data.type_.private_key_len() {
+      return Err(Error::InvalidKeyLength(ciphertext.len(), data.type_.private_key_len()));
+    }
+
+    if nonce.len() != XChaCha20Poly1305::NONCE_LENGTH || tag.len() != XChaCha20Poly1305::TAG_LENGTH {
+      return Err(Error::InvalidKeyFormat);
+    }
+
+    // Weak parameters must not be accepted, nor costly ones that could stall the caller.
+    if salt.len() != PBKDF2_SALT_LENGTH || !PBKDF2_ITERATIONS_RANGE.contains(&data.iterations) {
+      return Err(Error::InvalidKeyFormat);
+    }
+
+    let mut private: Vec<u8> = vec![0; ciphertext.len()];
+    let mut key: [u8; XChaCha20Poly1305::KEY_LENGTH] = derive_key(passphrase, &salt, data.iterations)?;
+    let result: Result<usize, crypto::Error> =
+      XChaCha20Poly1305::try_decrypt(&key, &nonce, &public, &mut private, &ciphertext, &tag);
+
+    key.zeroize();
+
+    if let Err(error) = result {
+      private.zeroize();
+      return Err(error.into());
+    }
+
+    Ok(Self {
+      type_: data.type_,
+      public: public.into(),
+      private: private.into(),
+    })
+  }
+}
+
+/// Derives an encryption key from `passphrase` with PBKDF2-HMAC-SHA512.
+fn derive_key(passphrase: &str, salt: &[u8], iterations: usize) -> Result<[u8; XChaCha20Poly1305::KEY_LENGTH]> {
+  let mut key: [u8; XChaCha20Poly1305::KEY_LENGTH] = [0; XChaCha20Poly1305::KEY_LENGTH];
+
+  PBKDF2_HMAC_SHA512(passphrase.as_bytes(), salt, iterations, &mut key)?;
+
+  Ok(key)
 }
 
 impl Drop for KeyPair {
@@ -118,4 +249,77 @@ mod tests {
     assert_eq!(keypair.public().as_ref().len(), 32);
     assert_eq!(keypair.private().as_ref().len(), 32);
   }
+
+  #[test]
+  fn test_encrypted_json_round_trip() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let json: String = keypair.to_encrypted_json("correct horse battery staple").unwrap();
+
+    // The private key is not stored in the clear.
+    assert!(json.contains(&encode_b58(keypair.public().as_ref())));
+    assert!(!json.contains(&encode_b58(keypair.private().as_ref())));
+
+    let decoded: KeyPair = KeyPair::from_encrypted_json(&json, "correct horse battery staple").unwrap();
+    assert_eq!(decoded.type_(), keypair.type_());
+    assert_eq!(decoded.public().as_ref(), keypair.public().as_ref());
+    assert_eq!(decoded.private().as_ref(), keypair.private().as_ref());
+  }
+
+  #[test]
+  fn test_encrypted_json_wrong_passphrase() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let json: String = keypair.to_encrypted_json("correct horse battery staple").unwrap();
+
+    assert!(matches!(
+      KeyPair::from_encrypted_json(&json, "wrong passphrase"),
+      Err(Error::Crypto(_))
+    ));
+  }
+
+  #[test]
+  fn test_encrypted_json_parameters() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let json: String = keypair.to_encrypted_json("correct horse battery staple").unwrap();
+
+    let with = |key: &str, value: serde_json::Value| -> String {
+      let mut data: serde_json::Value = serde_json::from_str(&json).unwrap();
+      data[key] = value;
+      data.to_string()
+    };
+
+    for iterations in [0, 9_999, 10_000_001] {
+      assert!(matches!(
+        KeyPair::from_encrypted_json(&with("iterations", iterations.into()), "correct horse battery staple"),
+        Err(Error::InvalidKeyFormat)
+      ));
+    }
+
+    for salt in [vec![], vec![1; PBKDF2_SALT_LENGTH - 1], vec![1; PBKDF2_SALT_LENGTH + 1]] {
+      assert!(matches!(
+        KeyPair::from_encrypted_json(&with("salt", encode_b58(&salt).into()), "correct horse battery staple"),
+        Err(Error::InvalidKeyFormat)
+      ));
+    }
+  }
+
+  #[test]
+  fn test_encrypted_json_public_key_length() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let json: String = keypair.to_encrypted_json("correct horse battery staple").unwrap();
+
+    let mut data: serde_json::Value = serde_json::from_str(&json).unwrap();
+    data["publicKey"] = encode_b58(&[1; 16]).into();
+
+    assert!(matches!(
+      KeyPair::from_encrypted_json(&data.to_string(), "correct horse battery staple"),
+      Err(Error::InvalidKeyLength(16, 32))
+    ));
+  }
+
+  #[test]
+  fn test_try_from_ed25519_bytes_length() {
+    assert!(KeyPair::try_from_ed25519_bytes(&[1; 32]).is_ok());
+    assert!(KeyPair::try_from_ed25519_bytes(&[1; 16]).is_err());
+    assert!(KeyPair::try_from_ed25519_bytes(&[]).is_err());
+  }
 }
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use core::str::FromStr;
+use crypto::signatures::ed25519::PUBLIC_KEY_LENGTH;
+use crypto::signatures::ed25519::SECRET_KEY_LENGTH;
 
 use crate::crypto::merkle_key::MerkleDigest;
 use crate::crypto::merkle_key::MerkleKey;
@@ -26,6 +28,33 @@ impl KeyType {
     }
   }
 
+  /// Returns the length in bytes of a public key of this type.
+  pub const fn public_key_len(&self) -> usize {
+    match self {
+      Self::Ed25519 => PUBLIC_KEY_LENGTH,
+    }
+  }
+
+  /// Returns the length in bytes of a private key of this type.
+  pub const fn private_key_len(&self) -> usize {
+    match self {
+      Self::Ed25519 => SECRET_KEY_LENGTH,
+    }
+  }
+
+  /// Checks that `public` has the expected length of a public key of this type.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidKeyLength`] if the length does not match.
+  pub fn check_public_key(&self, public: &[u8]) -> Result<()> {
+    if public.len() == self.public_key_len() {
+      Ok(())
+    } else {
+      Err(Error::InvalidKeyLength(public.len(), self.public_key_len()))
+    }
+  }
+
   /// Creates a DID Document public key value for the given Merkle `root`.
   pub fn encode_merkle_key<D>(&self, root: &Hash<D>) -> Vec<u8>
   where
@@ -48,3 +77,27 @@ impl FromStr for KeyType {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_key_len_ed25519() {
+    assert_eq!(KeyType::Ed25519.public_key_len(), 32);
+    assert_eq!(KeyType::Ed25519.private_key_len(), 32);
+  }
+
+  #[test]
+  fn test_check_public_key() {
+    assert!(KeyType::Ed25519.check_public_key(&[0; 32]).is_ok());
+    assert!(matches!(
+      KeyType::Ed25519.check_public_key(&[0; 16]),
+      Err(Error::InvalidKeyLength(16, 32))
+    ));
+    assert!(matches!(
+      KeyType::Ed25519.check_public_key(&[]),
+      Err(Error::InvalidKeyLength(0, 32))
+    ));
+  }
+}
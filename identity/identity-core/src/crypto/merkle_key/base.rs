@@ -21,10 +21,25 @@ impl MerkleKey {
   pub const TYPE_SIG: &'static str = "MerkleKeySignature2021";
 
   /// Extracts the signature and digest algorithm tags from the public key value.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidMerkleSignatureKeyTag`] if the signature tag is missing or
+  /// unknown, and with [`Error::InvalidMerkleDigestKeyTag`] if the digest tag is missing or
+  /// unknown.
   pub fn extract_tags(data: &[u8]) -> Result<(MerkleSignatureTag, MerkleDigestTag)> {
     let tag_s: MerkleSignatureTag = Self::signature_tag(data, 0)?;
+
+    if MerkleSignatureTag::from_u8(tag_s.into()).is_none() {
+      return Err(Error::InvalidMerkleSignatureKeyTag(Some(tag_s)));
+    }
+
     let tag_d: MerkleDigestTag = Self::digest_tag(data, 1)?;
 
+    if MerkleDigestTag::from_u8(tag_d.into()).is_none() {
+      return Err(Error::InvalidMerkleDigestKeyTag(Some(tag_d)));
+    }
+
     Ok((tag_s, tag_d))
   }
 
@@ -69,6 +84,7 @@ mod tests {
   use crate::crypto::merkle_key::Sha256;
   use crate::crypto::merkle_tree::Hash;
   use crate::crypto::Ed25519;
+  use crate::error::Error;
 
   fn assert_tag<D, S>()
   where
@@ -93,4 +109,27 @@ mod tests {
   fn test_sha256_tag() {
     assert_tag::<Sha256, Ed25519>();
   }
+
+  #[test]
+  fn test_unknown_tags() {
+    let sha256: u8 = MerkleDigestTag::SHA256.into();
+    let ed25519: u8 = MerkleSignatureTag::ED25519.into();
+
+    assert!(matches!(
+      MerkleKey::extract_tags(&[ed25519, 0xFF, 0x0]),
+      Err(Error::InvalidMerkleDigestKeyTag(Some(tag))) if tag == MerkleDigestTag::new(0xFF)
+    ));
+    assert!(matches!(
+      MerkleKey::extract_tags(&[0xFF, sha256, 0x0]),
+      Err(Error::InvalidMerkleSignatureKeyTag(Some(tag))) if tag == MerkleSignatureTag::new(0xFF)
+    ));
+    assert!(matches!(
+      MerkleKey::extract_tags(&[ed25519]),
+      Err(Error::InvalidMerkleDigestKeyTag(None))
+    ));
+    assert!(matches!(
+      MerkleKey::extract_tags(&[]),
+      Err(Error::InvalidMerkleSignatureKeyTag(None))
+    ));
+  }
 }
@@ -17,6 +17,15 @@ impl MerkleDigestTag {
   pub const fn new(tag: u8) -> Self {
     Self(tag)
   }
+
+  /// Returns the [`MerkleDigestTag`] identified by `tag`, or `None` if the digest algorithm is
+  /// not supported.
+  pub const fn from_u8(tag: u8) -> Option<Self> {
+    match Self::new(tag) {
+      tag @ (Self::SHA256 | Self::BLAKE2B_256) => Some(tag),
+      _ => None,
+    }
+  }
 }
 
 impl From<u8> for MerkleDigestTag {
@@ -44,6 +53,15 @@ impl MerkleSignatureTag {
   pub const fn new(tag: u8) -> Self {
     Self(tag)
   }
+
+  /// Returns the [`MerkleSignatureTag`] identified by `tag`, or `None` if the signature
+  /// algorithm is not supported.
+  pub const fn from_u8(tag: u8) -> Option<Self> {
+    match Self::new(tag) {
+      tag @ Self::ED25519 => Some(tag),
+      _ => None,
+    }
+  }
 }
 
 impl From<u8> for MerkleSignatureTag {
@@ -57,3 +75,23 @@ impl From<MerkleSignatureTag> for u8 {
     other.0
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_digest_tag_from_u8() {
+    assert_eq!(MerkleDigestTag::from_u8(0x0), Some(MerkleDigestTag::SHA256));
+    assert_eq!(MerkleDigestTag::from_u8(0x1), Some(MerkleDigestTag::BLAKE2B_256));
+    assert_eq!(MerkleDigestTag::from_u8(0x2), None);
+    assert_eq!(MerkleDigestTag::from_u8(0xFF), None);
+  }
+
+  #[test]
+  fn test_signature_tag_from_u8() {
+    assert_eq!(MerkleSignatureTag::from_u8(0x0), Some(MerkleSignatureTag::ED25519));
+    assert_eq!(MerkleSignatureTag::from_u8(0x1), None);
+    assert_eq!(MerkleSignatureTag::from_u8(0xFF), None);
+  }
+}
@@ -23,6 +23,7 @@ use crate::crypto::SignatureValue;
 use crate::crypto::Signer as _;
 use crate::crypto::Verifier as _;
 use crate::crypto::Verify;
+use crate::error::Error;
 use crate::utils::encode_b58;
 
 fn inject_key(signature: &SignatureValue, key: &PublicKey) -> SignatureValue {
@@ -79,7 +80,10 @@ where
   let mut vkey: VerificationKey<'_> = VerificationKey::from_borrowed(&mkey);
   vkey.set_revocation(&revocation);
 
-  assert!(MerkleVerifier::<D, S>::verify(&input, &signature, &vkey).is_err());
+  assert!(matches!(
+    MerkleVerifier::<D, S>::verify(&input, &signature, &vkey),
+    Err(Error::KeyRevoked(revoked)) if revoked == index as u32
+  ));
 
   // Ensure all other keys are NOT valid
   for key in samples.iter() {
@@ -108,3 +112,22 @@ fn test_sign_verify_sha256_ed25519() {
 fn test_sign_verify_blake2b_ed25519() {
   __test_sign_verify::<Blake2b256, Ed25519>();
 }
+
+#[test]
+fn test_verify_revoked_before_signature() {
+  let input: &[u8] = b"IOTA Identity";
+  let keys: KeyCollection = KeyCollection::new_ed25519(8).unwrap();
+  let mkey: Vec<u8> = keys.encode_merkle_key::<Sha256>();
+  let skey: SigningKey<'_, Sha256> = keys.merkle_key(3).unwrap();
+
+  let signature: SignatureValue = MerkleSigner::<Sha256, Ed25519>::sign(&input, &skey).unwrap();
+
+  let mut revocation: BitSet = BitSet::new();
+  revocation.insert(3);
+  let mut vkey: VerificationKey<'_> = VerificationKey::from_borrowed(&mkey);
+  vkey.set_revocation(&revocation);
+
+  // The revocation flag is reported even if the signature would not verify.
+  let result = MerkleVerifier::<Sha256, Ed25519>::verify(b"other data", &signature, &vkey);
+  assert!(matches!(result, Err(Error::KeyRevoked(3))));
+}
@@ -87,6 +87,12 @@ where
   D: MerkleDigest,
   S: MerkleSignature + Verify<Public = [u8]>,
 {
+  /// Verifies a Merkle Key Collection signature.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::KeyRevoked`] if the signing key is part of the revocation
+  /// set of the [`VerificationKey`], without verifying the signature itself.
   fn verify<X>(data: &X, signature: &SignatureValue, public: &VerificationKey<'key>) -> Result<()>
   where
     X: Serialize,
@@ -106,10 +112,13 @@ where
     }
 
     // If a set of revocation flags was provided, ensure the public key
-    // was not revoked
+    // was not revoked - this is checked before the (more expensive)
+    // signature verification
     if let Some(revocation) = public.revocation {
-      if revocation.contains(merkle_proof.index() as u32) {
-        return Err(Error::InvalidProofValue("merkle key - revoked"));
+      let index: u32 = merkle_proof.index() as u32;
+
+      if revocation.contains(index) {
+        return Err(Error::KeyRevoked(index));
       }
     }
 
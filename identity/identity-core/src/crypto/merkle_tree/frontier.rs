@@ -0,0 +1,152 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+
+use crate::crypto::merkle_tree::AsLeaf;
+use crate::crypto::merkle_tree::DigestExt;
+use crate::crypto::merkle_tree::Hash;
+
+/// The roots of the perfect subtrees of a Merkle tree, which allow appending leaves and
+/// recomputing the root hash without rebuilding the tree.
+///
+/// The tree has the same shape as in [`compute_merkle_root`][crate::crypto::merkle_tree::compute_merkle_root]:
+/// the leaves are split at the largest power of two, so a tree of `n` leaves consists of one
+/// perfect subtree for each bit set in `n`. Appending a leaf only merges subtrees of equal
+/// height, which hashes `O(log n)` nodes.
+pub struct MerkleFrontier<D: DigestExt> {
+  len: usize,
+  /// The root of the perfect subtree of height `i`, if bit `i` of `len` is set.
+  peaks: Vec<Option<Hash<D>>>,
+}
+
+impl<D: DigestExt> MerkleFrontier<D> {
+  /// Creates a new, empty [`MerkleFrontier`].
+  pub fn new() -> Self {
+    Self {
+      len: 0,
+      peaks: Vec::new(),
+    }
+  }
+
+  /// Creates a new [`MerkleFrontier`] for the given slice of `leaves`.
+  pub fn from_leaves<L>(leaves: &[L]) -> Self
+  where
+    L: AsLeaf<D>,
+  {
+    let mut this: Self = Self::new();
+    this.extend(leaves);
+    this
+  }
+
+  /// Returns the number of leaves in the tree.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns `true` if the tree has no leaves.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Appends `leaf` to the tree.
+  pub fn push<L>(&mut self, leaf: &L)
+  where
+    L: AsLeaf<D>,
+  {
+    let mut digest: D = D::new();
+    let mut node: Hash<D> = leaf.hash(&mut digest);
+    let mut height: usize = 0;
+
+    // Merge subtrees of equal height, like the carry of a binary increment.
+    while let Some(peak) = self.peaks.get_mut(height).and_then(Option::take) {
+      node = digest.hash_node(&peak, &node);
+      height += 1;
+    }
+
+    if height == self.peaks.len() {
+      self.peaks.push(Some(node));
+    } else {
+      self.peaks[height] = Some(node);
+    }
+
+    self.len += 1;
+  }
+
+  /// Appends all `leaves` to the tree.
+  pub fn extend<L>(&mut self, leaves: &[L])
+  where
+    L: AsLeaf<D>,
+  {
+    for leaf in leaves {
+      self.push(leaf);
+    }
+  }
+
+  /// Computes the Merkle root hash of the tree.
+  pub fn root(&self) -> Hash<D> {
+    let mut digest: D = D::new();
+
+    // The lowest subtree holds the last leaves, so fold from right to left.
+    let root: Option<Hash<D>> = self.peaks.iter().flatten().fold(None, |rhs, lhs| match rhs {
+      Some(rhs) => Some(digest.hash_node(lhs, &rhs)),
+      None => Some(lhs.clone()),
+    });
+
+    root.unwrap_or_else(|| digest.hash_empty())
+  }
+}
+
+impl<D: DigestExt> Default for MerkleFrontier<D> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<D: DigestExt> Clone for MerkleFrontier<D>
+where
+  Hash<D>: Clone,
+{
+  fn clone(&self) -> Self {
+    Self {
+      len: self.len,
+      peaks: self.peaks.clone(),
+    }
+  }
+}
+
+impl<D: DigestExt> Debug for MerkleFrontier<D> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("MerkleFrontier")
+      .field("len", &self.len)
+      .field("peaks", &self.peaks)
+      .finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crypto::hashes::sha::Sha256;
+
+  use crate::crypto::merkle_tree::compute_merkle_root;
+  use crate::crypto::merkle_tree::Hash;
+  use crate::crypto::merkle_tree::MerkleFrontier;
+
+  #[test]
+  fn test_root_matches_full_tree() {
+    let leaves: Vec<[u8; 4]> = (0..70).map(u32::to_be_bytes).collect();
+    let mut frontier: MerkleFrontier<Sha256> = MerkleFrontier::new();
+
+    assert_eq!(frontier.root(), compute_merkle_root::<Sha256, [u8; 4]>(&[]));
+
+    for (index, leaf) in leaves.iter().enumerate() {
+      frontier.push(leaf);
+
+      let root: Hash<Sha256> = compute_merkle_root(&leaves[..=index]);
+      assert_eq!(frontier.len(), index + 1);
+      assert_eq!(frontier.root(), root);
+      assert_eq!(MerkleFrontier::<Sha256>::from_leaves(&leaves[..=index]).root(), root);
+    }
+  }
+}
@@ -6,6 +6,7 @@
 //! [WIKI]: https://en.wikipedia.org/wiki/Merkle_tree
 
 mod digest;
+mod frontier;
 mod hash;
 mod merkle;
 mod node;
@@ -16,6 +17,7 @@ mod traits;
 pub use self::digest::Digest;
 pub use self::digest::DigestExt;
 pub use self::digest::Output;
+pub use self::frontier::MerkleFrontier;
 pub use self::hash::Hash;
 pub use self::merkle::compute_merkle_proof;
 pub use self::merkle::compute_merkle_root;
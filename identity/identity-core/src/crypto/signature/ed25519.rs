@@ -7,6 +7,9 @@ use crypto::signatures::ed25519;
 use crypto::signatures::ed25519::PUBLIC_KEY_LENGTH;
 use crypto::signatures::ed25519::SECRET_KEY_LENGTH;
 use crypto::signatures::ed25519::SIGNATURE_LENGTH;
+use ed25519_zebra::batch;
+use ed25519_zebra::VerificationKeyBytes;
+use rand::rngs::OsRng;
 
 use crate::crypto::Sign;
 use crate::crypto::Verify;
@@ -50,6 +53,37 @@ where
   }
 }
 
+impl<T> Ed25519<T>
+where
+  T: AsRef<[u8]> + ?Sized,
+{
+  /// Verifies a batch of `(message, signature, public key)` triples at once, which is faster
+  /// than verifying each signature on its own.
+  ///
+  /// # Errors
+  ///
+  /// Fails if any signature or public key is malformed or any signature is invalid. The
+  /// error does not identify the invalid signature.
+  pub fn verify_batch(items: &[(&[u8], &[u8], &T)]) -> Result<()> {
+    let mut verifier: batch::Verifier = batch::Verifier::new();
+
+    for (message, signature, key) in items {
+      let key: [u8; PUBLIC_KEY_LENGTH] = parse_public(key.as_ref())?.to_bytes();
+      let sig: [u8; SIGNATURE_LENGTH] = parse_signature(signature)?.to_bytes();
+
+      verifier.queue((
+        VerificationKeyBytes::from(key),
+        ed25519_zebra::Signature::from(sig),
+        message,
+      ));
+    }
+
+    verifier
+      .verify(OsRng)
+      .map_err(|_| Error::InvalidProofValue("ed25519"))
+  }
+}
+
 fn parse_public(slice: &[u8]) -> Result<ed25519::PublicKey> {
   let bytes: [u8; PUBLIC_KEY_LENGTH] = slice
     .try_into()
@@ -77,6 +111,7 @@ fn parse_signature(slice: &[u8]) -> Result<ed25519::Signature> {
 #[cfg(test)]
 mod tests {
   use crate::crypto::Ed25519;
+  use crate::crypto::KeyPair;
   use crate::crypto::Sign;
   use crate::crypto::Verify;
 
@@ -96,4 +131,33 @@ mod tests {
     let verified: _ = Ed25519::verify(&hex::decode(MESSAGE_HEX).unwrap()[..], &signature, &public_key);
     assert!(verified.is_ok());
   }
+
+  #[test]
+  fn test_ed25519_verify_batch() {
+    let keypairs: Vec<KeyPair> = (0..4).map(|_| KeyPair::new_ed25519().unwrap()).collect();
+    let messages: Vec<Vec<u8>> = (0..4u8).map(|index| vec![index; 32]).collect();
+    let mut signatures: Vec<[u8; 64]> = keypairs
+      .iter()
+      .zip(messages.iter())
+      .map(|(keypair, message)| Ed25519::sign(message, keypair.private()).unwrap())
+      .collect();
+
+    let items = |signatures: &[[u8; 64]]| -> Result<(), crate::Error> {
+      let items: Vec<(&[u8], &[u8], &[u8])> = messages
+        .iter()
+        .zip(signatures.iter())
+        .zip(keypairs.iter())
+        .map(|((message, signature), keypair)| (&message[..], &signature[..], keypair.public().as_ref()))
+        .collect();
+
+      Ed25519::verify_batch(&items)
+    };
+
+    assert!(items(&signatures).is_ok());
+    assert!(Ed25519::<[u8]>::verify_batch(&[]).is_ok());
+
+    // A single invalid signature fails the whole batch.
+    signatures[2][0] ^= 1;
+    assert!(items(&signatures).is_err());
+  }
 }
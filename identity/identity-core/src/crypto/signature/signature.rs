@@ -8,9 +8,8 @@ use serde::__private::ser::FlatMapSerializer;
 use serde::ser::SerializeMap;
 use serde::ser::Serializer;
 use serde::Serialize;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering;
 
+use crate::crypto::SignatureOptions;
 use crate::crypto::SignatureValue;
 use crate::error::Result;
 
@@ -23,8 +22,10 @@ pub struct Signature {
   value: SignatureValue,
   #[serde(rename = "verificationMethod")]
   method: String,
-  #[serde(default, skip_deserializing)]
-  hidden: AtomicBoolCell,
+  #[serde(default)]
+  challenge: Option<String>,
+  #[serde(default)]
+  domain: Option<String>,
 }
 
 impl Signature {
@@ -34,7 +35,17 @@ impl Signature {
       type_: type_.into(),
       value: SignatureValue::None,
       method: method.into(),
-      hidden: AtomicBoolCell(AtomicBool::new(false)),
+      challenge: None,
+      domain: None,
+    }
+  }
+
+  /// Creates a new [`Signature`] instance with the given `type_`, `method` and proof `options`.
+  pub fn with_options(type_: impl Into<String>, method: impl Into<String>, options: SignatureOptions) -> Self {
+    Self {
+      challenge: options.challenge,
+      domain: options.domain,
+      ..Self::new(type_, method)
     }
   }
 
@@ -48,6 +59,16 @@ impl Signature {
     &*self.method
   }
 
+  /// Returns the challenge the signature was created for, if any.
+  pub fn challenge(&self) -> Option<&str> {
+    self.challenge.as_deref()
+  }
+
+  /// Returns the domain the signature is restricted to, if any.
+  pub fn domain(&self) -> Option<&str> {
+    self.domain.as_deref()
+  }
+
   /// Returns a reference to the signature `value`.
   pub const fn value(&self) -> &SignatureValue {
     &self.value
@@ -67,20 +88,6 @@ impl Signature {
   pub fn clear_value(&mut self) {
     self.value = SignatureValue::None;
   }
-
-  /// Flag the signature value so it is ignored during serialization
-  pub fn hide_value(&self) {
-    self.hidden.set(true);
-  }
-
-  /// Restore the signature value state so serialization behaves normally
-  pub fn show_value(&self) {
-    self.hidden.set(false);
-  }
-
-  fn __hide(&self) -> bool {
-    self.hidden.get() || self.value.is_none()
-  }
 }
 
 impl Debug for Signature {
@@ -89,6 +96,8 @@ impl Debug for Signature {
       .field("type_", &self.type_)
       .field("value", &self.value)
       .field("method", &self.method)
+      .field("challenge", &self.challenge)
+      .field("domain", &self.domain)
       .finish()
   }
 }
@@ -98,17 +107,27 @@ impl Serialize for Signature {
   where
     S: Serializer,
   {
-    let hide: bool = self.__hide();
+    let hide: bool = self.value.is_none();
+
+    let options: usize = self.challenge.iter().chain(self.domain.iter()).count();
 
     let mut state: S::SerializeMap = if hide {
-      serializer.serialize_map(Some(1 + 5))?
+      serializer.serialize_map(Some(1 + 5 + options))?
     } else {
-      serializer.serialize_map(Some(2 + 5))?
+      serializer.serialize_map(Some(2 + 5 + options))?
     };
 
     state.serialize_entry("type", &self.type_)?;
     state.serialize_entry("verificationMethod", &self.method)?;
 
+    if let Some(challenge) = self.challenge.as_ref() {
+      state.serialize_entry("challenge", challenge)?;
+    }
+
+    if let Some(domain) = self.domain.as_ref() {
+      state.serialize_entry("domain", domain)?;
+    }
+
     if !hide {
       Serialize::serialize(&self.value, FlatMapSerializer(&mut state))?;
     }
@@ -117,51 +136,74 @@ impl Serialize for Signature {
   }
 }
 
-/// Cell-style wrapper around an AtomicBool.
-/// This is essentially a `Cell` but with `Sync` implemented.
-pub(crate) struct AtomicBoolCell(AtomicBool);
-
-impl AtomicBoolCell {
-  pub(crate) fn set(&self, value: bool) {
-    self.0.store(value, Ordering::Relaxed);
+#[cfg(test)]
+mod tests {
+  use std::sync::Arc;
+  use std::thread;
+  use std::thread::JoinHandle;
+
+  use super::*;
+  use crate::crypto::Ed25519;
+  use crate::crypto::JcsEd25519;
+  use crate::crypto::KeyPair;
+  use crate::crypto::SetSignature;
+  use crate::crypto::Signer;
+  use crate::crypto::TrySignature;
+  use crate::crypto::TrySignatureMut;
+  use crate::crypto::Verifier;
+
+  #[derive(Serialize)]
+  struct Document {
+    data: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof: Option<Signature>,
+  }
+
+  impl TrySignature for Document {
+    fn signature(&self) -> Option<&Signature> {
+      self.proof.as_ref()
+    }
   }
 
-  pub(crate) fn get(&self) -> bool {
-    self.0.load(Ordering::Relaxed)
+  impl TrySignatureMut for Document {
+    fn signature_mut(&mut self) -> Option<&mut Signature> {
+      self.proof.as_mut()
+    }
   }
-}
 
-impl Clone for AtomicBoolCell {
-  fn clone(&self) -> Self {
-    Self(AtomicBool::new(self.0.load(Ordering::Relaxed)))
+  impl SetSignature for Document {
+    fn set_signature(&mut self, signature: Signature) {
+      self.proof = Some(signature);
+    }
   }
-}
 
-impl PartialEq for AtomicBoolCell {
-  fn eq(&self, other: &Self) -> bool {
-    self.0.load(Ordering::Relaxed) == other.0.load(Ordering::Relaxed)
-  }
-}
+  #[test]
+  fn test_concurrent_verify() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: Document = Document { data: 123, proof: None };
 
-impl Eq for AtomicBoolCell {}
+    JcsEd25519::<Ed25519>::create_signature(&mut document, "#key-1", keypair.private().as_ref()).unwrap();
 
-impl Default for AtomicBoolCell {
-  fn default() -> Self {
-    Self(AtomicBool::new(false))
-  }
-}
+    let document: Arc<Document> = Arc::new(document);
+    let public: Arc<Vec<u8>> = Arc::new(keypair.public().as_ref().to_vec());
 
-impl PartialOrd for AtomicBoolCell {
-  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-    self
-      .0
-      .load(Ordering::Relaxed)
-      .partial_cmp(&other.0.load(Ordering::Relaxed))
-  }
-}
+    let handles: Vec<JoinHandle<()>> = (0..8)
+      .map(|_| {
+        let document: Arc<Document> = Arc::clone(&document);
+        let public: Arc<Vec<u8>> = Arc::clone(&public);
+
+        thread::spawn(move || {
+          for _ in 0..100 {
+            assert!(JcsEd25519::<Ed25519>::verify_signature(&*document, &public[..]).is_ok());
+          }
+        })
+      })
+      .collect();
+
+    for handle in handles {
+      handle.join().unwrap();
+    }
 
-impl Ord for AtomicBoolCell {
-  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-    self.0.load(Ordering::Relaxed).cmp(&other.0.load(Ordering::Relaxed))
+    assert!(JcsEd25519::<Ed25519>::verify_signature(&*document, &public[..]).is_ok());
   }
 }
@@ -0,0 +1,30 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+/// Optional properties of a [`Signature`][crate::crypto::Signature] which are covered by the
+/// signature value.
+///
+/// [More Info](https://w3c-ccg.github.io/ld-proofs/#proof-options)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SignatureOptions {
+  /// A value chosen by the verifier to prevent replay of the signed data.
+  pub challenge: Option<String>,
+  /// The operational domain the signature is restricted to.
+  pub domain: Option<String>,
+}
+
+impl SignatureOptions {
+  /// Creates a new [`SignatureOptions`] instance with the given `challenge`.
+  pub fn with_challenge(challenge: impl Into<String>) -> Self {
+    Self {
+      challenge: Some(challenge.into()),
+      domain: None,
+    }
+  }
+
+  /// Sets the `domain` of the options.
+  pub fn domain(mut self, domain: impl Into<String>) -> Self {
+    self.domain = Some(domain.into());
+    self
+  }
+}
@@ -3,8 +3,11 @@
 
 use serde::Serialize;
 
+use crate::common::Value;
+use crate::convert::ToJson;
 use crate::crypto::SetSignature;
 use crate::crypto::Signature;
+use crate::crypto::SignatureOptions;
 use crate::crypto::SignatureValue;
 use crate::crypto::TrySignature;
 use crate::error::Error;
@@ -58,9 +61,25 @@ pub trait Signer<Secret: ?Sized>: Named {
   where
     T: Serialize + SetSignature,
   {
-    data.set_signature(Signature::new(Self::NAME, method));
+    Self::create_signature_with_options(data, method, SignatureOptions::default(), secret)
+  }
 
-    let value: SignatureValue = Self::sign(&data, secret)?;
+  /// Creates and applies a [signature][`Signature`] with the given proof `options`, e.g. a
+  /// verifier challenge, to the given `data`.
+  ///
+  /// The options are covered by the signature value.
+  fn create_signature_with_options<T>(
+    data: &mut T,
+    method: impl Into<String>,
+    options: SignatureOptions,
+    secret: &Secret,
+  ) -> Result<()>
+  where
+    T: Serialize + SetSignature,
+  {
+    data.set_signature(Signature::with_options(Self::NAME, method, options));
+
+    let value: SignatureValue = Self::sign(&signing_input(&*data)?, secret)?;
     let write: &mut Signature = data.try_signature_mut()?;
 
     write.set_value(value);
@@ -90,12 +109,168 @@ pub trait Verifier<Public: ?Sized>: Named {
       return Err(Error::InvalidProofValue("signature name"));
     }
 
-    signature.hide_value();
+    Self::verify(&signing_input(data)?, signature.value(), public)
+  }
+}
+
+// =============================================================================
+// =============================================================================
 
-    Self::verify(&data, signature.value(), public)?;
+/// The properties of a serialized [signature][`Signature`] holding its value.
+const SIGNATURE_VALUE_KEYS: &[&str] = &["jws", "proofValue", "signatureValue"];
 
-    signature.show_value();
+/// Serializes `data` as the input of a signature operation.
+///
+/// The value of the embedded [signature][`Signature`] is omitted entirely: its `type` and
+/// `verificationMethod` are signed but no `signatureValue` (or `jws`/`proofValue`) field is
+/// present, not even as an empty string, as required by the [JCS Ed25519 Signature 2020][SPEC]
+/// suite. Signing and verification use the same input.
+///
+/// The signature is located among the top-level properties of `data`, either as a single proof
+/// or as the first proof of a proof set.
+///
+/// [SPEC]: https://identity.foundation/JcsEd25519Signature2020/
+fn signing_input<T>(data: &T) -> Result<Value>
+where
+  T: Serialize + TrySignature,
+{
+  let signature: Value = data.try_signature()?.to_json_value()?;
+  let mut input: Value = data.to_json_value()?;
 
-    Ok(())
+  let proof: &mut Value = input
+    .as_object_mut()
+    .into_iter()
+    .flat_map(|object| object.values_mut())
+    .filter_map(|value| match value {
+      Value::Array(proofs) => proofs.first_mut(),
+      value => Some(value),
+    })
+    .find(|value| **value == signature)
+    .ok_or(Error::MissingSignature)?;
+
+  if let Value::Object(proof) = proof {
+    for key in SIGNATURE_VALUE_KEYS {
+      proof.remove(*key);
+    }
+  }
+
+  Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::convert::ToJson;
+  use crate::crypto::Ed25519;
+  use crate::crypto::JcsEd25519;
+  use crate::crypto::PrivateKey;
+  use crate::crypto::PublicKey;
+  use crate::crypto::SetSignature;
+  use crate::crypto::Signature;
+  use crate::crypto::Signer;
+  use crate::crypto::TrySignature;
+  use crate::crypto::TrySignatureMut;
+  use crate::crypto::Verifier;
+  use crate::utils::decode_b58;
+
+  use super::signing_input;
+
+  const PUBLIC: &str = "8CpYU3CXo1NEXVi5ZJcGgfmYjMoQ4xpewofpcPnWS5kt";
+  const SECRET: &str = "8gFfcuUTmX7P4DYfpEV7iVWzfSSV6QHQZFZamT6oNjVV";
+
+  // The canonical signing input and signature computed for `Document` by an independent
+  // JCS + Ed25519 implementation (Python `cryptography`).
+  const INPUT: &str = concat!(
+    r#"{"id":"did:example:123","proof":{"type":"JcsEd25519Signature2020","#,
+    r#""verificationMethod":"did:example:123#key-1"}}"#,
+  );
+  const SIGNATURE: &str = "2i7TNj9khszwp9A4EPvVLMHguz3mqx7jKrigZb67BN2yY3jTrzFDUaR7nLbj92EBQ5FCB1DKgdXeit3jkHBKQd7d";
+
+  #[derive(Serialize)]
+  struct Document {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof: Option<Signature>,
+  }
+
+  impl TrySignature for Document {
+    fn signature(&self) -> Option<&Signature> {
+      self.proof.as_ref()
+    }
+  }
+
+  impl TrySignatureMut for Document {
+    fn signature_mut(&mut self) -> Option<&mut Signature> {
+      self.proof.as_mut()
+    }
+  }
+
+  impl SetSignature for Document {
+    fn set_signature(&mut self, signature: Signature) {
+      self.proof = Some(signature);
+    }
+  }
+
+  #[derive(Serialize)]
+  struct ProofSet {
+    id: String,
+    proof: Vec<Signature>,
+  }
+
+  impl TrySignature for ProofSet {
+    fn signature(&self) -> Option<&Signature> {
+      self.proof.first()
+    }
+  }
+
+  impl TrySignatureMut for ProofSet {
+    fn signature_mut(&mut self) -> Option<&mut Signature> {
+      self.proof.first_mut()
+    }
+  }
+
+  impl SetSignature for ProofSet {
+    fn set_signature(&mut self, signature: Signature) {
+      self.proof = vec![signature];
+    }
+  }
+
+  #[test]
+  fn test_signing_input_reference() {
+    let public: PublicKey = decode_b58(PUBLIC).unwrap().into();
+    let private: PrivateKey = decode_b58(SECRET).unwrap().into();
+
+    let mut document: Document = Document {
+      id: "did:example:123".to_owned(),
+      proof: None,
+    };
+
+    JcsEd25519::<Ed25519<PrivateKey>>::create_signature(&mut document, "did:example:123#key-1", &private).unwrap();
+
+    // The signature value is omitted from the signing input but present afterwards.
+    assert_eq!(signing_input(&document).unwrap().to_jcs().unwrap(), INPUT.as_bytes());
+    assert_eq!(document.proof.as_ref().unwrap().value().as_str(), SIGNATURE);
+    assert!(document.to_json().unwrap().contains(SIGNATURE));
+
+    assert!(JcsEd25519::<Ed25519<PublicKey>>::verify_signature(&document, &public).is_ok());
+  }
+
+  #[test]
+  fn test_signing_input_proof_set() {
+    let public: PublicKey = decode_b58(PUBLIC).unwrap().into();
+    let private: PrivateKey = decode_b58(SECRET).unwrap().into();
+
+    let mut document: ProofSet = ProofSet {
+      id: "did:example:123".to_owned(),
+      proof: Vec::new(),
+    };
+
+    JcsEd25519::<Ed25519<PrivateKey>>::create_signature(&mut document, "did:example:123#key-1", &private).unwrap();
+
+    // The value is omitted from the first proof of the set.
+    let input: String = INPUT.replace(r#""proof":{"#, r#""proof":[{"#).replace("}}", "}]}");
+    assert_eq!(signing_input(&document).unwrap().to_jcs().unwrap(), input.as_bytes());
+    assert!(document.to_json().unwrap().contains(document.proof[0].value().as_str()));
+
+    assert!(JcsEd25519::<Ed25519<PublicKey>>::verify_signature(&document, &public).is_ok());
   }
 }
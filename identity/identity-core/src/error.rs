@@ -39,6 +39,12 @@ pub enum Error {
   /// Cause by a failure to decode a Roaring Bitmap.
   #[error("Failed to decode roaring bitmap: {0}")]
   DecodeBitmap(std::io::Error),
+  /// Cause by a failure to encode a status list bitstring.
+  #[error("Failed to encode status list: {0}")]
+  EncodeStatusList(std::io::Error),
+  /// Cause by a failure to decode a status list bitstring.
+  #[error("Failed to decode status list: {0}")]
+  DecodeStatusList(std::io::Error),
   /// Caused by attempting to perform an invalid `Diff` operation.
   #[error("Invalid Document Diff: {0}")]
   InvalidDiff(#[from] identity_diff::Error),
@@ -54,6 +60,9 @@ pub enum Error {
   /// Caused by attempting to parse an invalid DID proof.
   #[error("Invalid Proof Format")]
   InvalidProofFormat,
+  /// Raised by a verification attempt with a revoked Merkle Key Collection key.
+  #[error("Key Revoked: {0}")]
+  KeyRevoked(u32),
   /// Caused by attempting to parse an invalid cryptographic key.
   #[error("Invalid Key Format")]
   InvalidKeyFormat,
@@ -78,6 +87,9 @@ pub enum Error {
   /// Caused by attempting to create a Proof with more nodes than allowed.
   #[error("Invalid number of nodes in the Proof: {0}")]
   InvalidProofSize(usize),
+  /// Caused by updating a KeyCollection with a MerkleFrontier of a different number of keys.
+  #[error("Invalid Merkle Frontier Size. Received {0}, Expected {1}")]
+  InvalidMerkleFrontierSize(usize, usize),
 }
 
 impl From<crypto::Error> for Error {
@@ -0,0 +1,34 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+/// Splits a JSON Pointer ([RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901)) into its
+/// first reference token, unescaped, and the remaining pointer, e.g. `"/a~1b/c"` into
+/// `("a/b", "/c")`.
+///
+/// Returns `None` if `pointer` does not start with `/`, including the empty pointer.
+pub fn split_pointer(pointer: &str) -> Option<(String, &str)> {
+  let pointer: &str = pointer.strip_prefix('/')?;
+  let (token, pointer): (&str, &str) = pointer.find('/').map_or((pointer, ""), |index| pointer.split_at(index));
+
+  Some((token.replace("~1", "/").replace("~0", "~"), pointer))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_split_pointer() {
+    assert_eq!(split_pointer("/a/b/c"), Some(("a".to_owned(), "/b/c")));
+    assert_eq!(split_pointer("/a"), Some(("a".to_owned(), "")));
+    assert_eq!(split_pointer("/"), Some(("".to_owned(), "")));
+    assert_eq!(split_pointer("//a"), Some(("".to_owned(), "/a")));
+
+    // `~1` is unescaped before `~0`, so `~01` is the literal `~1`.
+    assert_eq!(split_pointer("/a~1b/c~0d"), Some(("a/b".to_owned(), "/c~0d")));
+    assert_eq!(split_pointer("/~01"), Some(("~1".to_owned(), "")));
+
+    assert_eq!(split_pointer(""), None);
+    assert_eq!(split_pointer("a/b"), None);
+  }
+}
@@ -1,10 +1,12 @@
 // Copyright 2020-2021 Runnerc
 // SPDX-License-Identifier: Apache-2.0
 
-//! Misc. utility functions (encoding, decoding, and ed25519 utils).
+//! Misc. utility functions (encoding, decoding, ed25519 and JSON Pointer utils).
 
 mod base_encoding;
 mod ed25519;
+mod json_pointer;
 
 pub use self::base_encoding::*;
 pub use self::ed25519::*;
+pub use self::json_pointer::*;
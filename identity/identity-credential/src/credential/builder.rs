@@ -93,6 +93,8 @@ impl<T> CredentialBuilder<T> {
   }
 
   /// Sets the value of the `Credential` `issuanceDate`.
+  ///
+  /// Defaults to the current time if not set.
   #[must_use]
   pub fn issuance_date(mut self, value: Timestamp) -> Self {
     self.issuance_date = Some(value);
@@ -100,6 +102,8 @@ impl<T> CredentialBuilder<T> {
   }
 
   /// Sets the value of the `Credential` `expirationDate`.
+  ///
+  /// Must be after the `issuanceDate`, or [`build`](Self::build) will fail.
   #[must_use]
   pub fn expiration_date(mut self, value: Timestamp) -> Self {
     self.expiration_date = Some(value);
@@ -258,6 +262,45 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_builder_default_issuance_date_now() {
+    let before: Timestamp = Timestamp::now_utc();
+    let credential: Credential = CredentialBuilder::default()
+      .subject(subject())
+      .issuer(issuer())
+      .build()
+      .unwrap();
+    let after: Timestamp = Timestamp::now_utc();
+
+    assert!(credential.issuance_date >= before);
+    assert!(credential.issuance_date <= after);
+  }
+
+  #[test]
+  fn test_builder_expiration_after_issuance() {
+    let credential: Credential = CredentialBuilder::default()
+      .subject(subject())
+      .issuer(issuer())
+      .issuance_date(Timestamp::parse("2010-01-01T00:00:00Z").unwrap())
+      .expiration_date(Timestamp::parse("2020-01-01T00:00:00Z").unwrap())
+      .build()
+      .unwrap();
+
+    assert_eq!(credential.expiration_date.unwrap().to_string(), "2020-01-01T00:00:00Z");
+  }
+
+  #[test]
+  #[should_panic = "InvalidExpirationDate"]
+  fn test_builder_expiration_before_issuance() {
+    let _: Credential = CredentialBuilder::default()
+      .subject(subject())
+      .issuer(issuer())
+      .issuance_date(Timestamp::parse("2020-01-01T00:00:00Z").unwrap())
+      .expiration_date(Timestamp::parse("2010-01-01T00:00:00Z").unwrap())
+      .build()
+      .unwrap();
+  }
+
   #[test]
   #[should_panic = "MissingSubject"]
   fn test_builder_missing_subjects() {
@@ -3,14 +3,17 @@
 
 use core::fmt::Display;
 use core::fmt::Formatter;
+use core::time::Duration;
 
 use serde::Serialize;
 
 use identity_core::common::Context;
 use identity_core::common::Object;
+use identity_core::common::ObjectExt;
 use identity_core::common::OneOrMany;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
+use identity_core::common::Value;
 use identity_core::convert::FmtJson;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signature;
@@ -21,8 +24,10 @@ use identity_did::verification::TryMethod;
 
 use crate::credential::CredentialBuilder;
 use crate::credential::Evidence;
+use crate::credential::ExpiryStatus;
 use crate::credential::Issuer;
 use crate::credential::Policy;
+use crate::credential::ProofPolicy;
 use crate::credential::Refresh;
 use crate::credential::Schema;
 use crate::credential::Status;
@@ -32,6 +37,8 @@ use crate::error::Result;
 
 lazy_static! {
   static ref BASE_CONTEXT: Context = Context::Url(Url::parse("https://www.w3.org/2018/credentials/v1").unwrap());
+  static ref SECURITY_CONTEXT: Context =
+    Context::Url(Url::parse("https://w3id.org/security/suites/ed25519-2020/v1").unwrap());
 }
 
 /// Represents a set of claims describing an entity.
@@ -80,8 +87,10 @@ pub struct Credential<T = Object> {
   #[serde(flatten)]
   pub properties: T,
   /// Proof(s) used to verify a `Credential`
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub proof: Option<Signature>,
+  ///
+  /// Each proof of a proof set is created over the `Credential` without the other proofs.
+  #[serde(default, skip_serializing_if = "OneOrMany::is_empty")]
+  pub proof: OneOrMany<Signature>,
 }
 
 impl<T> Credential<T> {
@@ -95,6 +104,22 @@ impl<T> Credential<T> {
     "VerifiableCredential"
   }
 
+  /// Returns the JSON-LD context of the Ed25519 signature suites used for DID-based proofs.
+  pub fn security_context() -> &'static Context {
+    &*SECURITY_CONTEXT
+  }
+
+  /// Appends the [security context][Credential::security_context] to the `context` set, unless
+  /// it is already present.
+  ///
+  /// NOTE: modifying the `context` invalidates an existing proof, so this should be called before
+  /// signing.
+  pub fn ensure_security_context(&mut self) {
+    if !self.context.contains(Self::security_context()) {
+      self.context.push(Self::security_context().clone());
+    }
+  }
+
   /// Creates a new `CredentialBuilder` to configure a `Credential`.
   ///
   /// This is the same as [CredentialBuilder::new].
@@ -103,14 +128,29 @@ impl<T> Credential<T> {
   }
 
   /// Returns a new `Credential` based on the `CredentialBuilder` configuration.
+  ///
+  /// The `issuanceDate` defaults to the current time when not set.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the `expirationDate` is not after the `issuanceDate`, or if
+  /// the resulting `Credential` is not structurally valid.
   pub fn from_builder(builder: CredentialBuilder<T>) -> Result<Self> {
+    let issuance_date: Timestamp = builder.issuance_date.unwrap_or_else(Timestamp::now_utc);
+
+    if let Some(expiration_date) = builder.expiration_date {
+      if expiration_date <= issuance_date {
+        return Err(Error::InvalidExpirationDate);
+      }
+    }
+
     let this: Self = Self {
       context: builder.context.into(),
       id: builder.id,
       types: builder.types.into(),
       credential_subject: builder.subject.into(),
       issuer: builder.issuer.ok_or(Error::MissingIssuer)?,
-      issuance_date: builder.issuance_date.unwrap_or_default(),
+      issuance_date,
       expiration_date: builder.expiration_date,
       credential_status: builder.status.into(),
       credential_schema: builder.schema.into(),
@@ -119,7 +159,7 @@ impl<T> Credential<T> {
       evidence: builder.evidence.into(),
       non_transferable: builder.non_transferable,
       properties: builder.properties,
-      proof: None,
+      proof: OneOrMany::default(),
     };
 
     this.check_structure()?;
@@ -155,14 +195,136 @@ impl<T> Credential<T> {
     Ok(())
   }
 
-  /// Returns a reference to the proof.
+  /// Validates the semantic structure of the `Credential`, additionally requiring every
+  /// `context` entry to be well-formed.
+  ///
+  /// See [`Credential::check_structure`].
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidContext`] if an object context is empty or contains term
+  /// definitions other than strings, objects or `null`. Keywords such as `@version` or
+  /// `@protected` may also be booleans or numbers. URL contexts may use any scheme, e.g. `urn:`
+  /// or `did:`.
+  pub fn verify_structure_strict(&self) -> Result<()> {
+    self.check_structure()?;
+
+    for (index, context) in self.context.iter().enumerate() {
+      if !check_context(context) {
+        return Err(Error::InvalidContext(index));
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Returns whether the `Credential` is expired or expires within `warn_window` of `now`,
+  /// based on its `expiration_date`.
+  ///
+  /// NOTE: this does not verify the credential proof.
+  pub fn expiry_status(&self, now: Timestamp, warn_window: Duration) -> ExpiryStatus {
+    let expiration: i64 = match self.expiration_date {
+      Some(expiration) => expiration.to_unix(),
+      None => return ExpiryStatus::Valid,
+    };
+
+    let remaining: i64 = expiration - now.to_unix();
+
+    if remaining <= 0 {
+      ExpiryStatus::Expired
+    } else if (remaining as u64) <= warn_window.as_secs() {
+      ExpiryStatus::ExpiringSoon
+    } else {
+      ExpiryStatus::Valid
+    }
+  }
+
+  /// Returns the claim referenced by the JSON Pointer `pointer` within the properties of the
+  /// credential subject at `subject_index`, e.g. `credential.get_claim(0, "/degree/name")`.
+  ///
+  /// Returns `None` if there is no subject at `subject_index` or `pointer` does not reference a
+  /// claim. See [`ObjectExt::pointer`].
+  pub fn get_claim(&self, subject_index: usize, pointer: &str) -> Option<&Value> {
+    self.credential_subject.get(subject_index)?.properties.pointer(pointer)
+  }
+
+  /// Returns an iterator over the `id`s of the credential subjects, skipping subjects without one.
+  pub fn subject_dids(&self) -> impl Iterator<Item = &Url> + '_ {
+    self.credential_subject.iter().filter_map(|subject| subject.id.as_ref())
+  }
+
+  /// Returns a reference to the proof, or the first proof of a proof set.
   pub fn proof(&self) -> Option<&Signature> {
-    self.proof.as_ref()
+    self.proof.first()
   }
 
-  /// Returns a mutable reference to the proof.
+  /// Returns a mutable reference to the proof, or the first proof of a proof set.
   pub fn proof_mut(&mut self) -> Option<&mut Signature> {
-    self.proof.as_mut()
+    self.proof.get_mut(0)
+  }
+
+  /// Returns all proofs of the `Credential`.
+  pub fn proofs(&self) -> &[Signature] {
+    self.proof.as_slice()
+  }
+
+  /// Adds `proof` to the proof set of the `Credential`.
+  ///
+  /// The `proof` must have been created over the [unsigned][Credential::unsigned] `Credential`.
+  pub fn push_proof(&mut self, proof: Signature) {
+    self.proof.push(proof);
+  }
+
+  /// Returns a copy of the `Credential` without any proofs, e.g. to create an additional proof.
+  pub fn unsigned(&self) -> Self
+  where
+    T: Clone,
+  {
+    self.with_proofs(OneOrMany::default())
+  }
+
+  /// Verifies the proofs of the `Credential` as required by `policy`.
+  ///
+  /// `verify` is called for each proof with a copy of the `Credential` carrying only that proof,
+  /// since each proof of a proof set is created without the other proofs.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidProof`] if the `Credential` has no proofs or the proofs do not
+  /// satisfy `policy`.
+  pub fn verify_proofs<F>(&self, policy: ProofPolicy, mut verify: F) -> Result<()>
+  where
+    T: Clone,
+    F: FnMut(&Self) -> bool,
+  {
+    if self.proof.is_empty() {
+      return Err(Error::InvalidProof);
+    }
+
+    let mut verified = self
+      .proof
+      .iter()
+      .map(|proof| verify(&self.with_proofs(OneOrMany::One(proof.clone()))));
+
+    let satisfied: bool = match policy {
+      ProofPolicy::Any => verified.any(|verified| verified),
+      ProofPolicy::All => verified.all(|verified| verified),
+    };
+
+    if satisfied {
+      Ok(())
+    } else {
+      Err(Error::InvalidProof)
+    }
+  }
+
+  fn with_proofs(&self, proof: OneOrMany<Signature>) -> Self
+  where
+    T: Clone,
+  {
+    let mut this: Self = self.clone();
+    this.proof = proof;
+    this
   }
 }
 
@@ -177,22 +339,250 @@ where
 
 impl<T> TrySignature for Credential<T> {
   fn signature(&self) -> Option<&Signature> {
-    self.proof.as_ref()
+    self.proof()
   }
 }
 
 impl<T> TrySignatureMut for Credential<T> {
   fn signature_mut(&mut self) -> Option<&mut Signature> {
-    self.proof.as_mut()
+    self.proof_mut()
   }
 }
 
+/// Replaces all proofs of the `Credential` with the single proof `value`.
 impl<T> SetSignature for Credential<T> {
   fn set_signature(&mut self, value: Signature) {
-    self.proof.replace(value);
+    self.proof = OneOrMany::One(value);
   }
 }
 
 impl<T> TryMethod for Credential<T> {
   const TYPE: MethodUriType = MethodUriType::Absolute;
 }
+
+fn check_context(context: &Context) -> bool {
+  match context {
+    // A `Url` is always absolute.
+    Context::Url(_) => true,
+    Context::Obj(object) => {
+      !object.is_empty()
+        && object.iter().all(|(key, value)| {
+          let keyword: bool = key.starts_with('@') && (value.is_boolean() || value.is_number());
+
+          !key.is_empty() && (keyword || value.is_string() || value.is_object() || value.is_null())
+        })
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use identity_core::crypto::Ed25519;
+  use identity_core::crypto::JcsEd25519;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::Signer;
+  use identity_core::crypto::Verifier;
+  use serde_json::json;
+
+  use super::*;
+
+  fn credential(context: serde_json::Value) -> Credential {
+    Credential::from_json_value(json!({
+      "@context": context,
+      "type": "VerifiableCredential",
+      "credentialSubject": { "id": "did:example:subject" },
+      "issuer": "did:example:issuer",
+      "issuanceDate": "2021-01-01T00:00:00Z"
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn test_verify_structure_strict() {
+    let credential: Credential = credential(json!([
+      "https://www.w3.org/2018/credentials/v1",
+      "https://www.w3.org/2018/credentials/examples/v1",
+      "urn:example:context",
+      "did:example:123#context",
+      { "name": "https://schema.org/name", "ex": { "@id": "https://example.com/ex" }, "@vocab": null },
+      { "@version": 1.1, "@protected": true, "@propagate": false, "name": "https://schema.org/name" }
+    ]));
+
+    assert!(credential.check_structure().is_ok());
+    assert!(credential.verify_structure_strict().is_ok());
+  }
+
+  #[test]
+  fn test_verify_structure_strict_malformed_context() {
+    let malformed: &[serde_json::Value] = &[
+      json!({}),
+      json!({ "name": 1 }),
+      json!({ "name": true }),
+      json!({ "": "https://schema.org/name" }),
+    ];
+
+    for entry in malformed {
+      let credential: Credential = credential(json!(["https://www.w3.org/2018/credentials/v1", entry]));
+
+      // The lenient check only inspects the base context.
+      assert!(credential.check_structure().is_ok());
+      assert!(matches!(
+        credential.verify_structure_strict(),
+        Err(Error::InvalidContext(1))
+      ));
+    }
+  }
+
+  #[test]
+  fn test_verify_structure_strict_missing_base_context() {
+    let credential: Credential = credential(json!(["https://www.w3.org/2018/credentials/examples/v1"]));
+
+    assert!(matches!(
+      credential.verify_structure_strict(),
+      Err(Error::MissingBaseContext)
+    ));
+  }
+
+  #[test]
+  fn test_ensure_security_context() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut credential: Credential = credential(json!("https://www.w3.org/2018/credentials/v1"));
+
+    // Signing twice must not duplicate the context.
+    for _ in 0..2 {
+      credential.ensure_security_context();
+      JcsEd25519::<Ed25519>::create_signature(&mut credential, "did:example:issuer#key-1", keypair.private().as_ref())
+        .unwrap();
+      assert!(verify(&keypair)(&credential));
+    }
+
+    let security: &Context = Credential::<Object>::security_context();
+    assert_eq!(
+      credential.context.iter().filter(|context| *context == security).count(),
+      1
+    );
+
+    // The base context remains first.
+    assert!(credential.check_structure().is_ok());
+  }
+
+  #[test]
+  fn test_expiry_status() {
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    let mut credential: Credential = credential(json!("https://www.w3.org/2018/credentials/v1"));
+    let now: Timestamp = Timestamp::parse("2021-06-01T00:00:00Z").unwrap();
+
+    // Credentials without an expiration date never expire.
+    assert_eq!(credential.expiry_status(now, DAY), ExpiryStatus::Valid);
+
+    credential.expiration_date = Some(Timestamp::parse("2021-06-10T00:00:00Z").unwrap());
+    assert_eq!(credential.expiry_status(now, DAY), ExpiryStatus::Valid);
+    assert_eq!(credential.expiry_status(now, DAY * 9), ExpiryStatus::ExpiringSoon);
+    assert_eq!(credential.expiry_status(now, DAY * 30), ExpiryStatus::ExpiringSoon);
+
+    let now: Timestamp = Timestamp::parse("2021-06-09T12:00:00Z").unwrap();
+    assert_eq!(credential.expiry_status(now, DAY), ExpiryStatus::ExpiringSoon);
+
+    // A credential is expired from its expiration date on.
+    let now: Timestamp = Timestamp::parse("2021-06-10T00:00:00Z").unwrap();
+    assert_eq!(credential.expiry_status(now, DAY), ExpiryStatus::Expired);
+    let now: Timestamp = Timestamp::parse("2022-01-01T00:00:00Z").unwrap();
+    assert_eq!(credential.expiry_status(now, Duration::from_secs(0)), ExpiryStatus::Expired);
+  }
+
+  #[test]
+  fn test_get_claim() {
+    let credential: Credential = Credential::from_json_value(json!({
+      "@context": "https://www.w3.org/2018/credentials/v1",
+      "type": "VerifiableCredential",
+      "credentialSubject": [
+        { "id": "did:example:alice", "degree": { "type": "BachelorDegree", "name": "Bachelor of Arts" } },
+        { "id": "did:example:bob", "degree": { "type": "MasterDegree" } }
+      ],
+      "issuer": "did:example:issuer",
+      "issuanceDate": "2021-01-01T00:00:00Z"
+    }))
+    .unwrap();
+
+    assert_eq!(
+      credential.get_claim(0, "/degree/name"),
+      Some(&json!("Bachelor of Arts"))
+    );
+    assert_eq!(credential.get_claim(1, "/degree/type"), Some(&json!("MasterDegree")));
+    assert_eq!(credential.get_claim(1, "/degree/name"), None);
+
+    // Out-of-range subject index.
+    assert_eq!(credential.get_claim(2, "/degree/type"), None);
+  }
+
+  #[test]
+  fn test_subject_dids() {
+    let credential: Credential = Credential::from_json_value(json!({
+      "@context": "https://www.w3.org/2018/credentials/v1",
+      "type": "VerifiableCredential",
+      "credentialSubject": [
+        { "id": "did:example:alice", "degree": "Bachelor of Arts" },
+        { "degree": "Master of Arts" },
+        { "id": "did:example:bob" }
+      ],
+      "issuer": "did:example:issuer",
+      "issuanceDate": "2021-01-01T00:00:00Z"
+    }))
+    .unwrap();
+
+    let dids: Vec<&str> = credential.subject_dids().map(Url::as_str).collect();
+
+    assert_eq!(dids, ["did:example:alice", "did:example:bob"]);
+  }
+
+  fn verify(keypair: &KeyPair) -> impl Fn(&Credential) -> bool + '_ {
+    move |credential| JcsEd25519::<Ed25519>::verify_signature(credential, keypair.public().as_ref()).is_ok()
+  }
+
+  #[test]
+  fn test_proof_set() {
+    let issuer: KeyPair = KeyPair::new_ed25519().unwrap();
+    let cosigner: KeyPair = KeyPair::new_ed25519().unwrap();
+    let unsigned: Credential = credential(json!("https://www.w3.org/2018/credentials/v1"));
+
+    let mut credential: Credential = unsigned.clone();
+    for (keypair, method) in [
+      (&issuer, "did:example:issuer#key-1"),
+      (&cosigner, "did:example:cosigner#key-1"),
+    ] {
+      let mut signed: Credential = unsigned.unsigned();
+      JcsEd25519::<Ed25519>::create_signature(&mut signed, method, keypair.private().as_ref()).unwrap();
+      credential.push_proof(signed.proof().unwrap().clone());
+    }
+
+    assert_eq!(credential.proofs().len(), 2);
+    assert!(credential.to_json_value().unwrap()["proof"].is_array());
+    assert_eq!(
+      Credential::from_json(&credential.to_json().unwrap()).unwrap(),
+      credential
+    );
+
+    // Each key only verifies its own proof.
+    assert!(credential.verify_proofs(ProofPolicy::Any, verify(&issuer)).is_ok());
+    assert!(credential.verify_proofs(ProofPolicy::Any, verify(&cosigner)).is_ok());
+    assert!(credential.verify_proofs(ProofPolicy::All, verify(&issuer)).is_err());
+    assert!(credential
+      .verify_proofs(ProofPolicy::All, |credential: &Credential| {
+        [&issuer, &cosigner]
+          .into_iter()
+          .any(|keypair| verify(keypair)(credential))
+      })
+      .is_ok());
+
+    // A proof set verifies as a whole only through `verify_proofs`.
+    assert!(JcsEd25519::<Ed25519>::verify_signature(&credential, issuer.public().as_ref()).is_err());
+
+    assert!(matches!(
+      unsigned.verify_proofs(ProofPolicy::Any, verify(&issuer)),
+      Err(Error::InvalidProof)
+    ));
+  }
+}
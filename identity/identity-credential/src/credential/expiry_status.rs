@@ -0,0 +1,15 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+/// The expiration state of a [`Credential`][crate::credential::Credential] at a given time.
+///
+/// See [`Credential::expiry_status`][crate::credential::Credential::expiry_status].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExpiryStatus {
+  /// The credential has no expiration date or does not expire within the warning window.
+  Valid,
+  /// The credential expires within the warning window.
+  ExpiringSoon,
+  /// The expiration date of the credential has passed.
+  Expired,
+}
@@ -8,8 +8,10 @@
 mod builder;
 mod credential;
 mod evidence;
+mod expiry_status;
 mod issuer;
 mod policy;
+mod proof_policy;
 mod refresh;
 mod schema;
 mod status;
@@ -18,8 +20,10 @@ mod subject;
 pub use self::builder::CredentialBuilder;
 pub use self::credential::Credential;
 pub use self::evidence::Evidence;
+pub use self::expiry_status::ExpiryStatus;
 pub use self::issuer::Issuer;
 pub use self::policy::Policy;
+pub use self::proof_policy::ProofPolicy;
 pub use self::refresh::Refresh;
 pub use self::schema::Schema;
 pub use self::status::Status;
@@ -0,0 +1,13 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+/// Specifies which proofs of a [`Credential`][crate::credential::Credential] must verify.
+///
+/// See [`Credential::verify_proofs`][crate::credential::Credential::verify_proofs].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProofPolicy {
+  /// At least one proof must verify.
+  Any,
+  /// Every proof must verify.
+  All,
+}
@@ -15,6 +15,9 @@ pub enum Error {
   /// Caused when validating a Credential without a valid base context.
   #[error("Missing Base Context")]
   MissingBaseContext,
+  /// Caused when validating a Credential with a malformed context entry at the given index.
+  #[error("Invalid Context at index {0}")]
+  InvalidContext(usize),
   /// Caused when validating a Credential without a valid base type.
   #[error("Missing Base Type")]
   MissingBaseType,
@@ -27,4 +30,11 @@ pub enum Error {
   /// Caused when validating a Credential with a malformed subject.
   #[error("Invalid Credential Subject")]
   InvalidSubject,
+  /// Caused when building a Credential that expires before it is issued.
+  #[error("Invalid Credential Expiration Date: must be after the issuance date")]
+  InvalidExpirationDate,
+  /// Caused when the proofs of a Credential do not satisfy the required
+  /// [`ProofPolicy`][crate::credential::ProofPolicy].
+  #[error("Invalid Credential Proof")]
+  InvalidProof,
 }
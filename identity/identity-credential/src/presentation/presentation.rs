@@ -68,6 +68,22 @@ impl<T, U> Presentation<T, U> {
     "VerifiablePresentation"
   }
 
+  /// Returns the JSON-LD context of the Ed25519 signature suites used for DID-based proofs.
+  pub fn security_context() -> &'static Context {
+    Credential::<U>::security_context()
+  }
+
+  /// Appends the [security context][Presentation::security_context] to the `context` set, unless
+  /// it is already present.
+  ///
+  /// NOTE: modifying the `context` invalidates an existing proof, so this should be called before
+  /// signing. The contexts of the contained credentials are not modified.
+  pub fn ensure_security_context(&mut self) {
+    if !self.context.contains(Self::security_context()) {
+      self.context.push(Self::security_context().clone());
+    }
+  }
+
   /// Creates a `PresentationBuilder` to configure a new Presentation.
   ///
   /// This is the same as [PresentationBuilder::new].
@@ -95,6 +111,12 @@ impl<T, U> Presentation<T, U> {
   }
 
   /// Validates the semantic structure of the `Presentation`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the [base context][Presentation::base_context] is not the first entry of the
+  /// `context` set, if the `types` do not contain the [base type][Presentation::base_type], or if
+  /// any of the contained credentials is structurally invalid.
   pub fn check_structure(&self) -> Result<()> {
     // Ensure the base context is present and in the correct location
     match self.context.get(0) {
@@ -160,10 +182,21 @@ impl<T> TryMethod for Presentation<T> {
 
 #[cfg(test)]
 mod tests {
+  use identity_core::common::Context;
+  use identity_core::common::Object;
+  use identity_core::common::OneOrMany;
+  use identity_core::common::Url;
   use identity_core::convert::FromJson;
+  use identity_core::crypto::Ed25519;
+  use identity_core::crypto::JcsEd25519;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::Signer;
+  use identity_core::crypto::Verifier;
+  use serde_json::json;
 
   use crate::credential::Credential;
   use crate::credential::Subject;
+  use crate::error::Error;
 
   use super::Presentation;
 
@@ -214,4 +247,93 @@ mod tests {
       "Bachelor of Science in Mechanical Engineering"
     );
   }
+
+  fn presentation() -> Presentation {
+    Presentation::from_json_value(json!({
+      "@context": "https://www.w3.org/2018/credentials/v1",
+      "type": "VerifiablePresentation",
+      "verifiableCredential": {
+        "@context": "https://www.w3.org/2018/credentials/v1",
+        "type": "VerifiableCredential",
+        "credentialSubject": { "id": "did:example:alice" },
+        "issuer": "did:example:issuer",
+        "issuanceDate": "2021-01-01T00:00:00Z"
+      },
+      "holder": "did:example:alice"
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn test_check_structure() {
+    assert!(presentation().check_structure().is_ok());
+  }
+
+  #[test]
+  fn test_check_structure_missing_base_type() {
+    let mut presentation: Presentation = presentation();
+    presentation.types = OneOrMany::One("CredentialManagerPresentation".to_owned());
+
+    assert!(matches!(presentation.check_structure(), Err(Error::MissingBaseType)));
+
+    presentation.types = OneOrMany::Many(Vec::new());
+
+    assert!(matches!(presentation.check_structure(), Err(Error::MissingBaseType)));
+  }
+
+  #[test]
+  fn test_check_structure_missing_base_context() {
+    let mut presentation: Presentation = presentation();
+    let examples: Context = Context::Url(Url::parse("https://www.w3.org/2018/credentials/examples/v1").unwrap());
+
+    // The base context must be the first entry.
+    presentation.context = OneOrMany::Many(vec![examples.clone(), Presentation::<Object>::base_context().clone()]);
+
+    assert!(matches!(presentation.check_structure(), Err(Error::MissingBaseContext)));
+
+    presentation.context = OneOrMany::One(examples);
+
+    assert!(matches!(presentation.check_structure(), Err(Error::MissingBaseContext)));
+
+    presentation.context = OneOrMany::Many(Vec::new());
+
+    assert!(matches!(presentation.check_structure(), Err(Error::MissingBaseContext)));
+  }
+
+  #[test]
+  fn test_check_structure_invalid_credential() {
+    let mut presentation: Presentation = presentation();
+    presentation.verifiable_credential.get_mut(0).unwrap().types = OneOrMany::One("ExampleCredential".to_owned());
+
+    assert!(matches!(presentation.check_structure(), Err(Error::MissingBaseType)));
+  }
+
+  #[test]
+  fn test_ensure_security_context() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut presentation: Presentation = presentation();
+
+    // Signing twice must not duplicate the context.
+    for _ in 0..2 {
+      presentation.ensure_security_context();
+      JcsEd25519::<Ed25519>::create_signature(&mut presentation, "did:example:alice#key-1", keypair.private().as_ref())
+        .unwrap();
+      assert!(JcsEd25519::<Ed25519>::verify_signature(&presentation, keypair.public().as_ref()).is_ok());
+    }
+
+    let security: &Context = Presentation::<Object>::security_context();
+    assert_eq!(
+      presentation
+        .context
+        .iter()
+        .filter(|context| *context == security)
+        .count(),
+      1
+    );
+
+    // The base context remains first and the credential contexts are not modified.
+    assert!(presentation.check_structure().is_ok());
+    let credential: &Credential = presentation.verifiable_credential.get(0).unwrap();
+    assert!(!credential.context.contains(security));
+  }
 }
@@ -0,0 +1,65 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use identity_did::did::CoreDID;
+use identity_did::did::DID;
+use identity_did::document::CoreDocument;
+use identity_did::verification::MethodData;
+use identity_did::verification::MethodScope;
+use identity_did::verification::MethodType;
+use identity_did::verification::VerificationMethod;
+
+fn method(controller: &CoreDID, fragment: &str) -> VerificationMethod {
+  VerificationMethod::builder(Default::default())
+    .id(controller.to_url().join(fragment).unwrap())
+    .controller(controller.clone())
+    .key_type(MethodType::Ed25519VerificationKey2018)
+    .key_data(MethodData::new_multibase(fragment.as_bytes()))
+    .build()
+    .unwrap()
+}
+
+fn document(size: usize) -> CoreDocument {
+  let controller: CoreDID = "did:example:1234".parse().unwrap();
+  let mut document: CoreDocument = CoreDocument::builder(Default::default())
+    .id(controller.clone())
+    .build()
+    .unwrap();
+
+  for index in 0..size {
+    let scope: MethodScope = match index % 3 {
+      0 => MethodScope::VerificationMethod,
+      1 => MethodScope::authentication(),
+      _ => MethodScope::capability_invocation(),
+    };
+
+    document
+      .insert_method(method(&controller, &format!("#key-{}", index)), scope)
+      .unwrap();
+  }
+
+  document
+}
+
+fn bench_resolve_method(c: &mut Criterion) {
+  let mut group = c.benchmark_group("resolve method");
+
+  for size in [10, 100, 1000] {
+    let document: CoreDocument = document(size);
+    let fragment: String = format!("#key-{}", size - 1);
+
+    group.bench_with_input(BenchmarkId::from_parameter(size), &fragment, |b, fragment| {
+      b.iter(|| black_box(document.resolve_method(fragment.as_str())))
+    });
+  }
+
+  group.finish();
+}
+
+criterion_group!(benches, bench_resolve_method);
+criterion_main!(benches);
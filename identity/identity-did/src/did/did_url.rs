@@ -7,6 +7,7 @@ use core::fmt::Display;
 use core::fmt::Formatter;
 
 use core::str::FromStr;
+use std::borrow::Cow;
 use std::cmp::Ordering;
 use std::convert::TryInto;
 use std::hash::Hash;
@@ -104,7 +105,7 @@ impl RelativeDIDUrl {
     self.path = value
       .filter(|s| !s.is_empty())
       .map(|s| {
-        if s.starts_with('/') && s.chars().all(is_char_path) {
+        if s.starts_with('/') && is_valid_component(s, is_char_path) {
           Ok(s.to_owned())
         } else {
           Err(DIDError::InvalidPath)
@@ -114,6 +115,24 @@ impl RelativeDIDUrl {
     Ok(())
   }
 
+  /// Set the [path](https://www.w3.org/TR/did-core/#path) component, percent-encoding any
+  /// characters that are not allowed in a path. The path must start with a '/'.
+  ///
+  /// See [`RelativeDIDUrl::set_path`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use identity_did::did::RelativeDIDUrl;
+  /// # let mut url = RelativeDIDUrl::new();
+  /// url.set_path_encoded(Some("/my path")).unwrap();
+  /// assert_eq!(url.path().unwrap(), "/my%20path");
+  /// ```
+  pub fn set_path_encoded(&mut self, value: Option<&str>) -> Result<(), DIDError> {
+    let encoded: Option<String> = value.map(|s| percent_encode(s, is_char_path));
+    self.set_path(encoded.as_deref())
+  }
+
   /// Return the [path](https://www.w3.org/TR/did-core/#query) component,
   /// excluding the leading '?' delimiter.
   ///
@@ -146,7 +165,7 @@ impl RelativeDIDUrl {
       .map(|mut s| {
         // Ignore leading '?' during validation.
         s = s.strip_prefix('?').unwrap_or(s);
-        if s.is_empty() || !s.chars().all(is_char_query) {
+        if s.is_empty() || !is_valid_component(s, is_char_query) {
           return Err(DIDError::InvalidQuery);
         }
         Ok(format!("?{}", s))
@@ -155,6 +174,15 @@ impl RelativeDIDUrl {
     Ok(())
   }
 
+  /// Set the [query](https://www.w3.org/TR/did-core/#query) component, percent-encoding any
+  /// characters that are not allowed in a query. A leading '?' is ignored.
+  ///
+  /// See [`RelativeDIDUrl::set_query`].
+  pub fn set_query_encoded(&mut self, value: Option<&str>) -> Result<(), DIDError> {
+    let encoded: Option<String> = value.map(|s| percent_encode(s.strip_prefix('?').unwrap_or(s), is_char_query));
+    self.set_query(encoded.as_deref())
+  }
+
   /// Return an iterator of `(name, value)` pairs in the query string.
   ///
   /// E.g. `"query1=a&query2=b" -> [("query1", "a"), ("query2", "b")]`
@@ -200,7 +228,7 @@ impl RelativeDIDUrl {
       .map(|mut s| {
         // Ignore leading '#' during validation.
         s = s.strip_prefix('#').unwrap_or(s);
-        if s.is_empty() || !s.chars().all(is_char_fragment) {
+        if s.is_empty() || !is_valid_component(s, is_char_fragment) {
           return Err(DIDError::InvalidFragment);
         }
         Ok(format!("#{}", s))
@@ -208,6 +236,25 @@ impl RelativeDIDUrl {
       .transpose()?;
     Ok(())
   }
+
+  /// Set the [fragment](https://www.w3.org/TR/did-core/#fragment) component, percent-encoding
+  /// any characters that are not allowed in a fragment. A leading '#' is ignored.
+  ///
+  /// See [`RelativeDIDUrl::set_fragment`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use identity_did::did::RelativeDIDUrl;
+  /// # let mut url = RelativeDIDUrl::new();
+  /// url.set_fragment_encoded(Some("#my fragment")).unwrap();
+  /// assert_eq!(url.fragment().unwrap(), "my%20fragment");
+  /// assert_eq!(url.to_string(), "#my%20fragment");
+  /// ```
+  pub fn set_fragment_encoded(&mut self, value: Option<&str>) -> Result<(), DIDError> {
+    let encoded: Option<String> = value.map(|s| percent_encode(s.strip_prefix('#').unwrap_or(s), is_char_fragment));
+    self.set_fragment(encoded.as_deref())
+  }
 }
 
 impl Display for RelativeDIDUrl {
@@ -353,6 +400,13 @@ where
     self.url.set_fragment(value)
   }
 
+  /// Sets the `fragment` component of the [`DIDUrl`], percent-encoding disallowed characters.
+  ///
+  /// See [`RelativeDIDUrl::set_fragment_encoded`].
+  pub fn set_fragment_encoded(&mut self, value: Option<&str>) -> Result<(), DIDError> {
+    self.url.set_fragment_encoded(value)
+  }
+
   /// Returns the [`DIDUrl`] `path` component.
   ///
   /// See [`RelativeDIDUrl::path`].
@@ -367,6 +421,13 @@ where
     self.url.set_path(value)
   }
 
+  /// Sets the `path` component of the [`DIDUrl`], percent-encoding disallowed characters.
+  ///
+  /// See [`RelativeDIDUrl::set_path_encoded`].
+  pub fn set_path_encoded(&mut self, value: Option<&str>) -> Result<(), DIDError> {
+    self.url.set_path_encoded(value)
+  }
+
   /// Returns the [`DIDUrl`] `query` component.
   ///
   /// See [`RelativeDIDUrl::query`].
@@ -381,6 +442,13 @@ where
     self.url.set_query(value)
   }
 
+  /// Sets the `query` component of the [`DIDUrl`], percent-encoding disallowed characters.
+  ///
+  /// See [`RelativeDIDUrl::set_query_encoded`].
+  pub fn set_query_encoded(&mut self, value: Option<&str>) -> Result<(), DIDError> {
+    self.url.set_query_encoded(value)
+  }
+
   /// Parses the [`DIDUrl`] query and returns an iterator of (key, value) pairs.
   ///
   /// See [`RelativeDIDUrl::query_pairs`].
@@ -388,6 +456,41 @@ where
     self.url.query_pairs()
   }
 
+  /// Compares two [`DIDUrl`]s, ignoring the order of `query` parameters.
+  ///
+  /// Unlike the byte-exact [`PartialEq`] implementation, `"?a=1&b=2"` and `"?b=2&a=1"` are
+  /// considered equal.
+  pub fn eq_canonical(&self, other: &Self) -> bool {
+    fn sorted_pairs(url: &RelativeDIDUrl) -> Vec<(Cow<'_, str>, Cow<'_, str>)> {
+      let mut pairs: Vec<(Cow<'_, str>, Cow<'_, str>)> = url.query_pairs().collect();
+      pairs.sort();
+      pairs
+    }
+
+    self.did() == other.did()
+      && self.path() == other.path()
+      && self.fragment() == other.fragment()
+      && sorted_pairs(self.url()) == sorted_pairs(other.url())
+  }
+
+  /// Returns the [`DIDUrl`] with the `fragment` component removed, leaving the rest unchanged.
+  pub fn without_fragment(mut self) -> Self {
+    self.url.fragment = None;
+    self
+  }
+
+  /// Returns the [`DIDUrl`] with the `query` component removed, leaving the rest unchanged.
+  pub fn without_query(mut self) -> Self {
+    self.url.query = None;
+    self
+  }
+
+  /// Returns the [`DIDUrl`] with the `path` component removed, leaving the rest unchanged.
+  pub fn without_path(mut self) -> Self {
+    self.url.path = None;
+    self
+  }
+
   /// Append a string representing a `path`, `query`, and/or `fragment` to this [`DIDUrl`].
   ///
   /// Must begin with a valid delimiter character: '/', '?', '#'. Overwrites the existing URL
@@ -600,6 +703,43 @@ pub(crate) const fn is_char_fragment(ch: char) -> bool {
   is_char_path(ch) || ch == '?'
 }
 
+/// Checks whether every character in `value` satisfies `is_char` or belongs to a valid
+/// percent-encoded triplet, e.g. `"%20"`.
+fn is_valid_component(value: &str, is_char: fn(char) -> bool) -> bool {
+  let mut chars = value.chars();
+
+  while let Some(ch) = chars.next() {
+    if ch == '%' {
+      match (chars.next(), chars.next()) {
+        (Some(hi), Some(lo)) if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit() => {}
+        _ => return false,
+      }
+    } else if !is_char(ch) {
+      return false;
+    }
+  }
+
+  true
+}
+
+/// Percent-encodes the UTF-8 bytes of every character in `value` not satisfying `is_char`.
+fn percent_encode(value: &str, is_char: fn(char) -> bool) -> String {
+  let mut output: String = String::with_capacity(value.len());
+  let mut buffer: [u8; 4] = [0; 4];
+
+  for ch in value.chars() {
+    if is_char(ch) {
+      output.push(ch);
+    } else {
+      for byte in ch.encode_utf8(&mut buffer).as_bytes() {
+        output.push_str(&format!("%{:02X}", byte));
+      }
+    }
+  }
+
+  output
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -828,6 +968,94 @@ mod tests {
     assert!(matches!(relative_url.set_fragment(Some("#fragment\"other\"")), Err(DIDError::InvalidFragment)));
   }
 
+  #[test]
+  fn test_did_url_eq_canonical() {
+    let did_url1 = CoreDIDUrl::parse("did:example:1234567890/path?a=1&b=2#fragment").unwrap();
+    let did_url2 = CoreDIDUrl::parse("did:example:1234567890/path?b=2&a=1#fragment").unwrap();
+    assert_ne!(did_url1, did_url2);
+    assert!(did_url1.eq_canonical(&did_url2));
+    assert!(did_url2.eq_canonical(&did_url1));
+    assert!(did_url1.eq_canonical(&did_url1));
+
+    // Differing values, paths, fragments, or DIDs are still unequal.
+    let did_url3 = CoreDIDUrl::parse("did:example:1234567890/path?b=1&a=2#fragment").unwrap();
+    assert!(!did_url1.eq_canonical(&did_url3));
+    let did_url4 = CoreDIDUrl::parse("did:example:1234567890/other?b=2&a=1#fragment").unwrap();
+    assert!(!did_url1.eq_canonical(&did_url4));
+    let did_url5 = CoreDIDUrl::parse("did:example:1234567890/path?b=2&a=1#other").unwrap();
+    assert!(!did_url1.eq_canonical(&did_url5));
+    let did_url6 = CoreDIDUrl::parse("did:example:0987654321/path?b=2&a=1#fragment").unwrap();
+    assert!(!did_url1.eq_canonical(&did_url6));
+    let did_url7 = CoreDIDUrl::parse("did:example:1234567890/path?a=1#fragment").unwrap();
+    assert!(!did_url1.eq_canonical(&did_url7));
+  }
+
+  #[test]
+  fn test_did_url_without_components() {
+    let did_url = CoreDIDUrl::parse("did:example:1234567890/path?query#fragment").unwrap();
+
+    let stripped = did_url.clone().without_fragment();
+    assert_eq!(stripped.to_string(), "did:example:1234567890/path?query");
+    assert_eq!(stripped.did(), did_url.did());
+    assert!(stripped.fragment().is_none());
+
+    let stripped = did_url.clone().without_query();
+    assert_eq!(stripped.to_string(), "did:example:1234567890/path#fragment");
+    assert!(stripped.query().is_none());
+    assert_eq!(stripped.path(), did_url.path());
+    assert_eq!(stripped.fragment(), did_url.fragment());
+
+    let stripped = did_url.clone().without_path();
+    assert_eq!(stripped.to_string(), "did:example:1234567890?query#fragment");
+    assert!(stripped.path().is_none());
+    assert_eq!(stripped.query(), did_url.query());
+
+    let stripped = did_url.clone().without_path().without_query().without_fragment();
+    assert_eq!(stripped.to_string(), "did:example:1234567890");
+    assert!(stripped.url().is_empty());
+  }
+
+  #[rustfmt::skip]
+  #[test]
+  fn test_set_encoded() {
+    let mut relative_url = RelativeDIDUrl::new();
+
+    // Disallowed characters are percent-encoded.
+    assert!(relative_url.set_path_encoded(Some("/white space/{resource}")).is_ok());
+    assert_eq!(relative_url.path().unwrap(), "/white%20space/%7Bresource%7D");
+    assert!(relative_url.set_query_encoded(Some("?name=a b&rate=100%")).is_ok());
+    assert_eq!(relative_url.query().unwrap(), "name=a%20b&rate=100%25");
+    assert!(relative_url.set_fragment_encoded(Some("#my fragment")).is_ok());
+    assert_eq!(relative_url.fragment().unwrap(), "my%20fragment");
+    assert!(relative_url.set_fragment_encoded(Some("ünïcode\"quoted\"")).is_ok());
+    assert_eq!(relative_url.fragment().unwrap(), "%C3%BCn%C3%AFcode%22quoted%22");
+
+    // The strict setters still reject unencoded input.
+    assert!(matches!(relative_url.set_path(Some("/white space")), Err(DIDError::InvalidPath)));
+    assert!(matches!(relative_url.set_fragment(Some("#my fragment")), Err(DIDError::InvalidFragment)));
+
+    // Malformed percent-encoding is rejected.
+    assert!(matches!(relative_url.set_path(Some("/bad%2")), Err(DIDError::InvalidPath)));
+    assert!(matches!(relative_url.set_query(Some("?bad=%zz")), Err(DIDError::InvalidQuery)));
+    assert!(matches!(relative_url.set_fragment(Some("#bad%")), Err(DIDError::InvalidFragment)));
+  }
+
+  #[test]
+  fn test_set_encoded_round_trip() {
+    let mut did_url = CoreDIDUrl::parse("did:example:1234567890").unwrap();
+    did_url.set_path_encoded(Some("/my path")).unwrap();
+    did_url.set_query_encoded(Some("key=some value")).unwrap();
+    did_url.set_fragment_encoded(Some("my fragment")).unwrap();
+    assert_eq!(
+      did_url.to_string(),
+      "did:example:1234567890/my%20path?key=some%20value#my%20fragment"
+    );
+
+    let parsed = CoreDIDUrl::parse(did_url.to_string()).unwrap();
+    assert_eq!(parsed, did_url);
+    assert_eq!(parsed.fragment().unwrap(), "my%20fragment");
+  }
+
   proptest::proptest! {
     #[test]
     fn test_fuzz_join_no_panic(s in "\\PC*") {
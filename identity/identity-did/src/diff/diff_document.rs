@@ -6,6 +6,8 @@ use serde::Serialize;
 
 use identity_core::common::Object;
 use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
 use identity_core::diff::Diff;
 use identity_core::diff::DiffString;
 use identity_core::diff::DiffVec;
@@ -203,6 +205,7 @@ where
       capability_invocation,
       service,
       properties,
+      method_index: Default::default(),
     })
   }
 
@@ -288,6 +291,7 @@ where
       capability_invocation,
       service,
       properties,
+      method_index: Default::default(),
     })
   }
 
@@ -308,6 +312,190 @@ where
   }
 }
 
+impl<T, U, V> DiffDocument<T, U, V>
+where
+  T: Diff + Serialize + for<'de> Deserialize<'de>,
+  U: Diff + Serialize + for<'de> Deserialize<'de> + Default,
+  V: Diff + Serialize + for<'de> Deserialize<'de> + Default,
+{
+  /// Returns the names of the fields changed by this diff, e.g. `"service"`.
+  pub fn changed_fields(&self) -> Vec<String> {
+    let fields: [(&str, bool); 11] = [
+      ("id", self.id.is_some()),
+      ("controller", self.controller.is_some()),
+      ("alsoKnownAs", self.also_known_as.is_some()),
+      ("verificationMethod", self.verification_method.is_some()),
+      ("authentication", self.authentication.is_some()),
+      ("assertionMethod", self.assertion_method.is_some()),
+      ("keyAgreement", self.key_agreement.is_some()),
+      ("capabilityDelegation", self.capability_delegation.is_some()),
+      ("capabilityInvocation", self.capability_invocation.is_some()),
+      ("service", self.service.is_some()),
+      ("properties", self.properties.is_some()),
+    ];
+
+    fields
+      .iter()
+      .filter(|(_, changed)| *changed)
+      .map(|(name, _)| (*name).to_owned())
+      .collect()
+  }
+
+  /// Returns the names of the fields changed by this diff which differ between `local` and
+  /// `base`, i.e. the fields changed both locally and by the diff since `base`.
+  ///
+  /// The properties of the documents are compared key by key and reported by key.
+  pub fn conflicts(&self, local: &CoreDocument<T, U, V>, base: &CoreDocument<T, U, V>) -> Vec<String> {
+    self.conflicts_with_metadata(local, base, &[])
+  }
+
+  /// Returns the conflicts like [`DiffDocument::conflicts`], ignoring the `metadata`
+  /// properties, e.g. a timestamp or proof which changes with every update.
+  pub fn conflicts_with_metadata(
+    &self,
+    local: &CoreDocument<T, U, V>,
+    base: &CoreDocument<T, U, V>,
+    metadata: &[&str],
+  ) -> Vec<String> {
+    let checks: [(&str, bool, bool); 10] = [
+      ("id", self.id.is_some(), local.id() != base.id()),
+      (
+        "controller",
+        self.controller.is_some(),
+        local.controller() != base.controller(),
+      ),
+      (
+        "alsoKnownAs",
+        self.also_known_as.is_some(),
+        local.also_known_as() != base.also_known_as(),
+      ),
+      (
+        "verificationMethod",
+        self.verification_method.is_some(),
+        local.verification_method() != base.verification_method(),
+      ),
+      (
+        "authentication",
+        self.authentication.is_some(),
+        local.authentication() != base.authentication(),
+      ),
+      (
+        "assertionMethod",
+        self.assertion_method.is_some(),
+        local.assertion_method() != base.assertion_method(),
+      ),
+      (
+        "keyAgreement",
+        self.key_agreement.is_some(),
+        local.key_agreement() != base.key_agreement(),
+      ),
+      (
+        "capabilityDelegation",
+        self.capability_delegation.is_some(),
+        local.capability_delegation() != base.capability_delegation(),
+      ),
+      (
+        "capabilityInvocation",
+        self.capability_invocation.is_some(),
+        local.capability_invocation() != base.capability_invocation(),
+      ),
+      ("service", self.service.is_some(), local.service() != base.service()),
+    ];
+
+    let mut conflicts: Vec<String> = checks
+      .iter()
+      .filter(|(_, changed, modified)| *changed && *modified)
+      .map(|(name, _, _)| (*name).to_owned())
+      .collect();
+
+    if let Some(properties) = self.properties.as_ref() {
+      if local.properties() != base.properties() {
+        conflicts.extend(property_conflicts(
+          properties,
+          local.properties(),
+          base.properties(),
+          metadata,
+        ));
+      }
+    }
+
+    conflicts
+  }
+}
+
+/// Returns the keys of the properties changed both by `diff` and in `local` since `base`,
+/// excluding the `metadata` keys.
+///
+/// Properties which do not serialize as JSON objects are compared as a whole.
+fn property_conflicts<T>(diff: &T::Type, local: &T, base: &T, metadata: &[&str]) -> Vec<String>
+where
+  T: Diff + Serialize,
+{
+  let to_object = |properties: &T| -> Option<Object> { Object::from_json_value(properties.to_json_value().ok()?).ok() };
+
+  let objects: Option<(Object, Object, Object)> = base
+    .merge(diff.clone())
+    .ok()
+    .and_then(|merged| Some((to_object(&merged)?, to_object(local)?, to_object(base)?)));
+
+  let (merged, local, base): (Object, Object, Object) = match objects {
+    Some(objects) => objects,
+    None => return vec!["properties".to_owned()],
+  };
+
+  let mut keys: Vec<&String> = merged.keys().chain(local.keys()).chain(base.keys()).collect();
+  keys.sort();
+  keys.dedup();
+
+  keys
+    .into_iter()
+    .filter(|key| !metadata.contains(&key.as_str()))
+    .filter(|key| merged.get(*key) != base.get(*key) && local.get(*key) != base.get(*key))
+    .cloned()
+    .collect()
+}
+
+impl<T, U, V> CoreDocument<T, U, V>
+where
+  T: Diff + Serialize + for<'de> Deserialize<'de>,
+  U: Diff + Serialize + for<'de> Deserialize<'de> + Default,
+  V: Diff + Serialize + for<'de> Deserialize<'de> + Default,
+{
+  /// Merges `diff` into `self`, rejecting changes to fields that were also changed locally.
+  ///
+  /// `base` is the document `diff` was created from. Unlike [`Diff::merge`], which silently
+  /// overwrites local changes, this fails if any field touched by `diff` differs between `self`
+  /// and `base`.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::MergeConflict`] listing the conflicting fields, or if the merge fails.
+  pub fn merge_strict(&self, base: &Self, diff: DiffDocument<T, U, V>) -> Result<Self> {
+    self.merge_strict_with_metadata(base, diff, &[])
+  }
+
+  /// Merges `diff` into `self` like [`CoreDocument::merge_strict`], ignoring changes to the
+  /// `metadata` properties. See [`DiffDocument::conflicts_with_metadata`].
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::MergeConflict`] listing the conflicting fields, or if the merge fails.
+  pub fn merge_strict_with_metadata(
+    &self,
+    base: &Self,
+    diff: DiffDocument<T, U, V>,
+    metadata: &[&str],
+  ) -> Result<Self> {
+    let conflicts: Vec<String> = diff.conflicts_with_metadata(self, base, metadata);
+
+    if !conflicts.is_empty() {
+      return Err(Error::MergeConflict(conflicts));
+    }
+
+    Diff::merge(self, diff)
+  }
+}
+
 #[cfg(test)]
 mod test {
   use std::collections::BTreeMap;
@@ -378,7 +566,7 @@ mod test {
     assert_ne!(doc, new);
 
     let diff = doc.diff(&new).unwrap();
-    assert_eq!(diff.id, Some(DiffString(Some(new_did.to_string()))));
+    assert_eq!(diff.id, Some(DiffString(Some(new_did.to_string().into()))));
     let merge = doc.merge(diff).unwrap();
     assert_eq!(merge, new);
   }
@@ -394,7 +582,7 @@ mod test {
     let diff = doc.diff(&new).unwrap();
     assert_eq!(
       diff.clone().controller.unwrap(),
-      Some(DiffString(Some(new_controller.to_string())))
+      Some(DiffString(Some(new_controller.to_string().into())))
     );
     let merge = doc.merge(diff).unwrap();
     assert_eq!(merge, new);
@@ -779,4 +967,72 @@ mod test {
     let diff = doc.diff(&new).unwrap();
     assert!(diff.capability_invocation.is_some());
   }
+
+  #[test]
+  fn test_merge_strict_conflict() {
+    let base = document();
+
+    // Two writers concurrently change the `alsoKnownAs` field of the same base document.
+    let mut local = base.clone();
+    local.also_known_as_mut().push("diff:local:1234".parse().unwrap());
+    let mut remote = base.clone();
+    remote.also_known_as_mut().push("diff:remote:1234".parse().unwrap());
+
+    let diff = base.diff(&remote).unwrap();
+    assert_eq!(diff.conflicts(&local, &base), vec!["alsoKnownAs".to_owned()]);
+
+    let error = local.merge_strict(&base, diff.clone()).unwrap_err();
+    assert!(matches!(error, Error::MergeConflict(fields) if fields == ["alsoKnownAs"]));
+
+    // The lenient merge still overwrites the local change.
+    assert_eq!(local.merge(diff).unwrap(), remote);
+  }
+
+  #[test]
+  fn test_merge_strict_disjoint_fields() {
+    let base = document();
+
+    let mut local = base.clone();
+    local.also_known_as_mut().push("diff:local:1234".parse().unwrap());
+    let mut remote = base.clone();
+    assert!(remote
+      .verification_method_mut()
+      .append(method(&base.clone().controller.unwrap(), "#key-diff")));
+
+    let diff = base.diff(&remote).unwrap();
+    assert!(diff.conflicts(&local, &base).is_empty());
+
+    let merge = local.merge_strict(&base, diff).unwrap();
+    assert_eq!(merge.also_known_as(), local.also_known_as());
+    assert_eq!(merge.verification_method(), remote.verification_method());
+  }
+
+  #[test]
+  fn test_merge_strict_properties() {
+    let base = document();
+
+    // Both writers change the metadata, but different properties otherwise.
+    let mut local = base.clone();
+    local.properties_mut().insert("updated".into(), "local".into());
+    local.properties_mut().insert("key1".into(), "local".into());
+    let mut remote = base.clone();
+    remote.properties_mut().insert("updated".into(), "remote".into());
+    remote.properties_mut().insert("key2".into(), "remote".into());
+
+    let diff = base.diff(&remote).unwrap();
+    assert_eq!(diff.conflicts(&local, &base), vec!["updated".to_owned()]);
+    assert!(diff.conflicts_with_metadata(&local, &base, &["updated"]).is_empty());
+
+    let merge = local.merge_strict_with_metadata(&base, diff, &["updated"]).unwrap();
+    assert_eq!(merge.properties().get("key1"), Some(&Value::from("local")));
+    assert_eq!(merge.properties().get("key2"), Some(&Value::from("remote")));
+
+    // Changes to the same property conflict.
+    remote.properties_mut().insert("key1".into(), "remote".into());
+    let diff = base.diff(&remote).unwrap();
+    assert_eq!(
+      diff.conflicts_with_metadata(&local, &base, &["updated"]),
+      vec!["key1".to_owned()]
+    );
+  }
 }
@@ -243,7 +243,7 @@ mod test {
     assert!(diff.key_data.is_none());
     assert!(diff.key_type.is_none());
     assert!(diff.properties.is_none());
-    assert_eq!(diff.id, Some(DiffString(Some("did:diff:123".to_string()))));
+    assert_eq!(diff.id, Some(DiffString(Some("did:diff:123".to_string().into()))));
 
     let merge = method.merge(diff).unwrap();
     assert_eq!(merge, new);
@@ -260,7 +260,7 @@ mod test {
     assert!(diff.key_data.is_none());
     assert!(diff.key_type.is_none());
     assert!(diff.properties.is_none());
-    assert_eq!(diff.controller, Some(DiffString(Some("did:diff:123".to_string()))));
+    assert_eq!(diff.controller, Some(DiffString(Some("did:diff:123".to_string().into()))));
 
     let merge = method.merge(diff).unwrap();
     assert_eq!(merge, new);
@@ -297,7 +297,7 @@ mod test {
     assert_eq!(
       diff.key_data,
       Some(DiffMethodData::PublicKeyBase58(Some(DiffString(Some(
-        "diff".to_string()
+        "diff".to_string().into()
       )))))
     );
 
@@ -319,7 +319,7 @@ mod test {
     assert_eq!(
       diff.key_data,
       Some(DiffMethodData::PublicKeyMultibase(Some(DiffString(Some(
-        "diff".to_string()
+        "diff".to_string().into()
       )))))
     );
 
@@ -196,7 +196,7 @@ mod test {
     assert!(diff.properties.is_none());
     assert!(diff.service_endpoint.is_none());
     assert!(diff.type_.is_none());
-    assert_eq!(diff.id, Some(DiffString(Some("did:diff:123".to_string()))));
+    assert_eq!(diff.id, Some(DiffString(Some("did:diff:123".to_string().into()))));
     let merge = service.merge(diff).unwrap();
     assert_eq!(merge, new);
   }
@@ -211,7 +211,7 @@ mod test {
     assert!(diff.properties.is_none());
     assert!(diff.service_endpoint.is_none());
     assert!(diff.id.is_none());
-    assert_eq!(diff.type_, Some(DiffString(Some("test_service_2".to_string()))));
+    assert_eq!(diff.type_, Some(DiffString(Some("test_service_2".to_string().into()))));
     let merge = service.merge(diff).unwrap();
     assert_eq!(merge, new);
   }
@@ -229,7 +229,7 @@ mod test {
     assert!(diff.type_.is_none());
     assert_eq!(
       diff.service_endpoint,
-      Some(DiffString(Some("\"did:test:1234#service\"".to_owned())))
+      Some(DiffString(Some("\"did:test:1234#service\"".to_owned().into())))
     );
     let merge = service.merge(diff).unwrap();
     assert_eq!(merge, new);
@@ -253,7 +253,7 @@ mod test {
     assert_eq!(
       diff.service_endpoint,
       Some(DiffString(Some(
-        r#"["https://example.com/","did:test:1234#service"]"#.to_owned()
+        r#"["https://example.com/","did:test:1234#service"]"#.to_owned().into()
       )))
     );
     let merge = service.merge(diff).unwrap();
@@ -284,7 +284,7 @@ mod test {
     assert_eq!(
       diff.service_endpoint,
       Some(DiffString(Some(
-        r#"{"origins":["https://example.com/","did:test:1234#service"]}"#.to_owned()
+        r#"{"origins":["https://example.com/","did:test:1234#service"]}"#.to_owned().into()
       )))
     );
     let merge = service.merge(diff).unwrap();
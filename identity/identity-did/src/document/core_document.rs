@@ -13,6 +13,10 @@ use identity_core::convert::FmtJson;
 
 use crate::did::CoreDID;
 use crate::did::CoreDIDUrl;
+use crate::did::DID;
+use crate::document::method_index::MethodEntries;
+use crate::document::method_index::MethodIndex;
+use crate::document::method_index::MethodSet;
 use crate::document::DocumentBuilder;
 use crate::error::Error;
 use crate::error::Result;
@@ -51,6 +55,8 @@ pub struct CoreDocument<T = Object, U = Object, V = Object> {
   pub(crate) service: OrderedSet<Service<V>>,
   #[serde(flatten)]
   pub(crate) properties: T,
+  #[serde(skip)]
+  pub(crate) method_index: MethodIndex,
 }
 
 impl<T, U, V> CoreDocument<T, U, V> {
@@ -75,6 +81,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
       capability_invocation: builder.capability_invocation.try_into()?,
       service: builder.service.try_into()?,
       properties: builder.properties,
+      method_index: MethodIndex::default(),
     })
   }
 
@@ -115,6 +122,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
 
   /// Returns a mutable reference to the `CoreDocument` verificationMethod set.
   pub fn verification_method_mut(&mut self) -> &mut OrderedSet<VerificationMethod<U>> {
+    self.method_index.invalidate();
     &mut self.verification_method
   }
 
@@ -125,6 +133,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
 
   /// Returns a mutable reference to the `CoreDocument` authentication set.
   pub fn authentication_mut(&mut self) -> &mut OrderedSet<MethodRef<U>> {
+    self.method_index.invalidate();
     &mut self.authentication
   }
 
@@ -135,6 +144,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
 
   /// Returns a mutable reference to the `CoreDocument` assertionMethod set.
   pub fn assertion_method_mut(&mut self) -> &mut OrderedSet<MethodRef<U>> {
+    self.method_index.invalidate();
     &mut self.assertion_method
   }
 
@@ -145,6 +155,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
 
   /// Returns a mutable reference to the `CoreDocument` keyAgreement set.
   pub fn key_agreement_mut(&mut self) -> &mut OrderedSet<MethodRef<U>> {
+    self.method_index.invalidate();
     &mut self.key_agreement
   }
 
@@ -155,6 +166,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
 
   /// Returns a mutable reference to the `CoreDocument` capabilityDelegation set.
   pub fn capability_delegation_mut(&mut self) -> &mut OrderedSet<MethodRef<U>> {
+    self.method_index.invalidate();
     &mut self.capability_delegation
   }
 
@@ -165,6 +177,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
 
   /// Returns a mutable reference to the `CoreDocument` capabilityInvocation set.
   pub fn capability_invocation_mut(&mut self) -> &mut OrderedSet<MethodRef<U>> {
+    self.method_index.invalidate();
     &mut self.capability_invocation
   }
 
@@ -206,6 +219,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
       capability_invocation: self.capability_invocation,
       service: self.service,
       properties: f(self.properties),
+      method_index: self.method_index,
     }
   }
 
@@ -230,6 +244,7 @@ impl<T, U, V> CoreDocument<T, U, V> {
       capability_invocation: self.capability_invocation,
       service: self.service,
       properties: f(self.properties)?,
+      method_index: self.method_index,
     })
   }
 
@@ -243,6 +258,8 @@ impl<T, U, V> CoreDocument<T, U, V> {
       return Err(Error::MethodAlreadyExists);
     }
 
+    self.method_index.invalidate();
+
     match scope {
       MethodScope::VerificationMethod => self.verification_method.append(method),
       MethodScope::VerificationRelationship(MethodRelationship::Authentication) => {
@@ -271,6 +288,8 @@ impl<T, U, V> CoreDocument<T, U, V> {
   ///
   /// Returns an error if the method does not exist.
   pub fn remove_method(&mut self, did: &CoreDIDUrl) -> Result<()> {
+    self.method_index.invalidate();
+
     let was_removed: bool = [
       self.authentication.remove(did),
       self.assertion_method.remove(did),
@@ -326,6 +345,78 @@ impl<T, U, V> CoreDocument<T, U, V> {
     }
   }
 
+  /// Attaches the relationship to the method resolved by `method_query`, first moving the
+  /// method into the set of verification methods if it is embedded in a verification
+  /// relationship.
+  ///
+  /// See [`Self::promote_method`] and [`Self::attach_method_relationship`].
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the method does not exist.
+  pub fn attach_method_relationship_promoting<'query, Q>(
+    &mut self,
+    method_query: Q,
+    relationship: MethodRelationship,
+  ) -> Result<bool>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    let method_query: MethodQuery<'query> = method_query.into();
+
+    self.promote_method(method_query.clone())?;
+    self.attach_method_relationship(method_query, relationship)
+  }
+
+  /// Moves the method embedded in a verification relationship and resolved by `method_query`
+  /// into the set of verification methods, leaving a [`MethodRef::Refer`] in its place.
+  ///
+  /// Returns `false` if the method is already in the set of verification methods.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error if the method does not exist.
+  pub fn promote_method<'query, Q>(&mut self, method_query: Q) -> Result<bool>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    let method_query: MethodQuery<'query> = method_query.into();
+
+    if self
+      .resolve_method_with_scope(method_query.clone(), MethodScope::VerificationMethod)
+      .is_some()
+    {
+      return Ok(false);
+    }
+
+    self.method_index.invalidate();
+
+    let relationships: [&mut OrderedSet<MethodRef<U>>; 5] = [
+      &mut self.authentication,
+      &mut self.assertion_method,
+      &mut self.key_agreement,
+      &mut self.capability_delegation,
+      &mut self.capability_invocation,
+    ];
+
+    for relationship in relationships {
+      let method_ref: &mut MethodRef<U> = match relationship.query_mut(method_query.clone()) {
+        Some(method_ref) if matches!(method_ref, MethodRef::Embed(_)) => method_ref,
+        _ => continue,
+      };
+
+      let reference: MethodRef<U> = MethodRef::Refer(method_ref.id().clone());
+
+      if let MethodRef::Embed(method) = core::mem::replace(method_ref, reference) {
+        self.verification_method.append(method);
+      }
+
+      return Ok(true);
+    }
+
+    Err(Error::MethodNotFound)
+  }
+
   /// Detaches the relationship from the method resolved by `method_query`.
   ///
   /// # Errors
@@ -469,6 +560,28 @@ impl<T, U, V> CoreDocument<T, U, V> {
       .ok_or(Error::MethodNotFound)
   }
 
+  /// Returns the absolute DID Url of the verification method `reference`, e.g. the
+  /// `verificationMethod` of a [`Signature`](identity_core::crypto::Signature).
+  ///
+  /// A relative reference such as a fragment (`#key-1`, or `key-1`) is resolved against the
+  /// document `id`, so it is equivalent to the absolute DID Url of the same method. An absolute
+  /// DID Url is returned as-is.
+  ///
+  /// # Errors
+  ///
+  /// Fails if `reference` is not a valid DID Url.
+  pub fn method_url(&self, reference: &str) -> Result<CoreDIDUrl> {
+    let method_url: CoreDIDUrl = if reference.starts_with(&format!("{}:", CoreDID::SCHEME)) {
+      CoreDIDUrl::parse(reference)?
+    } else if reference.starts_with('#') || reference.starts_with('/') || reference.starts_with('?') {
+      self.id.to_url().join(reference)?
+    } else {
+      self.id.to_url().join(format!("#{}", reference))?
+    };
+
+    Ok(method_url)
+  }
+
   /// Returns a mutable reference to the first [`VerificationMethod`] with an `id` property
   /// matching the provided `query`.
   pub fn resolve_method_mut<'query, Q>(&mut self, query: Q) -> Option<&mut VerificationMethod<U>>
@@ -495,41 +608,67 @@ impl<T, U, V> CoreDocument<T, U, V> {
   pub fn resolve_method_ref<'a>(&'a self, method_ref: &'a MethodRef<U>) -> Option<&'a VerificationMethod<U>> {
     match method_ref {
       MethodRef::Embed(method) => Some(method),
-      MethodRef::Refer(did) => self.verification_method.query(did),
+      MethodRef::Refer(did) => did
+        .fragment()
+        .and_then(|fragment| self.method_entries().verification_method.get(fragment))
+        .and_then(|index| self.verification_method.get(*index)),
     }
   }
 
-  fn resolve_method_inner(&self, query: MethodQuery<'_>) -> Option<&VerificationMethod<U>> {
-    let mut method: Option<&MethodRef<U>> = None;
-
-    if method.is_none() {
-      method = self.authentication.query(query.clone());
-    }
-
-    if method.is_none() {
-      method = self.assertion_method.query(query.clone());
-    }
+  /// Returns the index of the verification methods, building it if the document was mutated
+  /// since the last resolution.
+  fn method_entries(&self) -> &MethodEntries {
+    self.method_index.get_or_init(|| {
+      let mut entries: MethodEntries = MethodEntries::default();
+
+      // Verification relationships take precedence over the set of verification methods.
+      let relationships: [(MethodSet, &OrderedSet<MethodRef<U>>); 5] = [
+        (MethodSet::Authentication, &self.authentication),
+        (MethodSet::AssertionMethod, &self.assertion_method),
+        (MethodSet::KeyAgreement, &self.key_agreement),
+        (MethodSet::CapabilityDelegation, &self.capability_delegation),
+        (MethodSet::CapabilityInvocation, &self.capability_invocation),
+      ];
+
+      for (set, methods) in relationships {
+        for (index, method) in methods.iter().enumerate() {
+          if let Some(fragment) = method.id().fragment() {
+            entries.insert(fragment, set, index);
+          }
+        }
+      }
 
-    if method.is_none() {
-      method = self.key_agreement.query(query.clone());
-    }
+      for (index, method) in self.verification_method.iter().enumerate() {
+        if let Some(fragment) = method.id().fragment() {
+          entries.insert(fragment, MethodSet::VerificationMethod, index);
+        }
+      }
 
-    if method.is_none() {
-      method = self.capability_delegation.query(query.clone());
-    }
+      entries
+    })
+  }
 
-    if method.is_none() {
-      method = self.capability_invocation.query(query.clone());
-    }
+  fn resolve_method_inner(&self, query: MethodQuery<'_>) -> Option<&VerificationMethod<U>> {
+    let (set, index): (MethodSet, usize) = *self.method_entries().methods.get(query.fragment()?)?;
+
+    let relationship: &OrderedSet<MethodRef<U>> = match set {
+      MethodSet::VerificationMethod => return self.verification_method.get(index),
+      MethodSet::Authentication => &self.authentication,
+      MethodSet::AssertionMethod => &self.assertion_method,
+      MethodSet::KeyAgreement => &self.key_agreement,
+      MethodSet::CapabilityDelegation => &self.capability_delegation,
+      MethodSet::CapabilityInvocation => &self.capability_invocation,
+    };
 
-    match method {
-      Some(MethodRef::Embed(method)) => Some(method),
-      Some(MethodRef::Refer(did)) => self.verification_method.query(&did.to_string()),
-      None => self.verification_method.query(query),
-    }
+    relationship
+      .get(index)
+      .and_then(|method_ref| self.resolve_method_ref(method_ref))
   }
 
   fn resolve_method_mut_inner(&mut self, query: MethodQuery<'_>) -> Option<&mut VerificationMethod<U>> {
+    // The returned method may be modified, including its id.
+    self.method_index.invalidate();
+
     let mut method: Option<&mut MethodRef<U>> = None;
 
     if method.is_none() {
@@ -574,8 +713,11 @@ where
 #[cfg(test)]
 mod tests {
   use crate::did::CoreDID;
+  use crate::did::CoreDIDUrl;
   use crate::did::DID;
   use crate::document::CoreDocument;
+  use crate::error::Error;
+  use crate::verification::MethodRef;
   use crate::verification::MethodData;
   use crate::verification::MethodRelationship;
   use crate::verification::MethodScope;
@@ -646,6 +788,25 @@ mod tests {
     assert_eq!(document.resolve_method(""), None);
   }
 
+  #[test]
+  fn test_method_url() {
+    let document: CoreDocument = document();
+    let expected: CoreDIDUrl = controller().to_url().join("#key-1").unwrap();
+
+    // Relative references are resolved against the document id.
+    assert_eq!(document.method_url("#key-1").unwrap(), expected);
+    assert_eq!(document.method_url("key-1").unwrap(), expected);
+
+    // Absolute references are unchanged.
+    assert_eq!(document.method_url("did:example:1234#key-1").unwrap(), expected);
+    assert_eq!(
+      document.method_url("did:example:5678#key-1").unwrap().to_string(),
+      "did:example:5678#key-1"
+    );
+
+    assert!(document.method_url("did:").is_err());
+  }
+
   #[rustfmt::skip]
   #[test]
   fn test_methods_index() {
@@ -656,6 +817,55 @@ mod tests {
     assert_eq!(document.methods().nth(2).unwrap().id().to_string(), "did:example:1234#key-3");
   }
 
+  #[test]
+  fn test_method_index_consistency() {
+    let mut document: CoreDocument = document();
+    assert!(!document.method_index.is_built());
+
+    assert!(document.resolve_method("#key-1").is_some());
+    assert!(document.resolve_method("#index-test").is_none());
+    assert!(document.method_index.is_built());
+
+    // Inserting a method invalidates the index.
+    let method: VerificationMethod = method(document.id(), "#index-test");
+    document
+      .insert_method(method.clone(), MethodScope::VerificationMethod)
+      .unwrap();
+    assert_eq!(document.resolve_method("#index-test"), Some(&method));
+
+    // Attached relationships resolve to the referenced method.
+    document
+      .attach_method_relationship("#index-test", MethodRelationship::Authentication)
+      .unwrap();
+    assert_eq!(document.resolve_method("#index-test"), Some(&method));
+    let method_ref: &MethodRef = document.authentication().query("#index-test").unwrap();
+    assert_eq!(document.resolve_method_ref(method_ref), Some(&method));
+
+    // Removing a method invalidates the index.
+    document.remove_method(method.id()).unwrap();
+    assert!(document.resolve_method("#index-test").is_none());
+    assert!(document.authentication().query("#index-test").is_none());
+
+    // Mutations through accessors invalidate the index.
+    assert!(document.resolve_method("#auth-key").is_some());
+    document.authentication_mut().clear();
+    assert!(document.resolve_method("#auth-key").is_none());
+
+    assert!(document.resolve_method("#key-2").is_some());
+    let renamed: CoreDIDUrl = document.id().to_url().join("#key-5").unwrap();
+    *document.resolve_method_mut("#key-2").unwrap().id_mut() = renamed;
+    assert!(document.resolve_method("#key-2").is_none());
+    assert_eq!(
+      document.resolve_method("#key-5").unwrap().id().to_string(),
+      "did:example:1234#key-5"
+    );
+
+    // The index does not affect equality.
+    let fresh: CoreDocument = document.clone().map(|properties| properties);
+    assert_eq!(document, fresh);
+    assert_eq!(document, document.clone());
+  }
+
   #[test]
   fn test_attach_verification_relationships() {
     let mut document: CoreDocument = document();
@@ -701,6 +911,48 @@ mod tests {
       .is_err());
   }
 
+  #[test]
+  fn test_attach_verification_relationships_promoting() {
+    let mut document: CoreDocument = document();
+    let auth_key = document.id().to_url().join("#auth-key").unwrap();
+
+    assert!(matches!(
+      document.attach_method_relationship(auth_key.clone(), MethodRelationship::AssertionMethod),
+      Err(Error::InvalidMethodEmbedded)
+    ));
+
+    assert!(document
+      .attach_method_relationship_promoting(auth_key.clone(), MethodRelationship::AssertionMethod)
+      .unwrap());
+
+    // The method moved into the verification methods and is referenced by both relationships.
+    assert!(document
+      .resolve_method_with_scope("#auth-key", MethodScope::VerificationMethod)
+      .is_some());
+    assert!(document
+      .authentication()
+      .iter()
+      .any(|method_ref| matches!(method_ref, MethodRef::Refer(did_url) if did_url == &auth_key)));
+    assert!(document
+      .assertion_method()
+      .iter()
+      .any(|method_ref| matches!(method_ref, MethodRef::Refer(did_url) if did_url == &auth_key)));
+    assert_eq!(document.methods().filter(|method| method.id() == &auth_key).count(), 1);
+    assert!(document.resolve_method("#auth-key").is_some());
+
+    // Promoting again is a no-op and attaching is idempotent.
+    assert!(!document.promote_method("#auth-key").unwrap());
+    assert!(!document
+      .attach_method_relationship_promoting(auth_key, MethodRelationship::AssertionMethod)
+      .unwrap());
+
+    // Missing methods still fail.
+    assert!(matches!(
+      document.attach_method_relationship_promoting("#doesNotExist", MethodRelationship::AssertionMethod),
+      Err(Error::MethodNotFound)
+    ));
+  }
+
   #[test]
   fn test_detach_verification_relationships() {
     let mut document: CoreDocument = document();
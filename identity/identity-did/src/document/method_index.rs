@@ -0,0 +1,83 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use std::collections::HashMap;
+
+use once_cell::sync::OnceCell;
+
+/// Identifies one of the sets of verification methods of a
+/// [`CoreDocument`](crate::document::CoreDocument).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MethodSet {
+  VerificationMethod,
+  Authentication,
+  AssertionMethod,
+  KeyAgreement,
+  CapabilityDelegation,
+  CapabilityInvocation,
+}
+
+/// The positions of the verification methods of a document, keyed by fragment.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MethodEntries {
+  /// The position of the first method or relationship matching a fragment, in resolution order.
+  pub(crate) methods: HashMap<String, (MethodSet, usize)>,
+  /// The position of the first method in the `verificationMethod` set matching a fragment.
+  pub(crate) verification_method: HashMap<String, usize>,
+}
+
+impl MethodEntries {
+  /// Records the method identified by `fragment` at `index` of `set`, unless a previous method
+  /// with the same fragment was already recorded.
+  pub(crate) fn insert(&mut self, fragment: &str, set: MethodSet, index: usize) {
+    if set == MethodSet::VerificationMethod && !self.verification_method.contains_key(fragment) {
+      self.verification_method.insert(fragment.to_owned(), index);
+    }
+
+    if !self.methods.contains_key(fragment) {
+      self.methods.insert(fragment.to_owned(), (set, index));
+    }
+  }
+}
+
+/// A lazily built index of the verification methods of a document.
+///
+/// The index is a cache: it must be invalidated whenever the methods of the document are
+/// mutated and never affects equality of documents.
+#[derive(Clone, Default)]
+pub(crate) struct MethodIndex(OnceCell<MethodEntries>);
+
+impl MethodIndex {
+  /// Returns the cached entries, building them with `f` if necessary.
+  pub(crate) fn get_or_init<F>(&self, f: F) -> &MethodEntries
+  where
+    F: FnOnce() -> MethodEntries,
+  {
+    self.0.get_or_init(f)
+  }
+
+  /// Returns `true` if the index has been built.
+  #[cfg(test)]
+  pub(crate) fn is_built(&self) -> bool {
+    self.0.get().is_some()
+  }
+
+  /// Drops the cached entries.
+  pub(crate) fn invalidate(&mut self) {
+    self.0.take();
+  }
+}
+
+impl PartialEq for MethodIndex {
+  fn eq(&self, _other: &Self) -> bool {
+    true
+  }
+}
+
+impl Debug for MethodIndex {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.write_str("MethodIndex")
+  }
+}
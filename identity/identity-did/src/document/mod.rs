@@ -7,6 +7,7 @@
 
 mod builder;
 mod core_document;
+mod method_index;
 
 pub use self::builder::DocumentBuilder;
 pub use self::core_document::CoreDocument;
@@ -30,6 +30,8 @@ pub enum Error {
   BuilderInvalidServiceType,
   #[error("Invalid Service Property: `service_endpoint`")]
   BuilderInvalidServiceEndpoint,
+  #[error("Invalid DIDComm Service Endpoint: {0}")]
+  InvalidDIDCommServiceEndpoint(&'static str),
 
   #[error("Invalid Verification Method Property: `id`")]
   BuilderInvalidMethodId,
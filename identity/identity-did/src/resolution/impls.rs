@@ -235,6 +235,7 @@ fn dereference_primary(document: CoreDocument, mut did_url: CoreDIDUrl) -> Resul
         // TODO: support service endpoint sets and map? Dereferencing spec does not address them.
         ServiceEndpoint::Set(_) => Err(Error::InvalidResolutionService),
         ServiceEndpoint::Map(_) => Err(Error::InvalidResolutionService),
+        ServiceEndpoint::Object(_) => Err(Error::InvalidResolutionService),
       })
       .transpose()?
       // 1.3. Return the output service endpoint URL.
@@ -0,0 +1,206 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+use core::convert::TryFrom;
+
+use identity_core::common::Object;
+use identity_core::common::Url;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::service::ServiceEndpoint;
+
+const URI: &str = "uri";
+const ACCEPT: &str = "accept";
+const ROUTING_KEYS: &str = "routingKeys";
+
+/// The `serviceEndpoint` of a DIDComm v2 messaging service.
+///
+/// It is represented as a JSON object with a `uri`, an optional list of `accept` media types
+/// and a list of `routingKeys`. Any other property is allowed and ignored.
+///
+/// [Specification](https://identity.foundation/didcomm-messaging/spec/#did-document-service-endpoint)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DIDCommServiceEndpoint {
+  uri: Url,
+  accept: Option<Vec<String>>,
+  routing_keys: Vec<String>,
+}
+
+impl DIDCommServiceEndpoint {
+  /// The `type` of a DIDComm v2 messaging [`Service`](crate::service::Service).
+  pub const SERVICE_TYPE: &'static str = "DIDCommMessaging";
+
+  /// Creates a new `DIDCommServiceEndpoint`.
+  pub fn new(uri: Url, accept: Option<Vec<String>>, routing_keys: Vec<String>) -> Self {
+    Self {
+      uri,
+      accept,
+      routing_keys,
+    }
+  }
+
+  /// Returns the URI of the endpoint.
+  pub fn uri(&self) -> &Url {
+    &self.uri
+  }
+
+  /// Returns the accepted media types of the endpoint, if specified.
+  pub fn accept(&self) -> Option<&[String]> {
+    self.accept.as_deref()
+  }
+
+  /// Returns the keys of the mediators routing messages to the endpoint.
+  pub fn routing_keys(&self) -> &[String] {
+    &self.routing_keys
+  }
+
+  /// Checks that the given `endpoint` is a valid DIDComm v2 messaging endpoint.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidDIDCommServiceEndpoint`] if `endpoint` is not a JSON object, if
+  /// `uri` is missing or not a URL, if `accept` is not a list of strings, or if `routingKeys` is
+  /// missing or not a list of strings.
+  pub fn check_structure(endpoint: &ServiceEndpoint) -> Result<()> {
+    Self::try_from(endpoint).map(|_| ())
+  }
+}
+
+impl TryFrom<&ServiceEndpoint> for DIDCommServiceEndpoint {
+  type Error = Error;
+
+  fn try_from(endpoint: &ServiceEndpoint) -> Result<Self, Self::Error> {
+    let object: Object = match endpoint {
+      ServiceEndpoint::Object(object) => object.clone(),
+      ServiceEndpoint::Map(_) => Object::from_json_value(endpoint.to_json_value()?)?,
+      ServiceEndpoint::One(_) | ServiceEndpoint::Set(_) => {
+        return Err(Error::InvalidDIDCommServiceEndpoint("expected a map"));
+      }
+    };
+
+    let uri: Url = object
+      .get(URI)
+      .ok_or(Error::InvalidDIDCommServiceEndpoint("missing `uri`"))?
+      .as_str()
+      .and_then(|uri| Url::parse(uri).ok())
+      .ok_or(Error::InvalidDIDCommServiceEndpoint("invalid `uri`"))?;
+
+    let accept: Option<Vec<String>> = object
+      .get(ACCEPT)
+      .map(|accept| strings(accept).ok_or(Error::InvalidDIDCommServiceEndpoint("invalid `accept`")))
+      .transpose()?;
+
+    let routing_keys: Vec<String> = object
+      .get(ROUTING_KEYS)
+      .ok_or(Error::InvalidDIDCommServiceEndpoint("missing `routingKeys`"))
+      .and_then(|keys| strings(keys).ok_or(Error::InvalidDIDCommServiceEndpoint("invalid `routingKeys`")))?;
+
+    Ok(Self::new(uri, accept, routing_keys))
+  }
+}
+
+impl From<DIDCommServiceEndpoint> for ServiceEndpoint {
+  fn from(other: DIDCommServiceEndpoint) -> Self {
+    let mut object: Object = Object::new();
+
+    object.insert(URI.to_owned(), other.uri.into_string().into());
+
+    if let Some(accept) = other.accept {
+      object.insert(ACCEPT.to_owned(), accept.into());
+    }
+
+    object.insert(ROUTING_KEYS.to_owned(), other.routing_keys.into());
+
+    ServiceEndpoint::Object(object)
+  }
+}
+
+/// Returns the strings of a JSON array, or `None` if `value` is not an array of strings.
+fn strings(value: &Value) -> Option<Vec<String>> {
+  value
+    .as_array()?
+    .iter()
+    .map(|value| value.as_str().map(ToOwned::to_owned))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+
+  use super::*;
+
+  fn endpoint(json: &str) -> ServiceEndpoint {
+    ServiceEndpoint::from_json(json).unwrap()
+  }
+
+  fn object(json: &str) -> ServiceEndpoint {
+    ServiceEndpoint::Object(Object::from_json(json).unwrap())
+  }
+
+  #[test]
+  fn test_valid() {
+    let json: &str = r#"{"uri":"https://example.com/path","accept":["didcomm/v2","didcomm/aip2;env=rfc587"],"routingKeys":["did:example:somemediator#somekey"]}"#;
+    let didcomm: DIDCommServiceEndpoint = DIDCommServiceEndpoint::try_from(&endpoint(json)).unwrap();
+
+    assert_eq!(didcomm.uri().as_str(), "https://example.com/path");
+    assert_eq!(didcomm.accept().unwrap(), ["didcomm/v2", "didcomm/aip2;env=rfc587"]);
+    assert_eq!(didcomm.routing_keys(), ["did:example:somemediator#somekey"]);
+    assert_eq!(ServiceEndpoint::from(didcomm), endpoint(json));
+
+    // `accept` is optional and `routingKeys` may be empty.
+    let json: &str = r#"{"routingKeys":[],"uri":"https://example.com/path"}"#;
+    let didcomm: DIDCommServiceEndpoint = DIDCommServiceEndpoint::try_from(&endpoint(json)).unwrap();
+
+    assert!(didcomm.accept().is_none());
+    assert!(didcomm.routing_keys().is_empty());
+    assert_eq!(ServiceEndpoint::from(didcomm).to_json().unwrap(), json);
+
+    // Other properties are allowed.
+    let json: &str = r#"{"uri":"https://example.com/path","routingKeys":[],"other":{"key":1}}"#;
+    assert!(DIDCommServiceEndpoint::check_structure(&endpoint(json)).is_ok());
+  }
+
+  #[test]
+  fn test_invalid() {
+    let invalid: &[(&str, &str)] = &[
+      (
+        r#"{"routingKeys":["did:example:somemediator#somekey"]}"#,
+        "missing `uri`",
+      ),
+      (r#"{"uri":"https:// example.com","routingKeys":[]}"#, "invalid `uri`"),
+      (
+        r#"{"uri":["https://example.com/path"],"routingKeys":[]}"#,
+        "invalid `uri`",
+      ),
+      (
+        r#"{"uri":"https://example.com/path","accept":"didcomm/v2","routingKeys":[]}"#,
+        "invalid `accept`",
+      ),
+      (r#"{"uri":"https://example.com/path"}"#, "missing `routingKeys`"),
+      (
+        r#"{"uri":"https://example.com/path","routingKeys":[1]}"#,
+        "invalid `routingKeys`",
+      ),
+    ];
+
+    for (json, reason) in invalid {
+      assert!(matches!(
+        DIDCommServiceEndpoint::check_structure(&object(json)),
+        Err(Error::InvalidDIDCommServiceEndpoint(error)) if error == *reason
+      ));
+    }
+
+    for json in [r#""https://example.com/path""#, r#"["https://example.com/path"]"#] {
+      assert!(matches!(
+        DIDCommServiceEndpoint::check_structure(&endpoint(json)),
+        Err(Error::InvalidDIDCommServiceEndpoint("expected a map"))
+      ));
+    }
+  }
+}
@@ -4,9 +4,11 @@
 #![allow(clippy::module_inception)]
 
 mod builder;
+mod didcomm_endpoint;
 mod service;
 mod service_endpoint;
 
 pub use self::builder::ServiceBuilder;
+pub use self::didcomm_endpoint::DIDCommServiceEndpoint;
 pub use self::service::Service;
 pub use self::service_endpoint::ServiceEndpoint;
@@ -7,6 +7,7 @@ use core::fmt::Formatter;
 use serde::Serialize;
 
 use identity_core::common::Object;
+use identity_core::common::Url;
 use identity_core::convert::FmtJson;
 
 use crate::did::CoreDIDUrl;
@@ -77,6 +78,27 @@ impl<T> Service<T> {
     &mut self.service_endpoint
   }
 
+  /// Returns the URL of a single-URL `Service` endpoint, or `None` if the endpoint is a set,
+  /// map or object.
+  pub fn endpoint_url(&self) -> Option<&Url> {
+    match &self.service_endpoint {
+      ServiceEndpoint::One(url) => Some(url),
+      ServiceEndpoint::Set(_) | ServiceEndpoint::Map(_) | ServiceEndpoint::Object(_) => None,
+    }
+  }
+
+  /// Returns all URLs of the `Service` endpoint, flattening sets and maps in order.
+  ///
+  /// An object endpoint has no URLs of its own and returns an empty list.
+  pub fn endpoint_urls(&self) -> Vec<&Url> {
+    match &self.service_endpoint {
+      ServiceEndpoint::One(url) => vec![url],
+      ServiceEndpoint::Set(set) => set.iter().collect(),
+      ServiceEndpoint::Map(map) => map.values().flat_map(|set| set.iter()).collect(),
+      ServiceEndpoint::Object(_) => Vec::new(),
+    }
+  }
+
   /// Returns a reference to the custom `Service` properties.
   pub fn properties(&self) -> &T {
     &self.properties
@@ -105,6 +127,8 @@ where
 
 #[cfg(test)]
 mod tests {
+  use indexmap::map::IndexMap;
+
   use crate::did::CoreDIDUrl;
   use crate::service::Service;
   use identity_core::common::Object;
@@ -151,4 +175,43 @@ mod tests {
       assert_eq!(Service::from_json(expected).unwrap(), service)
     }
   }
+
+  fn service(endpoint: ServiceEndpoint) -> Service {
+    Service::builder(Object::new())
+      .id(CoreDIDUrl::parse("did:example:123#service").unwrap())
+      .type_("DIDCommMessaging".to_owned())
+      .service_endpoint(endpoint)
+      .build()
+      .unwrap()
+  }
+
+  #[test]
+  fn test_service_endpoint_urls() {
+    let url1 = Url::parse("https://iota.org/").unwrap();
+    let url2 = Url::parse("wss://www.example.com/socketserver/").unwrap();
+    let url3 = Url::parse("did:abc:123#service").unwrap();
+
+    // Single URL.
+    let one: Service = service(url1.clone().into());
+    assert_eq!(one.endpoint_url(), Some(&url1));
+    assert_eq!(one.endpoint_urls(), vec![&url1]);
+
+    // Set of URLs.
+    let set: Service = service(ServiceEndpoint::Set(
+      OrderedSet::try_from(vec![url1.clone(), url2.clone()]).unwrap(),
+    ));
+    assert_eq!(set.endpoint_url(), None);
+    assert_eq!(set.endpoint_urls(), vec![&url1, &url2]);
+
+    // Map of URL sets.
+    let mut map: IndexMap<String, OrderedSet<Url>> = IndexMap::new();
+    map.insert(
+      "origins".to_owned(),
+      OrderedSet::try_from(vec![url1.clone(), url2.clone()]).unwrap(),
+    );
+    map.insert("did".to_owned(), OrderedSet::try_from(vec![url3.clone()]).unwrap());
+    let map: Service = service(ServiceEndpoint::Map(map));
+    assert_eq!(map.endpoint_url(), None);
+    assert_eq!(map.endpoint_urls(), vec![&url1, &url2, &url3]);
+  }
 }
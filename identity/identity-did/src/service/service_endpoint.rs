@@ -5,22 +5,31 @@ use core::fmt::Display;
 use core::fmt::Formatter;
 
 use indexmap::map::IndexMap;
+use serde::de::Deserializer;
+use serde::de::Error as _;
+use serde::Deserialize;
 use serde::Serialize;
 
+use identity_core::common::Object;
 use identity_core::common::Url;
 use identity_core::convert::FmtJson;
 
+use crate::service::DIDCommServiceEndpoint;
 use crate::utils::OrderedSet;
 
 /// A single URL, set, or map of endpoints specified in a [`Service`].
 ///
+/// The endpoint of a [DIDComm messaging service](DIDCommServiceEndpoint) is kept as an
+/// [`Object`]; any other JSON object must be a valid map of URL sets.
+///
 /// [Specification](https://www.w3.org/TR/did-core/#dfn-serviceendpoint)
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 #[serde(untagged)]
 pub enum ServiceEndpoint {
   One(Url),
   Set(OrderedSet<Url>),
   Map(IndexMap<String, OrderedSet<Url>>),
+  Object(Object),
   // TODO: enforce set/map is non-empty?
 }
 
@@ -42,6 +51,36 @@ impl From<IndexMap<String, OrderedSet<Url>>> for ServiceEndpoint {
   }
 }
 
+impl<'de> Deserialize<'de> for ServiceEndpoint {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum __ServiceEndpoint {
+      One(Url),
+      Set(OrderedSet<Url>),
+      Map(IndexMap<String, OrderedSet<Url>>),
+      Object(Object),
+    }
+
+    match __ServiceEndpoint::deserialize(deserializer)? {
+      __ServiceEndpoint::One(url) => Ok(Self::One(url)),
+      __ServiceEndpoint::Set(set) => Ok(Self::Set(set)),
+      __ServiceEndpoint::Map(map) => Ok(Self::Map(map)),
+      // Only a DIDComm endpoint may be an object other than a map of URL sets.
+      __ServiceEndpoint::Object(object) => {
+        let endpoint: Self = Self::Object(object);
+
+        DIDCommServiceEndpoint::check_structure(&endpoint)
+          .map(|_| endpoint)
+          .map_err(|_| D::Error::custom("data did not match any variant of untagged enum ServiceEndpoint"))
+      }
+    }
+  }
+}
+
 impl Display for ServiceEndpoint {
   fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
     self.fmt_json(f)
@@ -195,6 +234,26 @@ mod tests {
     assert_eq!(endpoint_map, ServiceEndpoint::from_json(&ser_endpoint_map).unwrap());
   }
 
+  #[test]
+  fn test_service_endpoint_object() {
+    // VALID: a map of URL sets is still a Map.
+    let json: &str = r#"{"key":["https://iota.org/"]}"#;
+    assert!(matches!(
+      ServiceEndpoint::from_json(json).unwrap(),
+      ServiceEndpoint::Map(_)
+    ));
+
+    // VALID: a DIDComm endpoint is an Object.
+    let json: &str = r#"{"accept":["didcomm/v2"],"routingKeys":[],"uri":"https://example.com/path"}"#;
+    let endpoint: ServiceEndpoint = ServiceEndpoint::from_json(json).unwrap();
+    assert!(matches!(endpoint, ServiceEndpoint::Object(_)));
+    assert_eq!(endpoint.to_json().unwrap(), json);
+
+    // INVALID: any other object.
+    assert!(ServiceEndpoint::from_json(r#"{"key":"https://iota.org/"}"#).is_err());
+    assert!(ServiceEndpoint::from_json(r#"{"accept":["didcomm/v2"],"routingKeys":[]}"#).is_err());
+  }
+
   #[test]
   fn test_service_endpoint_serde_fails() {
     // INVALID: empty
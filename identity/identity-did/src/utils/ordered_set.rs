@@ -20,6 +20,9 @@ use crate::verification::MethodQuery;
 /// An ordered set backed by a `Vec<T>`.
 ///
 /// Note: Ordering is based on insert order and **not** [`Ord`].
+///
+/// Deserialization fails with [`Error::OrderedSetDuplicate`] if the input contains multiple
+/// items with the same key, e.g. verification methods sharing an id.
 #[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
 #[repr(transparent)]
 #[serde(bound(deserialize = "T: KeyComparable + Deserialize<'de>"), try_from = "Vec<T>")]
@@ -285,6 +288,8 @@ where
 mod tests {
   use super::*;
 
+  use identity_core::convert::FromJson;
+
   use crate::did::CoreDIDUrl;
   use crate::verification::MethodRef;
 
@@ -350,6 +355,16 @@ mod tests {
     let _: OrderedSet<u8> = OrderedSet::try_from(source).unwrap();
   }
 
+  #[test]
+  fn test_deserialize_duplicate() {
+    assert_eq!(&*OrderedSet::<u8>::from_json("[1, 2, 3]").unwrap(), &[1, 2, 3]);
+    assert!(OrderedSet::<u8>::from_json("[1, 2, 2]").is_err());
+
+    let did: &str = "did:example:123#sign-0";
+    let json: String = format!(r#"["{}", "{}"]"#, did, did);
+    assert!(OrderedSet::<MethodRef>::from_json(&json).is_err());
+  }
+
   #[test]
   fn test_collect() {
     let source: Vec<u8> = vec![1, 2, 3, 3, 2, 4, 5, 1, 1];
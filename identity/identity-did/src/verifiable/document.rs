@@ -22,6 +22,7 @@ use identity_core::crypto::PublicKey;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Sign;
 use identity_core::crypto::Signature;
+use identity_core::crypto::SignatureOptions;
 use identity_core::crypto::Signer;
 use identity_core::crypto::TrySignature;
 use identity_core::crypto::TrySignatureMut;
@@ -30,6 +31,7 @@ use identity_core::crypto::Verify;
 use identity_core::error::Error as CoreError;
 use serde::Serialize;
 
+use crate::did::CoreDIDUrl;
 use crate::document::CoreDocument;
 use crate::error::Error;
 use crate::error::Result;
@@ -68,6 +70,18 @@ impl<T, U, V> CoreDocument<Properties<T>, U, V> {
   pub fn set_proof(&mut self, signature: Signature) {
     self.properties_mut().set_proof(signature);
   }
+
+  pub fn proofs(&self) -> &[Signature] {
+    self.properties().proofs()
+  }
+
+  pub fn push_proof(&mut self, signature: Signature) {
+    self.properties_mut().push_proof(signature);
+  }
+
+  pub fn clear_proofs(&mut self) {
+    self.properties_mut().clear_proofs();
+  }
 }
 
 impl<T, U, V> TrySignature for CoreDocument<Properties<T>, U, V> {
@@ -119,6 +133,7 @@ pub struct DocumentSigner<'base, 'query, 'proof, T, U, V> {
   private: &'base PrivateKey,
   method: Option<MethodQuery<'query>>,
   merkle_key: Option<(&'proof PublicKey, &'proof dyn Any)>,
+  options: SignatureOptions,
 }
 
 impl<'base, T, U, V> DocumentSigner<'base, '_, '_, T, U, V> {
@@ -128,8 +143,15 @@ impl<'base, T, U, V> DocumentSigner<'base, '_, '_, T, U, V> {
       private,
       method: None,
       merkle_key: None,
+      options: SignatureOptions::default(),
     }
   }
+
+  /// Sets the proof options, e.g. a verifier challenge, of the created signatures.
+  pub fn options(mut self, options: SignatureOptions) -> Self {
+    self.options = options;
+    self
+  }
 }
 
 impl<'base, 'query, T, U, V> DocumentSigner<'base, 'query, '_, T, U, V> {
@@ -167,51 +189,89 @@ impl<T, U, V> DocumentSigner<'_, '_, '_, T, U, V> {
     let method: &VerificationMethod<U> = self.document.try_resolve_method(query)?;
     let method_uri: String = X::try_method(method)?;
 
-    match method.key_type() {
-      MethodType::Ed25519VerificationKey2018 => {
-        JcsEd25519::<Ed25519>::create_signature(that, method_uri, self.private.as_ref())?;
-      }
-      MethodType::MerkleKeyCollection2021 => {
-        let data: Vec<u8> = method.key_data().try_decode()?;
-
-        match MerkleKey::extract_tags(&data)? {
-          (MerkleSignatureTag::ED25519, MerkleDigestTag::SHA256) => {
-            self.merkle_key_sign::<X, Sha256, Ed25519>(that, method_uri)?;
-          }
-          (MerkleSignatureTag::ED25519, MerkleDigestTag::BLAKE2B_256) => {
-            self.merkle_key_sign::<X, Blake2b256, Ed25519>(that, method_uri)?;
-          }
-          (_, _) => {
-            return Err(Error::InvalidMethodType);
-          }
+    sign_for_method_type(
+      method,
+      that,
+      method_uri,
+      self.private,
+      self.merkle_key,
+      self.options.clone(),
+    )
+  }
+}
+
+/// Signs the provided data with `private`, using the signature suite associated with the
+/// [`MethodType`] of `method`. The created signature references the method as `method_uri`.
+///
+/// This is the signing counterpart of [`verify_for_method_type`]; support for new verification
+/// method types is registered in both. Signing with a Merkle Key Collection requires the public
+/// key and Merkle proof of the signing key as `merkle_key`.
+///
+/// # Errors
+///
+/// Fails if an unsupported verification method is used, the Merkle key is missing, data
+/// serialization fails, or the signature operation fails.
+pub fn sign_for_method_type<X, U>(
+  method: &VerificationMethod<U>,
+  that: &mut X,
+  method_uri: String,
+  private: &PrivateKey,
+  merkle_key: Option<(&PublicKey, &dyn Any)>,
+  options: SignatureOptions,
+) -> Result<()>
+where
+  X: Serialize + SetSignature,
+{
+  match method.key_type() {
+    MethodType::Ed25519VerificationKey2018 => {
+      JcsEd25519::<Ed25519>::create_signature_with_options(that, method_uri, options, private.as_ref())?;
+    }
+    MethodType::MerkleKeyCollection2021 => {
+      let data: Vec<u8> = method.key_data().try_decode()?;
+
+      match MerkleKey::extract_tags(&data)? {
+        (MerkleSignatureTag::ED25519, MerkleDigestTag::SHA256) => {
+          merkle_key_sign::<X, Sha256, Ed25519>(that, method_uri, private, merkle_key, options)?;
+        }
+        (MerkleSignatureTag::ED25519, MerkleDigestTag::BLAKE2B_256) => {
+          merkle_key_sign::<X, Blake2b256, Ed25519>(that, method_uri, private, merkle_key, options)?;
+        }
+        (_, _) => {
+          return Err(Error::InvalidMethodType);
         }
       }
     }
-
-    Ok(())
   }
 
-  fn merkle_key_sign<X, D, S>(&self, that: &mut X, method: String) -> Result<()>
-  where
-    X: Serialize + SetSignature,
-    D: MerkleDigest,
-    S: MerkleSignature + Sign<Private = [u8]>,
-    S::Output: AsRef<[u8]>,
-  {
-    match self.merkle_key {
-      Some((public, proof)) => {
-        let proof: &Proof<D> = proof
-          .downcast_ref()
-          .ok_or(Error::CoreError(CoreError::InvalidKeyFormat))?;
+  Ok(())
+}
 
-        let skey: SigningKey<'_, D> = SigningKey::from_borrowed(public, self.private, proof);
+fn merkle_key_sign<X, D, S>(
+  that: &mut X,
+  method: String,
+  private: &PrivateKey,
+  merkle_key: Option<(&PublicKey, &dyn Any)>,
+  options: SignatureOptions,
+) -> Result<()>
+where
+  X: Serialize + SetSignature,
+  D: MerkleDigest,
+  S: MerkleSignature + Sign<Private = [u8]>,
+  S::Output: AsRef<[u8]>,
+{
+  match merkle_key {
+    Some((public, proof)) => {
+      let proof: &Proof<D> = proof
+        .downcast_ref()
+        .ok_or(Error::CoreError(CoreError::InvalidKeyFormat))?;
 
-        MerkleSigner::<D, S>::create_signature(that, method, &skey)?;
+      let skey: SigningKey<'_, D> = SigningKey::from_borrowed(public, private, proof);
 
-        Ok(())
-      }
-      None => Err(Error::CoreError(CoreError::InvalidKeyFormat)),
+      MerkleSigner::<D, S>::create_signature_with_options(that, method, options, &skey)?;
+
+      Ok(())
     }
+    None => Err(Error::CoreError(CoreError::InvalidKeyFormat)),
   }
 }
 
@@ -244,7 +304,9 @@ where
     X: Serialize + TrySignature,
   {
     let signature: &Signature = that.try_signature()?;
-    let method: &VerificationMethod<U> = self.document.try_resolve_method(signature)?;
+    let method_url: CoreDIDUrl = self.document.method_url(signature.verification_method())?;
+    let method: &VerificationMethod<U> = self.document.try_resolve_method(&method_url)?;
+    Self::check_method_url(method, &method_url)?;
 
     Self::do_verify(method, that)
   }
@@ -261,42 +323,106 @@ where
     X: Serialize + TrySignature,
   {
     let signature: &Signature = that.try_signature()?;
-    let method: &VerificationMethod<U> = self.document.try_resolve_method_with_scope(signature, scope)?;
+    let method_url: CoreDIDUrl = self.document.method_url(signature.verification_method())?;
+    let method: &VerificationMethod<U> = self.document.try_resolve_method_with_scope(&method_url, scope)?;
+    Self::check_method_url(method, &method_url)?;
 
     Self::do_verify(method, that)
   }
 
-  /// Verifies the signature of the provided data.
+  /// Verifies a detached `signature` over the raw `data` bytes using the verification method
+  /// matching `query`.
+  ///
+  /// Unlike [`DocumentVerifier::verify`], the signature is not embedded in the data.
   ///
   /// # Errors
   ///
-  /// Fails if an unsupported verification method is used, document
-  /// serialization fails, or the verification operation fails.
-  pub fn do_verify<X>(method: &VerificationMethod<U>, that: &X) -> Result<()>
+  /// Fails if the method is not found, an unsupported verification method is used,
+  /// or the verification operation fails.
+  pub fn verify_detached<'query, Q>(&self, data: &[u8], signature: &[u8], query: Q) -> Result<()>
   where
-    X: Serialize + TrySignature,
+    Q: Into<MethodQuery<'query>>,
   {
-    let data: Vec<u8> = method.key_data().try_decode()?;
+    let method: &VerificationMethod<U> = self.document.try_resolve_method(query)?;
+    let public: Vec<u8> = method.key_data().try_decode()?;
 
     match method.key_type() {
       MethodType::Ed25519VerificationKey2018 => {
-        JcsEd25519::<Ed25519>::verify_signature(that, &data)?;
+        Ed25519::<[u8]>::verify(data, signature, &public)?;
+      }
+      MethodType::MerkleKeyCollection2021 => {
+        // Merkle Key Collection signatures carry a proof and cannot be detached.
+        return Err(Error::InvalidMethodType);
       }
-      MethodType::MerkleKeyCollection2021 => match MerkleKey::extract_tags(&data)? {
-        (MerkleSignatureTag::ED25519, MerkleDigestTag::SHA256) => {
-          merkle_key_verify::<X, Sha256, Ed25519, U>(that, method, &data)?;
-        }
-        (MerkleSignatureTag::ED25519, MerkleDigestTag::BLAKE2B_256) => {
-          merkle_key_verify::<X, Blake2b256, Ed25519, U>(that, method, &data)?;
-        }
-        (_, _) => {
-          return Err(Error::InvalidMethodType);
-        }
-      },
     }
 
     Ok(())
   }
+
+  /// Checks that the resolved `method` belongs to the DID of the absolute `method_url`.
+  ///
+  /// Methods are resolved by fragment, so an absolute reference to the method of another DID
+  /// would otherwise resolve to a method of this document with the same fragment.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::MethodNotFound`] if the DIDs differ.
+  pub fn check_method_url(method: &VerificationMethod<U>, method_url: &CoreDIDUrl) -> Result<()> {
+    if method.id().did() == method_url.did() {
+      Ok(())
+    } else {
+      Err(Error::MethodNotFound)
+    }
+  }
+
+  /// Verifies the signature of the provided data.
+  ///
+  /// # Errors
+  ///
+  /// Fails if an unsupported verification method is used, document
+  /// serialization fails, or the verification operation fails.
+  pub fn do_verify<X>(method: &VerificationMethod<U>, that: &X) -> Result<()>
+  where
+    X: Serialize + TrySignature,
+  {
+    verify_for_method_type(method, that)
+  }
+}
+
+/// Verifies the signature of the provided data with the public key material of `method`,
+/// using the signature suite associated with its [`MethodType`].
+///
+/// Support for new verification method types is registered here.
+///
+/// # Errors
+///
+/// Fails if an unsupported verification method is used, document
+/// serialization fails, or the verification operation fails.
+pub fn verify_for_method_type<X, U>(method: &VerificationMethod<U>, that: &X) -> Result<()>
+where
+  X: Serialize + TrySignature,
+  U: Revocation,
+{
+  let data: Vec<u8> = method.key_data().try_decode()?;
+
+  match method.key_type() {
+    MethodType::Ed25519VerificationKey2018 => {
+      JcsEd25519::<Ed25519>::verify_signature(that, &data)?;
+    }
+    MethodType::MerkleKeyCollection2021 => match MerkleKey::extract_tags(&data)? {
+      (MerkleSignatureTag::ED25519, MerkleDigestTag::SHA256) => {
+        merkle_key_verify::<X, Sha256, Ed25519, U>(that, method, &data)?;
+      }
+      (MerkleSignatureTag::ED25519, MerkleDigestTag::BLAKE2B_256) => {
+        merkle_key_verify::<X, Blake2b256, Ed25519, U>(that, method, &data)?;
+      }
+      (_, _) => {
+        return Err(Error::InvalidMethodType);
+      }
+    },
+  }
+
+  Ok(())
 }
 
 fn merkle_key_verify<X, D, S, U>(that: &X, method: &VerificationMethod<U>, data: &[u8]) -> Result<()>
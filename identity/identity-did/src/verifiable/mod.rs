@@ -5,6 +5,8 @@ mod document;
 mod properties;
 mod traits;
 
+pub use self::document::sign_for_method_type;
+pub use self::document::verify_for_method_type;
 pub use self::document::DocumentSigner;
 pub use self::document::DocumentVerifier;
 pub use self::properties::Properties;
@@ -4,6 +4,7 @@
 use core::ops::Deref;
 use core::ops::DerefMut;
 use identity_core::common::Object;
+use identity_core::common::OneOrMany;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signature;
 use identity_core::crypto::TrySignature;
@@ -12,15 +13,16 @@ use identity_core::crypto::TrySignatureMut;
 use crate::verification::MethodUriType;
 use crate::verification::TryMethod;
 
-/// A generic container for a set of properties (`T`) and a
-/// [`digital signature`][Signature].
+/// A generic container for a set of properties (`T`) and one or more
+/// [`digital signatures`][Signature].
+///
+/// Each signature of a proof set is created over the properties without the other signatures.
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
 pub struct Properties<T = Object> {
   #[serde(flatten)]
   pub(crate) properties: T,
-  // TODO: Support multiple signatures (?)
-  #[serde(skip_serializing_if = "Option::is_none")]
-  pub(crate) proof: Option<Signature>,
+  #[serde(default, skip_serializing_if = "OneOrMany::is_empty")]
+  pub(crate) proof: OneOrMany<Signature>,
 }
 
 impl<T> Properties<T> {
@@ -28,7 +30,7 @@ impl<T> Properties<T> {
   pub const fn new(properties: T) -> Self {
     Self {
       properties,
-      proof: None,
+      proof: OneOrMany::Many(Vec::new()),
     }
   }
 
@@ -36,23 +38,38 @@ impl<T> Properties<T> {
   pub const fn with_proof(properties: T, proof: Signature) -> Self {
     Self {
       properties,
-      proof: Some(proof),
+      proof: OneOrMany::One(proof),
     }
   }
 
-  /// Returns a reference to the [`proof`][`Signature`].
+  /// Returns a reference to the [`proof`][`Signature`], or the first proof of a proof set.
   pub fn proof(&self) -> Option<&Signature> {
-    self.proof.as_ref()
+    self.proof.get(0)
   }
 
-  /// Returns a mutable reference to the [`proof`][`Signature`].
+  /// Returns a mutable reference to the [`proof`][`Signature`], or the first proof of a proof set.
   pub fn proof_mut(&mut self) -> Option<&mut Signature> {
-    self.proof.as_mut()
+    self.proof.get_mut(0)
   }
 
-  /// Sets the value of the [`proof`][`Signature`].
+  /// Sets the value of the [`proof`][`Signature`], replacing all existing proofs.
   pub fn set_proof(&mut self, signature: Signature) {
-    self.proof = Some(signature);
+    self.proof = OneOrMany::One(signature);
+  }
+
+  /// Returns all [`proofs`][`Signature`].
+  pub fn proofs(&self) -> &[Signature] {
+    self.proof.as_slice()
+  }
+
+  /// Adds a [`proof`][`Signature`] to the proof set.
+  pub fn push_proof(&mut self, signature: Signature) {
+    self.proof.push(signature);
+  }
+
+  /// Removes all [`proofs`][`Signature`].
+  pub fn clear_proofs(&mut self) {
+    self.proof = OneOrMany::Many(Vec::new());
   }
 }
 
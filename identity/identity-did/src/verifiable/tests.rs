@@ -1,25 +1,36 @@
 // Copyright 2020-2021 Runnerc
 // SPDX-License-Identifier: Apache-2.0
 
+use core::any::Any;
+
+use identity_core::crypto::merkle_key::Blake2b256;
+use identity_core::crypto::merkle_key::MerkleDigest;
+use identity_core::crypto::merkle_key::MerkleDigestTag;
 use identity_core::crypto::merkle_key::MerkleKey;
 use identity_core::crypto::merkle_key::Sha256;
 use identity_core::crypto::merkle_tree::Hash;
 use identity_core::crypto::merkle_tree::Proof;
 use identity_core::crypto::Ed25519;
+use identity_core::crypto::JcsEd25519;
 use identity_core::crypto::KeyCollection;
 use identity_core::crypto::KeyPair;
 use identity_core::crypto::PrivateKey;
 use identity_core::crypto::PublicKey;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signature;
+use identity_core::crypto::Signer;
 use identity_core::crypto::TrySignature;
 use identity_core::crypto::TrySignatureMut;
 
 use crate::did::CoreDID;
 use crate::did::DID;
 use crate::document::CoreDocument;
+use crate::error::Error;
+use crate::verifiable::sign_for_method_type;
+use crate::verifiable::verify_for_method_type;
 use crate::verifiable::Properties;
 use crate::verification::MethodData;
+use crate::verification::MethodScope;
 use crate::verification::MethodType;
 use crate::verification::MethodUriType;
 use crate::verification::TryMethod;
@@ -141,3 +152,240 @@ fn test_sign_verify_that_merkle_key_ed25519_sha256() {
     assert!(document.verifier().verify(&that).is_ok());
   }
 }
+
+#[test]
+fn test_sign_merkle_key_unknown_digest_tag() {
+  let total: usize = 1 << 2;
+  let index: usize = 1;
+
+  let keys: KeyCollection = KeyCollection::new_ed25519(total).unwrap();
+  let controller: CoreDID = "did:example:1234".parse().unwrap();
+
+  let root: Hash<Sha256> = keys.merkle_root();
+  let proof: Proof<Sha256> = keys.merkle_proof(index).unwrap();
+
+  // A valid signature tag followed by an unknown digest tag.
+  let mut mkey: Vec<u8> = MerkleKey::encode_key::<Sha256, Ed25519>(&root);
+  mkey[1] = 0xFF;
+
+  let method: VerificationMethod = VerificationMethod::builder(Default::default())
+    .id(controller.to_url().join("#key-collection").unwrap())
+    .controller(controller.clone())
+    .key_type(MethodType::MerkleKeyCollection2021)
+    .key_data(MethodData::new_multibase(mkey))
+    .build()
+    .unwrap();
+
+  let document: CoreDocument<Properties> = CoreDocument::builder(Default::default())
+    .id(controller)
+    .verification_method(method)
+    .build()
+    .unwrap();
+
+  let public: &PublicKey = keys.public(index).unwrap();
+  let private: &PrivateKey = keys.private(index).unwrap();
+
+  let mut that: That = That::new(123);
+
+  let result: Result<(), Error> = document
+    .signer(private)
+    .method("#key-collection")
+    .merkle_key((public, &proof))
+    .sign(&mut that);
+
+  assert!(matches!(
+    result,
+    Err(Error::CoreError(identity_core::Error::InvalidMerkleDigestKeyTag(Some(tag)))) if tag == MerkleDigestTag::new(0xFF)
+  ));
+}
+
+fn merkle_key_document<D>(keys: &KeyCollection, index: usize) -> (CoreDocument<Properties>, That)
+where
+  D: MerkleDigest,
+{
+  let controller: CoreDID = "did:example:1234".parse().unwrap();
+  let root: Hash<D> = keys.merkle_root();
+  let proof: Proof<D> = keys.merkle_proof(index).unwrap();
+
+  let method: VerificationMethod = VerificationMethod::builder(Default::default())
+    .id(controller.to_url().join("#key-collection").unwrap())
+    .key_type(MethodType::MerkleKeyCollection2021)
+    .key_data(MethodData::new_multibase(MerkleKey::encode_key::<D, Ed25519>(&root)))
+    .build()
+    .unwrap();
+
+  let document: CoreDocument<Properties> = CoreDocument::builder(Default::default())
+    .id(controller)
+    .verification_method(method)
+    .build()
+    .unwrap();
+
+  let mut that: That = That::new(123);
+
+  document
+    .signer(keys.private(index).unwrap())
+    .method("#key-collection")
+    .merkle_key((keys.public(index).unwrap(), &proof))
+    .sign(&mut that)
+    .unwrap();
+
+  (document, that)
+}
+
+#[test]
+fn test_verify_for_method_type() {
+  // Ed25519VerificationKey2018
+  let key: KeyPair = KeyPair::new_ed25519().unwrap();
+  let controller: CoreDID = "did:example:1234".parse().unwrap();
+
+  let method: VerificationMethod = VerificationMethod::builder(Default::default())
+    .id(controller.to_url().join("#key-1").unwrap())
+    .key_type(MethodType::Ed25519VerificationKey2018)
+    .key_data(MethodData::new_multibase(key.public()))
+    .build()
+    .unwrap();
+
+  let document: CoreDocument<Properties> = CoreDocument::builder(Default::default())
+    .id(controller)
+    .verification_method(method)
+    .build()
+    .unwrap();
+
+  let mut that: That = That::new(123);
+  document.signer(key.private()).method("#key-1").sign(&mut that).unwrap();
+
+  let ed25519: &VerificationMethod = document.try_resolve_method("#key-1").unwrap();
+  assert!(verify_for_method_type(ed25519, &that).is_ok());
+  assert!(verify_for_method_type(ed25519, &That::new(123)).is_err());
+
+  // MerkleKeyCollection2021 with each supported digest.
+  let keys: KeyCollection = KeyCollection::new_ed25519(1 << 2).unwrap();
+
+  let (document, that_sha256): (CoreDocument<Properties>, That) = merkle_key_document::<Sha256>(&keys, 1);
+  let merkle_sha256: &VerificationMethod = document.try_resolve_method("#key-collection").unwrap();
+  assert!(verify_for_method_type(merkle_sha256, &that_sha256).is_ok());
+  // The signature suite is selected by the method, not the proof.
+  assert!(verify_for_method_type(ed25519, &that_sha256).is_err());
+
+  let (document, that_blake2b): (CoreDocument<Properties>, That) = merkle_key_document::<Blake2b256>(&keys, 1);
+  let merkle_blake2b: &VerificationMethod = document.try_resolve_method("#key-collection").unwrap();
+  assert!(verify_for_method_type(merkle_blake2b, &that_blake2b).is_ok());
+  assert!(verify_for_method_type(merkle_blake2b, &that_sha256).is_err());
+}
+
+#[test]
+fn test_sign_for_method_type() {
+  // Ed25519VerificationKey2018
+  let key: KeyPair = KeyPair::new_ed25519().unwrap();
+  let controller: CoreDID = "did:example:1234".parse().unwrap();
+
+  let method: VerificationMethod = VerificationMethod::builder(Default::default())
+    .id(controller.to_url().join("#key-1").unwrap())
+    .key_type(MethodType::Ed25519VerificationKey2018)
+    .key_data(MethodData::new_multibase(key.public()))
+    .build()
+    .unwrap();
+
+  let mut that: That = That::new(123);
+  sign_for_method_type(
+    &method,
+    &mut that,
+    "#key-1".into(),
+    key.private(),
+    None,
+    Default::default(),
+  )
+  .unwrap();
+  assert!(verify_for_method_type(&method, &that).is_ok());
+
+  // MerkleKeyCollection2021 with each supported digest.
+  let keys: KeyCollection = KeyCollection::new_ed25519(1 << 2).unwrap();
+  let public: &PublicKey = keys.public(1).unwrap();
+  let private: &PrivateKey = keys.private(1).unwrap();
+
+  let (document, _): (CoreDocument<Properties>, That) = merkle_key_document::<Sha256>(&keys, 1);
+  let merkle_sha256: &VerificationMethod = document.try_resolve_method("#key-collection").unwrap();
+  let proof: Proof<Sha256> = keys.merkle_proof(1).unwrap();
+
+  let mut that: That = That::new(123);
+  let merkle_key: Option<(&PublicKey, &dyn Any)> = Some((public, &proof));
+  sign_for_method_type(
+    merkle_sha256,
+    &mut that,
+    "#key-collection".into(),
+    private,
+    merkle_key,
+    Default::default(),
+  )
+  .unwrap();
+  assert!(verify_for_method_type(merkle_sha256, &that).is_ok());
+
+  let (document, _): (CoreDocument<Properties>, That) = merkle_key_document::<Blake2b256>(&keys, 1);
+  let merkle_blake2b: &VerificationMethod = document.try_resolve_method("#key-collection").unwrap();
+  let proof: Proof<Blake2b256> = keys.merkle_proof(1).unwrap();
+
+  let mut that: That = That::new(123);
+  let merkle_key: Option<(&PublicKey, &dyn Any)> = Some((public, &proof));
+  sign_for_method_type(
+    merkle_blake2b,
+    &mut that,
+    "#key-collection".into(),
+    private,
+    merkle_key,
+    Default::default(),
+  )
+  .unwrap();
+  assert!(verify_for_method_type(merkle_blake2b, &that).is_ok());
+
+  // A Merkle Key Collection cannot sign without the Merkle key of the signing key.
+  let result: Result<(), Error> = sign_for_method_type(
+    merkle_blake2b,
+    &mut That::new(123),
+    "#key-collection".into(),
+    private,
+    None,
+    Default::default(),
+  );
+  assert!(matches!(
+    result,
+    Err(Error::CoreError(identity_core::Error::InvalidKeyFormat))
+  ));
+}
+
+#[test]
+fn test_verify_relative_and_absolute_method() {
+  let key: KeyPair = KeyPair::new_ed25519().unwrap();
+  let controller: CoreDID = "did:example:1234".parse().unwrap();
+
+  let method: VerificationMethod = VerificationMethod::builder(Default::default())
+    .id(controller.to_url().join("#key-1").unwrap())
+    .controller(controller.clone())
+    .key_type(MethodType::Ed25519VerificationKey2018)
+    .key_data(MethodData::new_multibase(key.public()))
+    .build()
+    .unwrap();
+
+  let document: CoreDocument<Properties> = CoreDocument::builder(Default::default())
+    .id(controller)
+    .verification_method(method)
+    .build()
+    .unwrap();
+
+  // A fragment and the equivalent absolute DID Url refer to the same method.
+  for reference in ["#key-1", "key-1", "did:example:1234#key-1"] {
+    let mut that: That = That::new(123);
+    JcsEd25519::<Ed25519>::create_signature(&mut that, reference, key.private().as_ref()).unwrap();
+
+    assert!(document.verifier().verify(&that).is_ok());
+    assert!(document
+      .verifier()
+      .verify_with_scope(&that, MethodScope::VerificationMethod)
+      .is_ok());
+  }
+
+  // An absolute DID Url of another DID does not resolve to a method of the document.
+  let mut that: That = That::new(123);
+  JcsEd25519::<Ed25519>::create_signature(&mut that, "did:example:5678#key-1", key.private().as_ref()).unwrap();
+
+  assert!(matches!(document.verifier().verify(&that), Err(Error::MethodNotFound)));
+}
@@ -40,6 +40,8 @@ impl<T> MethodBuilder<T> {
   }
 
   /// Sets the `controller` value of the generated `VerificationMethod`.
+  ///
+  /// Defaults to the DID of the method `id` if not set.
   #[must_use]
   pub fn controller(mut self, value: CoreDID) -> Self {
     self.controller = Some(value);
@@ -117,13 +119,16 @@ mod tests {
   }
 
   #[test]
-  #[should_panic = "InvalidMethodController"]
-  fn test_missing_controller() {
-    let _: VerificationMethod = MethodBuilder::default()
-      .id("did:example:123".parse().unwrap())
+  fn test_default_controller() {
+    let method: VerificationMethod = MethodBuilder::default()
+      .id("did:example:123#key-1".parse().unwrap())
       .key_type(MethodType::Ed25519VerificationKey2018)
       .key_data(MethodData::PublicKeyMultibase("".into()))
       .build()
       .unwrap();
+
+    let did: CoreDID = "did:example:123".parse().unwrap();
+    assert_eq!(method.controller(), &did);
+    assert_eq!(method.controller(), method.id().did());
   }
 }
@@ -23,6 +23,17 @@ pub enum MethodData {
   PublicKeyJwk(Object),
 }
 
+/// The encodings of [`MethodData`] holding encoded bytes.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum MethodDataEncoding {
+  /// Base58 content, as in `publicKeyBase58`.
+  Base58,
+  /// [Multibase] content, as in `publicKeyMultibase`.
+  ///
+  /// [Multibase]: https://datatracker.ietf.org/doc/html/draft-multiformats-multibase-03
+  Multibase,
+}
+
 impl MethodData {
   /// Creates a new `MethodData` variant with base58-encoded content.
   pub fn new_b58(data: impl AsRef<[u8]>) -> Self {
@@ -51,6 +62,37 @@ impl MethodData {
       Self::PublicKeyJwk(_) => Err(Error::InvalidKeyData),
     }
   }
+
+  /// Returns the encoding of the `MethodData`, or `None` for `PublicKeyJwk` data.
+  pub fn encoding(&self) -> Option<MethodDataEncoding> {
+    match self {
+      Self::PublicKeyMultibase(_) => Some(MethodDataEncoding::Multibase),
+      Self::PublicKeyBase58(_) => Some(MethodDataEncoding::Base58),
+      Self::PublicKeyJwk(_) => None,
+    }
+  }
+
+  /// Re-encodes the content of the `MethodData` with the given `encoding`.
+  ///
+  /// `PublicKeyJwk` data is left unchanged.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the content cannot be decoded, in which case `self` remains unmodified.
+  pub fn normalize(&mut self, encoding: MethodDataEncoding) -> Result<()> {
+    if self.encoding().map_or(true, |current| current == encoding) {
+      return Ok(());
+    }
+
+    let data: Vec<u8> = self.try_decode()?;
+
+    *self = match encoding {
+      MethodDataEncoding::Base58 => Self::new_b58(data),
+      MethodDataEncoding::Multibase => Self::new_multibase(data),
+    };
+
+    Ok(())
+  }
 }
 
 impl Debug for MethodData {
@@ -62,3 +104,36 @@ impl Debug for MethodData {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_normalize() {
+    let bytes: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
+    let mut data: MethodData = MethodData::new_b58(bytes);
+
+    assert_eq!(data.encoding(), Some(MethodDataEncoding::Base58));
+
+    data.normalize(MethodDataEncoding::Multibase).unwrap();
+    assert_eq!(data, MethodData::new_multibase(bytes));
+    assert_eq!(data.encoding(), Some(MethodDataEncoding::Multibase));
+
+    data.normalize(MethodDataEncoding::Base58).unwrap();
+    assert_eq!(data, MethodData::new_b58(bytes));
+  }
+
+  #[test]
+  fn test_normalize_invalid() {
+    let mut data: MethodData = MethodData::PublicKeyBase58("0OIl".into());
+
+    assert!(data.normalize(MethodDataEncoding::Multibase).is_err());
+    assert_eq!(data, MethodData::PublicKeyBase58("0OIl".into()));
+
+    let mut data: MethodData = MethodData::PublicKeyJwk(Object::new());
+
+    assert!(data.normalize(MethodDataEncoding::Multibase).is_ok());
+    assert_eq!(data, MethodData::PublicKeyJwk(Object::new()));
+  }
+}
@@ -26,7 +26,7 @@ impl<'query> MethodQuery<'query> {
     }
   }
 
-  fn fragment(&self) -> Option<&str> {
+  pub(crate) fn fragment(&self) -> Option<&str> {
     let query = self.0.as_ref();
     if query.starts_with(CoreDID::SCHEME) && !query.ends_with('#') {
       // Extract the fragment from a full DID-like string
@@ -16,10 +16,16 @@ pub enum MethodScope {
 }
 
 impl MethodScope {
+  /// Returns the name of the DID Document property corresponding to the scope,
+  /// e.g. `"authentication"` or `"assertionMethod"`.
   pub fn as_str(&self) -> &'static str {
     match self {
-      Self::VerificationMethod => "VerificationMethod",
-      Self::VerificationRelationship(relationship) => relationship.into(),
+      Self::VerificationMethod => "verificationMethod",
+      Self::VerificationRelationship(MethodRelationship::Authentication) => "authentication",
+      Self::VerificationRelationship(MethodRelationship::AssertionMethod) => "assertionMethod",
+      Self::VerificationRelationship(MethodRelationship::KeyAgreement) => "keyAgreement",
+      Self::VerificationRelationship(MethodRelationship::CapabilityDelegation) => "capabilityDelegation",
+      Self::VerificationRelationship(MethodRelationship::CapabilityInvocation) => "capabilityInvocation",
     }
   }
 
@@ -53,14 +59,21 @@ impl Default for MethodScope {
 impl FromStr for MethodScope {
   type Err = Error;
 
+  /// Parses a [`MethodScope`] from a DID Document property name, e.g. `"authentication"`.
+  ///
+  /// The variant names, e.g. `"Authentication"`, are accepted as well.
   fn from_str(string: &str) -> Result<Self, Self::Err> {
     match string {
-      "VerificationMethod" => Ok(Self::VerificationMethod),
-      "Authentication" => Ok(Self::VerificationRelationship(MethodRelationship::Authentication)),
-      "AssertionMethod" => Ok(Self::VerificationRelationship(MethodRelationship::AssertionMethod)),
-      "KeyAgreement" => Ok(Self::VerificationRelationship(MethodRelationship::KeyAgreement)),
-      "CapabilityDelegation" => Ok(Self::VerificationRelationship(MethodRelationship::CapabilityDelegation)),
-      "CapabilityInvocation" => Ok(Self::VerificationRelationship(MethodRelationship::CapabilityInvocation)),
+      "verificationMethod" | "VerificationMethod" => Ok(Self::VerificationMethod),
+      "authentication" | "Authentication" => Ok(Self::VerificationRelationship(MethodRelationship::Authentication)),
+      "assertionMethod" | "AssertionMethod" => Ok(Self::VerificationRelationship(MethodRelationship::AssertionMethod)),
+      "keyAgreement" | "KeyAgreement" => Ok(Self::VerificationRelationship(MethodRelationship::KeyAgreement)),
+      "capabilityDelegation" | "CapabilityDelegation" => {
+        Ok(Self::VerificationRelationship(MethodRelationship::CapabilityDelegation))
+      }
+      "capabilityInvocation" | "CapabilityInvocation" => {
+        Ok(Self::VerificationRelationship(MethodRelationship::CapabilityInvocation))
+      }
       _ => Err(Error::UnknownMethodScope),
     }
   }
@@ -71,3 +84,40 @@ impl From<MethodRelationship> for MethodScope {
     Self::VerificationRelationship(relationship)
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_method_scope_str_round_trip() {
+    let scopes: [(MethodScope, &str); 6] = [
+      (MethodScope::VerificationMethod, "verificationMethod"),
+      (MethodScope::authentication(), "authentication"),
+      (MethodScope::assertion_method(), "assertionMethod"),
+      (MethodScope::key_agreement(), "keyAgreement"),
+      (MethodScope::capability_delegation(), "capabilityDelegation"),
+      (MethodScope::capability_invocation(), "capabilityInvocation"),
+    ];
+
+    for (scope, name) in scopes {
+      assert_eq!(scope.as_str(), name);
+      assert_eq!(MethodScope::from_str(name).unwrap(), scope);
+      assert_eq!(MethodScope::from_str(scope.as_str()).unwrap(), scope);
+    }
+
+    assert_eq!(
+      MethodScope::from_str("Authentication").unwrap(),
+      MethodScope::authentication()
+    );
+  }
+
+  #[test]
+  fn test_method_scope_unknown() {
+    assert!(matches!(
+      MethodScope::from_str("service"),
+      Err(Error::UnknownMethodScope)
+    ));
+    assert!(MethodScope::from_str("").is_err());
+  }
+}
@@ -18,6 +18,7 @@ mod verification_method;
 
 pub use self::builder::MethodBuilder;
 pub use self::method_data::MethodData;
+pub use self::method_data::MethodDataEncoding;
 pub use self::method_query::MethodQuery;
 pub use self::method_ref::MethodRef;
 pub use self::method_relationship::MethodRelationship;
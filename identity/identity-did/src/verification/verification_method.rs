@@ -45,9 +45,12 @@ impl<T> VerificationMethod<T> {
 
   /// Returns a new `Method` based on the `MethodBuilder` configuration.
   pub fn from_builder(builder: MethodBuilder<T>) -> Result<Self> {
+    let id: CoreDIDUrl = builder.id.ok_or(Error::BuilderInvalidMethodId)?;
+    let controller: CoreDID = builder.controller.unwrap_or_else(|| id.did().clone());
+
     Ok(VerificationMethod {
-      id: builder.id.ok_or(Error::BuilderInvalidMethodId)?,
-      controller: builder.controller.ok_or(Error::BuilderInvalidMethodController)?,
+      id,
+      controller,
       key_type: builder.key_type.ok_or(Error::BuilderInvalidMethodType)?,
       key_data: builder.key_data.ok_or(Error::BuilderInvalidMethodData)?,
       properties: builder.properties,
@@ -11,6 +11,8 @@ pub enum Error {
   DiffError(String),
   #[error("Merge Error: {0}")]
   MergeError(String),
+  #[error("Merge Conflict: {}", .0.join(", "))]
+  MergeConflict(Vec<String>),
   #[error("Conversion Error: {0}")]
   ConversionError(String),
 }
@@ -39,5 +39,7 @@ pub use self::hashset::DiffHashSet;
 pub use self::object::DiffObject;
 pub use self::option::DiffOption;
 pub use self::string::DiffString;
+pub use self::string::InnerString;
+pub use self::string::StringEdit;
 pub use self::traits::Diff;
 pub use self::vec::DiffVec;
@@ -118,7 +118,7 @@ mod tests {
 
     let diff = a.diff(&b).unwrap();
 
-    assert_eq!(diff, DiffOption::Some(DiffString(Some("B".to_owned()))));
+    assert_eq!(diff, DiffOption::Some(DiffString(Some("B".to_owned().into()))));
 
     let c = a.merge(diff).unwrap();
 
@@ -8,10 +8,73 @@ use crate::Diff;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 
+/// The maximum edit distance for which [`DiffString::edit_script`] computes an edit script,
+/// bounding the memory used by [`myers`]. Strings further apart are replaced instead.
+const MAX_EDIT_DISTANCE: isize = 1024;
+
 /// The Diff Type for a `String` type.
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
 #[serde(transparent)]
-pub struct DiffString(#[serde(skip_serializing_if = "Option::is_none")] pub Option<String>);
+pub struct DiffString(#[serde(skip_serializing_if = "Option::is_none")] pub Option<InnerString>);
+
+/// The Inner value for the `DiffString` type. Is `untagged` by default for `serde`, so a
+/// replacement serializes as a plain string.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InnerString {
+  /// Replaces the whole `String`.
+  Replace(String),
+  /// Applies an edit script to the `String`.
+  Edit(Vec<StringEdit>),
+}
+
+/// A single operation of a `String` edit script. Counts are in `char`s.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum StringEdit {
+  /// Keeps the next `n` characters.
+  #[serde(rename = "r")]
+  Retain(usize),
+  /// Removes the next `n` characters.
+  #[serde(rename = "d")]
+  Delete(usize),
+  /// Inserts a string.
+  #[serde(rename = "i")]
+  Insert(String),
+}
+
+impl DiffString {
+  /// Creates a `DiffString` transforming `current` into `other` with a minimal character-level
+  /// edit script computed with Myers' algorithm, instead of replacing the whole string.
+  ///
+  /// Falls back to a replacement if that is not larger than the edit script, so the result is
+  /// never larger than replacing the whole string, or if the strings differ in more than
+  /// [`MAX_EDIT_DISTANCE`] characters. Such diffs are merged like any other `DiffString`.
+  ///
+  /// [`Diff::diff`] uses this for `String`s if the `edit-script` feature is enabled.
+  pub fn edit_script(current: &str, other: &str) -> Self {
+    if current == other {
+      return Self(None);
+    }
+
+    let current: Vec<char> = current.chars().collect();
+    let other_chars: Vec<char> = other.chars().collect();
+    let script: Vec<StringEdit> = match myers(&current, &other_chars) {
+      Some(ops) => compress(&ops, &other_chars),
+      None => return Self(Some(InnerString::Replace(other.to_owned()))),
+    };
+
+    let replace_len: usize = serde_json::to_string(other).map(|json| json.len()).unwrap_or(0);
+    let script_len: usize = serde_json::to_string(&script)
+      .map(|json| json.len())
+      .unwrap_or(usize::MAX);
+
+    if script_len < replace_len {
+      Self(Some(InnerString::Edit(script)))
+    } else {
+      Self(Some(InnerString::Replace(other.to_owned())))
+    }
+  }
+}
 
 /// `Diff` trait implementation for `String`.
 impl Diff for String {
@@ -19,9 +82,13 @@ impl Diff for String {
   type Type = DiffString;
 
   /// compares two `String` types; `self`, `other` and returns a `DiffString` type.
+  ///
+  /// With the `edit-script` feature, changes are encoded with [`DiffString::edit_script`].
   fn diff(&self, other: &Self) -> crate::Result<Self::Type> {
     if self == other {
       Ok(DiffString(None))
+    } else if cfg!(feature = "edit-script") {
+      Ok(DiffString::edit_script(self, other))
     } else {
       other.clone().into_diff()
     }
@@ -29,17 +96,18 @@ impl Diff for String {
 
   /// Merges a `DiffString`; `diff` with a `String`; `self`.
   fn merge(&self, diff: Self::Type) -> crate::Result<Self> {
-    if diff.0.is_none() {
-      Ok(self.to_string())
-    } else {
-      Self::from_diff(diff)
+    match diff.0 {
+      None => Ok(self.to_string()),
+      Some(InnerString::Replace(value)) => Ok(value),
+      Some(InnerString::Edit(script)) => apply(self, &script),
     }
   }
 
   /// Converts a `DiffString` into a `String` type.
   fn from_diff(diff: Self::Type) -> crate::Result<Self> {
     match diff.0 {
-      Some(s) => Ok(s),
+      Some(InnerString::Replace(value)) => Ok(value),
+      Some(InnerString::Edit(script)) => apply("", &script),
       None => Err(crate::Error::ConversionError(
         "Problem converting from DiffString".into(),
       )),
@@ -48,7 +116,13 @@ impl Diff for String {
 
   /// Converts a `String` into a `DiffString` type.
   fn into_diff(self) -> crate::Result<Self::Type> {
-    Ok(DiffString(Some(self)))
+    Ok(DiffString(Some(InnerString::Replace(self))))
+  }
+}
+
+impl From<String> for InnerString {
+  fn from(other: String) -> Self {
+    Self::Replace(other)
   }
 }
 
@@ -56,11 +130,173 @@ impl Diff for String {
 impl Debug for DiffString {
   fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
     match &self.0 {
-      Some(val) => write!(f, "DiffString({:#?})", val),
+      Some(InnerString::Replace(val)) => write!(f, "DiffString({:#?})", val),
+      Some(InnerString::Edit(script)) => write!(f, "DiffString({:?})", script),
       None => write!(f, "DiffString None"),
     }
   }
 }
+
+/// Debug trait implementation for InnerString.
+impl Debug for InnerString {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Replace(val) => f.debug_tuple("Replace").field(val).finish(),
+      Self::Edit(script) => f.debug_tuple("Edit").field(script).finish(),
+    }
+  }
+}
+
+/// Applies an edit `script` to `value`. Characters left over after the last operation are kept.
+fn apply(value: &str, script: &[StringEdit]) -> crate::Result<String> {
+  let mut chars = value.chars();
+  let mut output: String = String::with_capacity(value.len());
+
+  for edit in script {
+    match edit {
+      StringEdit::Retain(count) => {
+        for _ in 0..*count {
+          output.push(
+            chars
+              .next()
+              .ok_or_else(|| crate::Error::merge("Edit script exceeds string length"))?,
+          );
+        }
+      }
+      StringEdit::Delete(count) => {
+        for _ in 0..*count {
+          chars
+            .next()
+            .ok_or_else(|| crate::Error::merge("Edit script exceeds string length"))?;
+        }
+      }
+      StringEdit::Insert(text) => output.push_str(text),
+    }
+  }
+
+  output.extend(chars);
+
+  Ok(output)
+}
+
+/// A single character-level operation of the shortest edit script.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+  Equal,
+  Delete,
+  Insert(usize),
+}
+
+/// Computes the shortest edit script transforming `a` into `b` (Myers, "An O(ND) Difference
+/// Algorithm and Its Variations").
+///
+/// Returns `None` if the edit distance `D` exceeds [`MAX_EDIT_DISTANCE`]. The trace of the
+/// search takes `O(D²)` memory.
+fn myers(a: &[char], b: &[char]) -> Option<Vec<Op>> {
+  let (n, m): (isize, isize) = (a.len() as isize, b.len() as isize);
+  let max: isize = n + m;
+
+  if max == 0 {
+    return Some(Vec::new());
+  }
+
+  let index = |k: isize| -> usize { (k + max + 1) as usize };
+  let mut v: Vec<isize> = vec![0; 2 * max as usize + 3];
+  let mut trace: Vec<Vec<isize>> = Vec::new();
+  let mut found: bool = false;
+
+  'search: for d in 0..=max.min(MAX_EDIT_DISTANCE) {
+    // Only the diagonals `-d - 1..=d + 1` are read when walking the trace backwards.
+    trace.push(v[index(-d - 1)..=index(d + 1)].to_vec());
+
+    for k in (-d..=d).step_by(2) {
+      let mut x: isize = if k == -d || (k != d && v[index(k - 1)] < v[index(k + 1)]) {
+        v[index(k + 1)]
+      } else {
+        v[index(k - 1)] + 1
+      };
+      let mut y: isize = x - k;
+
+      while x < n && y < m && a[x as usize] == b[y as usize] {
+        x += 1;
+        y += 1;
+      }
+
+      v[index(k)] = x;
+
+      if x >= n && y >= m {
+        found = true;
+        break 'search;
+      }
+    }
+  }
+
+  if !found {
+    return None;
+  }
+
+  // Walk the trace backwards to recover the path.
+  let mut ops: Vec<Op> = Vec::new();
+  let (mut x, mut y): (isize, isize) = (n, m);
+
+  for (d, v) in trace.iter().enumerate().rev() {
+    let d: isize = d as isize;
+    let k: isize = x - y;
+    let at = |k: isize| -> isize { v[(k + d + 1) as usize] };
+
+    let prev_k: isize = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+      k + 1
+    } else {
+      k - 1
+    };
+    let prev_x: isize = at(prev_k);
+    let prev_y: isize = prev_x - prev_k;
+
+    while x > prev_x && y > prev_y {
+      ops.push(Op::Equal);
+      x -= 1;
+      y -= 1;
+    }
+
+    if d > 0 {
+      if x == prev_x {
+        ops.push(Op::Insert(prev_y as usize));
+      } else {
+        ops.push(Op::Delete);
+      }
+    }
+
+    x = prev_x;
+    y = prev_y;
+  }
+
+  ops.reverse();
+  Some(ops)
+}
+
+/// Merges runs of character-level operations into a compact edit script. A trailing `Retain`
+/// is dropped since [`apply`] keeps the remaining characters.
+fn compress(ops: &[Op], b: &[char]) -> Vec<StringEdit> {
+  let mut script: Vec<StringEdit> = Vec::new();
+
+  for op in ops {
+    match (op, script.last_mut()) {
+      (Op::Equal, Some(StringEdit::Retain(count))) => *count += 1,
+      (Op::Equal, _) => script.push(StringEdit::Retain(1)),
+      (Op::Delete, Some(StringEdit::Delete(count))) => *count += 1,
+      (Op::Delete, _) => script.push(StringEdit::Delete(1)),
+      (Op::Insert(index), Some(StringEdit::Insert(text))) => text.push(b[*index]),
+      (Op::Insert(index), _) => script.push(StringEdit::Insert(b[*index].to_string())),
+    }
+  }
+
+  if let Some(StringEdit::Retain(_)) = script.last() {
+    script.pop();
+  }
+
+  script
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -72,7 +308,7 @@ mod tests {
 
     let diff = sa.diff(&sb).unwrap();
 
-    assert_eq!(diff, DiffString(Some("another_string".into())));
+    assert_eq!(diff, DiffString(Some("another_string".to_string().into())));
 
     let sc = sa.merge(diff).unwrap();
 
@@ -93,4 +329,95 @@ mod tests {
     assert_eq!(sb, sc);
     assert_eq!(sa, sc);
   }
+
+  #[test]
+  fn test_edit_script_one_char() {
+    let sa: String = format!("https://example.com/{}/endpoint", "a".repeat(200));
+    let sb: String = sa.replacen("example", "exbmple", 1);
+
+    let diff: DiffString = DiffString::edit_script(&sa, &sb);
+
+    // Keep the first 10 characters, then substitute a single one.
+    match &diff.0 {
+      Some(InnerString::Edit(script)) => {
+        assert_eq!(script.len(), 3);
+        assert_eq!(script[0], StringEdit::Retain(10));
+        assert!(script.contains(&StringEdit::Delete(1)));
+        assert!(script.contains(&StringEdit::Insert("b".into())));
+      }
+      _ => panic!("expected an edit script"),
+    }
+
+    let json: String = serde_json::to_string(&diff).unwrap();
+    assert!(json.len() < serde_json::to_string(&sb.clone().into_diff().unwrap()).unwrap().len());
+
+    let diff: DiffString = serde_json::from_str(&json).unwrap();
+    assert_eq!(sa.merge(diff).unwrap(), sb);
+  }
+
+  #[test]
+  fn test_diff_edit_script_feature() {
+    let sa: String = format!("https://example.com/{}/endpoint", "a".repeat(200));
+    let sb: String = sa.replacen("example", "exbmple", 1);
+
+    let diff: DiffString = sa.diff(&sb).unwrap();
+
+    if cfg!(feature = "edit-script") {
+      assert_eq!(diff, DiffString::edit_script(&sa, &sb));
+    } else {
+      assert_eq!(diff, DiffString(Some(sb.clone().into())));
+    }
+
+    assert_eq!(sa.merge(diff).unwrap(), sb);
+  }
+
+  #[test]
+  fn test_edit_script_merge() {
+    let cases: &[(&str, &str)] = &[
+      ("", "abc"),
+      ("abc", ""),
+      ("abcdef", "abXdeYf"),
+      ("the quick brown fox", "the quick red fox jumps"),
+      ("äöü", "aöü!"),
+    ];
+
+    for (sa, sb) in cases {
+      let diff: DiffString = DiffString::edit_script(sa, sb);
+      assert_eq!(sa.to_string().merge(diff).unwrap(), *sb);
+    }
+
+    assert_eq!(DiffString::edit_script("abc", "abc"), DiffString(None));
+  }
+
+  #[test]
+  fn test_edit_script_small_replacement() {
+    // Replacing a short string entirely is smaller than an edit script.
+    let diff: DiffString = DiffString::edit_script("test", "another_string");
+    assert_eq!(diff, DiffString(Some("another_string".to_string().into())));
+  }
+
+  #[test]
+  fn test_edit_script_max_distance() {
+    let sa: String = "a".repeat(2 * MAX_EDIT_DISTANCE as usize);
+    let chars: Vec<char> = sa.chars().collect();
+
+    // Every other character is deleted, right up to the maximum edit distance.
+    let within: Vec<char> = chars.iter().copied().step_by(2).collect();
+    assert!(myers(&chars, &within).is_some());
+
+    let beyond: Vec<char> = within[1..].to_vec();
+    assert!(myers(&chars, &beyond).is_none());
+
+    // Strings further apart are replaced.
+    let sb: String = beyond.iter().collect();
+    let diff: DiffString = DiffString::edit_script(&sa, &sb);
+    assert_eq!(diff, DiffString(Some(sb.clone().into())));
+    assert_eq!(sa.merge(diff).unwrap(), sb);
+  }
+
+  #[test]
+  fn test_edit_script_invalid() {
+    let diff: DiffString = DiffString(Some(InnerString::Edit(vec![StringEdit::Retain(10)])));
+    assert!("abc".to_string().merge(diff).is_err());
+  }
 }
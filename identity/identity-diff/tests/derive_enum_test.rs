@@ -0,0 +1,88 @@
+// Copyright 2020-2021 Runnerc
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_diff::Diff;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Debug, PartialEq, Diff, Serialize, Deserialize)]
+pub enum Shape {
+  Point,
+  Circle(u32),
+  Rect { width: u32, height: u32 },
+  Label(String, #[diff(should_ignore)] u32),
+}
+
+#[test]
+fn test_same_variant_named() {
+  let a: Shape = Shape::Rect { width: 1, height: 2 };
+  let b: Shape = Shape::Rect { width: 1, height: 5 };
+
+  let diff: DiffShape = a.diff(&b).unwrap();
+
+  // Only the changed field is recorded.
+  assert!(matches!(diff, DiffShape::Rect { width: None, height: Some(_) }));
+  assert_eq!(a.merge(diff).unwrap(), b);
+}
+
+#[test]
+fn test_same_variant_tuple() {
+  let a: Shape = Shape::Circle(1);
+  let b: Shape = Shape::Circle(2);
+
+  let diff: DiffShape = a.diff(&b).unwrap();
+  assert!(matches!(diff, DiffShape::Circle(Some(_))));
+  assert_eq!(a.merge(diff).unwrap(), b);
+
+  // Unchanged values produce an empty diff.
+  let diff: DiffShape = a.diff(&a).unwrap();
+  assert!(matches!(diff, DiffShape::Circle(None)));
+  assert_eq!(a.merge(diff).unwrap(), a);
+}
+
+#[test]
+fn test_same_variant_ignored_field() {
+  let a: Shape = Shape::Label("a".into(), 1);
+  let b: Shape = Shape::Label("b".into(), 2);
+
+  let diff: DiffShape = a.diff(&b).unwrap();
+  assert!(matches!(diff, DiffShape::Label(Some(_), None)));
+  // The ignored field keeps its current value.
+  assert_eq!(a.merge(diff).unwrap(), Shape::Label("b".into(), 1));
+}
+
+#[test]
+fn test_cross_variant() {
+  let a: Shape = Shape::Circle(3);
+  let b: Shape = Shape::Rect { width: 1, height: 2 };
+
+  // The whole value is replaced.
+  let diff: DiffShape = a.diff(&b).unwrap();
+  assert!(matches!(diff, DiffShape::Rect { width: Some(_), height: Some(_) }));
+  assert_eq!(a.merge(diff).unwrap(), b);
+
+  let diff: DiffShape = b.diff(&Shape::Point).unwrap();
+  assert!(matches!(diff, DiffShape::Point));
+  assert_eq!(b.merge(diff).unwrap(), Shape::Point);
+
+  let diff: DiffShape = Shape::Point.diff(&a).unwrap();
+  assert_eq!(Shape::Point.merge(diff).unwrap(), a);
+}
+
+#[test]
+fn test_from_into_diff() {
+  let a: Shape = Shape::Rect { width: 1, height: 2 };
+  let diff: DiffShape = a.clone().into_diff().unwrap();
+  assert_eq!(Shape::from_diff(diff).unwrap(), a);
+}
+
+#[test]
+fn test_serde_round_trip() {
+  let a: Shape = Shape::Circle(3);
+  let b: Shape = Shape::Rect { width: 1, height: 2 };
+
+  let diff: DiffShape = a.diff(&b).unwrap();
+  let json: String = serde_json::to_string(&diff).unwrap();
+  let diff: DiffShape = serde_json::from_str(&json).unwrap();
+  assert_eq!(a.merge(diff).unwrap(), b);
+}
@@ -0,0 +1,432 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use hyper::body::Bytes;
+use hyper::body::HttpBody;
+use hyper::client::HttpConnector;
+use hyper::header::CONTENT_LENGTH;
+use hyper::Body;
+use hyper::Client;
+use hyper::Response;
+use hyper::StatusCode;
+use hyper_tls::HttpsConnector;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_credential::credential::Credential;
+use identity_did::did::DID;
+use identity_did::service::Service;
+
+use crate::credential::ValidationLimits;
+use crate::did::RunnercDID;
+use crate::document::RunnercDocument;
+use crate::error::Error;
+use crate::error::Result;
+use crate::runnerc::TangleResolve;
+
+/// The path of the DID configuration resource of a domain.
+pub const DID_CONFIGURATION_PATH: &str = "/.well-known/did-configuration.json";
+
+/// The `@context` of a [`DIDConfiguration`].
+pub const DID_CONFIGURATION_CONTEXT: &str = "https://identity.foundation/.well-known/did-configuration/v1";
+
+const DOMAIN_LINKAGE_CREDENTIAL: &str = "DomainLinkageCredential";
+const LINKED_DOMAINS: &str = "LinkedDomains";
+
+/// The maximum size of a [`DIDConfiguration`] resource in bytes.
+const MAX_DID_CONFIGURATION_SIZE: usize = 256 * 1024;
+
+/// The time after which fetching a [`DIDConfiguration`] fails.
+const DID_CONFIGURATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The DID configuration resource served by a domain, listing the DIDs it is linked to.
+///
+/// NOTE: Only JSON-LD domain linkage credentials are supported, not JWTs.
+///
+/// [Specification](https://identity.foundation/.well-known/resources/did-configuration/)
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DIDConfiguration {
+  #[serde(rename = "@context")]
+  pub context: Url,
+  pub linked_dids: Vec<Credential>,
+}
+
+/// Verifies that `did` and the domain of `domain_url` are linked to each other.
+///
+/// The DID Document of `did` is read from `resolver` and must contain a `LinkedDomains` service
+/// with an endpoint on the origin of `domain_url`. The [`DIDConfiguration`] served at
+/// [`DID_CONFIGURATION_PATH`] of the domain must contain an unexpired `DomainLinkageCredential`
+/// issued to `did` for that origin and signed by a method of the DID Document.
+///
+/// # Errors
+///
+/// Fails with [`Error::InvalidLinkedDomain`] if `domain_url` is not an `https` URL or either
+/// direction of the link is missing, and with [`Error::DIDConfigurationUnavailable`] if the
+/// configuration cannot be fetched or parsed.
+pub async fn verify_linked_domain<R>(did: &RunnercDID, domain_url: &Url, resolver: &R) -> Result<()>
+where
+  R: TangleResolve,
+{
+  if domain_url.scheme() != "https" {
+    return Err(Error::InvalidLinkedDomain("the domain is not served over https"));
+  }
+
+  verify_linked_origin(did, domain_url, resolver).await
+}
+
+/// Verifies the link like [`verify_linked_domain`], whatever the scheme of `domain_url`.
+async fn verify_linked_origin<R>(did: &RunnercDID, domain_url: &Url, resolver: &R) -> Result<()>
+where
+  R: TangleResolve,
+{
+  let document: RunnercDocument = resolver.resolve(did).await?;
+  let configuration: DIDConfiguration = fetch_did_configuration(domain_url).await?;
+
+  check_linked_domain(&document, domain_url, &configuration, Timestamp::now_utc())
+}
+
+/// Fetches the [`DIDConfiguration`] of the domain of `domain_url`.
+async fn fetch_did_configuration(domain_url: &Url) -> Result<DIDConfiguration> {
+  let uri: http::Uri = domain_url
+    .join(DID_CONFIGURATION_PATH)?
+    .as_str()
+    .parse()
+    .map_err(|err: http::uri::InvalidUri| Error::DIDConfigurationUnavailable(err.to_string()))?;
+
+  let client: Client<HttpsConnector<HttpConnector>> = Client::builder().build(HttpsConnector::new());
+  let request = async {
+    let response: Response<Body> = client
+      .get(uri)
+      .await
+      .map_err(|err| Error::DIDConfigurationUnavailable(err.to_string()))?;
+
+    read_body(response).await
+  };
+
+  let body: Vec<u8> = tokio::time::timeout(DID_CONFIGURATION_TIMEOUT, request)
+    .await
+    .map_err(|_| Error::DIDConfigurationUnavailable(format!("timed out after {:?}", DID_CONFIGURATION_TIMEOUT)))??;
+
+  let value: Value =
+    Value::from_json_slice(&body).map_err(|err| Error::DIDConfigurationUnavailable(err.to_string()))?;
+
+  ValidationLimits::default().check(&value)?;
+
+  DIDConfiguration::from_json_value(value).map_err(|err| Error::DIDConfigurationUnavailable(err.to_string()))
+}
+
+/// Reads the body of a successful `response` of at most [`MAX_DID_CONFIGURATION_SIZE`] bytes.
+async fn read_body(mut response: Response<Body>) -> Result<Vec<u8>> {
+  let status: StatusCode = response.status();
+
+  if !status.is_success() {
+    return Err(Error::DIDConfigurationUnavailable(format!("status {}", status)));
+  }
+
+  let too_large = || Error::DIDConfigurationUnavailable(format!("exceeds {} bytes", MAX_DID_CONFIGURATION_SIZE));
+
+  let length: Option<usize> = response
+    .headers()
+    .get(CONTENT_LENGTH)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.parse().ok());
+
+  if matches!(length, Some(length) if length > MAX_DID_CONFIGURATION_SIZE) {
+    return Err(too_large());
+  }
+
+  // The length is not known for chunked responses, so it is checked while reading as well.
+  let mut body: Vec<u8> = Vec::with_capacity(length.unwrap_or_default());
+
+  while let Some(chunk) = response.body_mut().data().await {
+    let chunk: Bytes = chunk.map_err(|err| Error::DIDConfigurationUnavailable(err.to_string()))?;
+
+    if body.len() + chunk.len() > MAX_DID_CONFIGURATION_SIZE {
+      return Err(too_large());
+    }
+
+    body.extend_from_slice(&chunk);
+  }
+
+  Ok(body)
+}
+
+/// Checks both directions of the link between the DID Document and the domain of `domain_url`.
+fn check_linked_domain(
+  document: &RunnercDocument,
+  domain_url: &Url,
+  configuration: &DIDConfiguration,
+  now: Timestamp,
+) -> Result<()> {
+  let links_domain = |service: &&Service| {
+    service
+      .endpoint_urls()
+      .iter()
+      .any(|endpoint| endpoint.origin() == domain_url.origin())
+  };
+
+  if !document.services_by_type(LINKED_DOMAINS).iter().any(links_domain) {
+    return Err(Error::InvalidLinkedDomain("missing `LinkedDomains` service"));
+  }
+
+  if configuration.context.as_str() != DID_CONFIGURATION_CONTEXT {
+    return Err(Error::InvalidLinkedDomain("invalid DID configuration context"));
+  }
+
+  let origin: String = domain_url.origin().ascii_serialization();
+
+  // Compare parsed DIDs, which are normalized, e.g. `did:idns:main:123` is `did:idns:123`.
+  let is_did = |url: &Url| RunnercDID::parse(url.as_str()).map_or(false, |did| &did == document.id());
+
+  let links_did = |credential: &&Credential| {
+    credential.types.iter().any(|type_| type_ == DOMAIN_LINKAGE_CREDENTIAL)
+      && is_did(credential.issuer.url())
+      && credential.credential_subject.iter().any(|subject| {
+        subject.id.as_ref().map_or(false, is_did)
+          && subject.properties.get("origin").and_then(Value::as_str) == Some(origin.as_str())
+      })
+      && !matches!(credential.expiration_date, Some(expiration) if expiration <= now)
+  };
+
+  if configuration
+    .linked_dids
+    .iter()
+    .filter(links_did)
+    .any(|credential| document.verify_data(credential).is_ok())
+  {
+    Ok(())
+  } else {
+    Err(Error::InvalidLinkedDomain("missing domain linkage credential"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::convert::Infallible;
+  use std::net::TcpListener;
+
+  use hyper::service::make_service_fn;
+  use hyper::service::service_fn;
+  use hyper::Request;
+  use hyper::Server;
+
+  use identity_core::convert::ToJson;
+  use identity_core::crypto::KeyPair;
+  use identity_core::json;
+  use identity_credential::credential::CredentialBuilder;
+  use identity_credential::credential::Subject;
+  use identity_did::did::CoreDIDUrl;
+  use identity_did::service::ServiceEndpoint;
+
+  use super::*;
+
+  const DOMAIN: &str = "https://example.com";
+  const EXPIRATION: &str = "2100-01-01T00:00:00Z";
+
+  /// A [`TangleResolve`] returning the same document for every DID.
+  struct MockResolver(RunnercDocument);
+
+  #[async_trait::async_trait(?Send)]
+  impl TangleResolve for MockResolver {
+    async fn resolve(&self, _did: &RunnercDID) -> Result<RunnercDocument> {
+      Ok(self.0.clone())
+    }
+  }
+
+  /// Serves the configuration created by `f` for the URL of the server, returning that URL.
+  fn serve<F>(f: F) -> Url
+  where
+    F: FnOnce(&Url) -> DIDConfiguration,
+  {
+    let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url: Url = Url::parse(format!("http://{}", listener.local_addr().unwrap())).unwrap();
+    let body: String = f(&url).to_json().unwrap();
+
+    let make_service = make_service_fn(move |_| {
+      let body: String = body.clone();
+      async move {
+        Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+          let response: Response<Body> = if request.uri().path() == DID_CONFIGURATION_PATH {
+            Response::new(Body::from(body.clone()))
+          } else {
+            Response::builder()
+              .status(StatusCode::NOT_FOUND)
+              .body(Body::empty())
+              .unwrap()
+          };
+          async move { Ok::<_, Infallible>(response) }
+        }))
+      }
+    });
+
+    tokio::spawn(Server::from_tcp(listener).unwrap().serve(make_service));
+    url
+  }
+
+  fn document(keypair: &KeyPair, domain_url: &Url) -> RunnercDocument {
+    let mut document: RunnercDocument = RunnercDocument::new(keypair).unwrap();
+    let service: Service = Service::builder(Default::default())
+      .id(CoreDIDUrl::parse(format!("{}#linked-domain", document.id())).unwrap())
+      .type_(LINKED_DOMAINS)
+      .service_endpoint(ServiceEndpoint::One(domain_url.clone()))
+      .build()
+      .unwrap();
+
+    assert!(document.insert_service(service));
+    document
+  }
+
+  fn configuration(document: &RunnercDocument, keypair: &KeyPair, domain_url: &Url) -> DIDConfiguration {
+    configuration_for(&document.id().to_string(), document, keypair, domain_url)
+  }
+
+  /// Creates a configuration linking the origin of `domain_url` to `did`, the DID of `document`.
+  fn configuration_for(did: &str, document: &RunnercDocument, keypair: &KeyPair, domain_url: &Url) -> DIDConfiguration {
+    let subject: Subject = Subject::from_json_value(json!({
+      "id": did,
+      "origin": domain_url.origin().ascii_serialization(),
+    }))
+    .unwrap();
+
+    let mut credential: Credential = CredentialBuilder::default()
+      .context(Url::parse(DID_CONFIGURATION_CONTEXT).unwrap())
+      .issuer(Url::parse(did).unwrap())
+      .type_(DOMAIN_LINKAGE_CREDENTIAL)
+      .subject(subject)
+      .expiration_date(Timestamp::parse(EXPIRATION).unwrap())
+      .build()
+      .unwrap();
+
+    document
+      .sign_data(&mut credential, keypair.private(), "#sign-0")
+      .unwrap();
+
+    DIDConfiguration {
+      context: Url::parse(DID_CONFIGURATION_CONTEXT).unwrap(),
+      linked_dids: vec![credential],
+    }
+  }
+
+  #[tokio::test]
+  async fn test_verify_linked_domain() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut resolver: Option<MockResolver> = None;
+
+    let domain_url: Url = serve(|url| {
+      let document: RunnercDocument = document(&keypair, url);
+      let configuration: DIDConfiguration = configuration(&document, &keypair, url);
+      resolver = Some(MockResolver(document));
+      configuration
+    });
+    let resolver: MockResolver = resolver.unwrap();
+
+    assert!(verify_linked_origin(resolver.0.id(), &domain_url, &resolver)
+      .await
+      .is_ok());
+
+    // Only domains served over https are verified.
+    assert!(matches!(
+      verify_linked_domain(resolver.0.id(), &domain_url, &resolver).await,
+      Err(Error::InvalidLinkedDomain(_))
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_verify_linked_domain_unavailable() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+
+    // Nothing listens on a port once its listener is dropped.
+    let port: u16 = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let domain_url: Url = Url::parse(format!("http://127.0.0.1:{}", port)).unwrap();
+    let resolver: MockResolver = MockResolver(document(&keypair, &domain_url));
+
+    assert!(matches!(
+      verify_linked_origin(resolver.0.id(), &domain_url, &resolver).await,
+      Err(Error::DIDConfigurationUnavailable(_))
+    ));
+  }
+
+  #[tokio::test]
+  async fn test_read_body() {
+    let body: Vec<u8> = vec![b' '; MAX_DID_CONFIGURATION_SIZE];
+    assert_eq!(read_body(Response::new(Body::from(body.clone()))).await.unwrap(), body);
+
+    let response: Response<Body> = Response::builder()
+      .status(StatusCode::NOT_FOUND)
+      .body(Body::empty())
+      .unwrap();
+    assert!(matches!(
+      read_body(response).await,
+      Err(Error::DIDConfigurationUnavailable(_))
+    ));
+
+    // The announced length is too large.
+    let response: Response<Body> = Response::builder()
+      .header(CONTENT_LENGTH, MAX_DID_CONFIGURATION_SIZE + 1)
+      .body(Body::empty())
+      .unwrap();
+    assert!(matches!(
+      read_body(response).await,
+      Err(Error::DIDConfigurationUnavailable(_))
+    ));
+
+    // The chunks of a response without a length are too large.
+    let chunks: Vec<Result<Vec<u8>, Infallible>> = vec![Ok(body), Ok(vec![b' '])];
+    let response: Response<Body> = Response::new(Body::wrap_stream(futures::stream::iter(chunks)));
+    assert!(matches!(
+      read_body(response).await,
+      Err(Error::DIDConfigurationUnavailable(_))
+    ));
+  }
+
+  #[test]
+  fn test_check_linked_domain() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let domain_url: Url = Url::parse(DOMAIN).unwrap();
+    let linked: RunnercDocument = document(&keypair, &domain_url);
+    let valid: DIDConfiguration = configuration(&linked, &keypair, &domain_url);
+    let now: Timestamp = Timestamp::now_utc();
+
+    assert!(check_linked_domain(&linked, &domain_url, &valid, now).is_ok());
+
+    // The DIDs of the credential are compared after normalization.
+    let explicit_network: String = linked.id().to_string().replacen("did:idns:", "did:idns:main:", 1);
+    let explicit: DIDConfiguration = configuration_for(&explicit_network, &linked, &keypair, &domain_url);
+    assert!(check_linked_domain(&linked, &domain_url, &explicit, now).is_ok());
+
+    // The DID Document does not link to the domain.
+    let other_url: Url = Url::parse("https://other.example.com").unwrap();
+    let unlinked: RunnercDocument = document(&keypair, &other_url);
+    assert!(matches!(
+      check_linked_domain(&unlinked, &domain_url, &valid, now),
+      Err(Error::InvalidLinkedDomain(_))
+    ));
+
+    // The credential was issued for another origin.
+    let other_origin: DIDConfiguration = configuration(&linked, &keypair, &other_url);
+    assert!(matches!(
+      check_linked_domain(&linked, &domain_url, &other_origin, now),
+      Err(Error::InvalidLinkedDomain(_))
+    ));
+
+    // The credential was modified after it was signed.
+    let mut tampered: DIDConfiguration = valid.clone();
+    tampered.linked_dids[0].issuance_date = Timestamp::parse("2010-01-01T00:00:00Z").unwrap();
+    assert!(matches!(
+      check_linked_domain(&linked, &domain_url, &tampered, now),
+      Err(Error::InvalidLinkedDomain(_))
+    ));
+
+    // The credential has expired.
+    assert!(matches!(
+      check_linked_domain(&linked, &domain_url, &valid, Timestamp::parse(EXPIRATION).unwrap()),
+      Err(Error::InvalidLinkedDomain(_))
+    ));
+  }
+}
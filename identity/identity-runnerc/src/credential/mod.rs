@@ -1,9 +1,18 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod domain_linkage;
 mod validator;
 
+pub use self::domain_linkage::verify_linked_domain;
+pub use self::domain_linkage::DIDConfiguration;
+pub use self::domain_linkage::DID_CONFIGURATION_CONTEXT;
+pub use self::domain_linkage::DID_CONFIGURATION_PATH;
+pub use self::validator::AcceptAllTerms;
 pub use self::validator::CredentialValidation;
 pub use self::validator::CredentialValidator;
 pub use self::validator::DocumentValidation;
 pub use self::validator::PresentationValidation;
+pub use self::validator::TermsEvaluator;
+pub use self::validator::ValidationCheck;
+pub use self::validator::ValidationLimits;
@@ -2,13 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Arc;
 
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use serde_json::Value;
 
 use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
 use identity_core::convert::FromJson;
+use identity_core::crypto::Signature;
 use identity_credential::credential::Credential;
+use identity_credential::credential::Policy;
+use identity_credential::credential::ProofPolicy;
 use identity_credential::presentation::Presentation;
 
 use crate::did::RunnercDID;
@@ -23,6 +31,14 @@ pub struct CredentialValidation<T = Object> {
     pub issuer: DocumentValidation,
     pub subjects: BTreeMap<String, DocumentValidation>,
     pub verified: bool,
+    pub summary: Vec<ValidationCheck>,
+}
+
+impl<T> CredentialValidation<T> {
+    /// Returns an iterator over the checks that did not pass.
+    pub fn failed_checks(&self) -> impl Iterator<Item = &ValidationCheck> + '_ {
+        self.summary.iter().filter(|check| !check.is_verified())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
@@ -31,6 +47,67 @@ pub struct PresentationValidation<T = Object, U = Object> {
     pub holder: DocumentValidation,
     pub credentials: Vec<CredentialValidation<U>>,
     pub verified: bool,
+    pub summary: Vec<ValidationCheck>,
+}
+
+impl<T, U> PresentationValidation<T, U> {
+    /// Returns an iterator over the checks that did not pass.
+    pub fn failed_checks(&self) -> impl Iterator<Item = &ValidationCheck> + '_ {
+        self.summary.iter().filter(|check| !check.is_verified())
+    }
+
+    /// Returns the indices of the credentials that failed validation.
+    pub fn failed_credentials(&self) -> Vec<usize> {
+        self.failed_checks()
+            .filter_map(|check| match check {
+                ValidationCheck::Credential { index, .. } => Some(*index),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The outcome of a single check performed during credential or presentation validation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "check", rename_all = "camelCase")]
+pub enum ValidationCheck {
+    /// The issuer DID Document was resolved and verified.
+    Issuer { did: String, verified: bool },
+    /// The credential proof was verified against the issuer DID Document.
+    CredentialProof { verified: bool },
+    /// The DID Document of a credential subject was resolved and verified.
+    Subject { did: String, verified: bool },
+    /// The terms-of-use policy at `index` was accepted by the [`TermsEvaluator`].
+    TermsOfUse {
+        index: usize,
+        id: Option<String>,
+        verified: bool,
+    },
+    /// The holder DID Document was resolved and verified.
+    Holder { did: String, verified: bool },
+    /// The presentation proof was verified against the holder DID Document.
+    PresentationProof { verified: bool },
+    /// The credential at `index` in the presentation was verified.
+    Credential {
+        index: usize,
+        id: Option<String>,
+        verified: bool,
+    },
+}
+
+impl ValidationCheck {
+    /// Returns `true` if the check passed.
+    pub fn is_verified(&self) -> bool {
+        match self {
+            Self::Issuer { verified, .. }
+            | Self::CredentialProof { verified }
+            | Self::Subject { verified, .. }
+            | Self::TermsOfUse { verified, .. }
+            | Self::Holder { verified, .. }
+            | Self::PresentationProof { verified }
+            | Self::Credential { verified, .. } => *verified,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize)]
@@ -43,22 +120,179 @@ pub struct DocumentValidation {
 
 unsafe impl std::marker::Send for DocumentValidation {}
 
-#[derive()]
-pub struct CredentialValidator {}
+/// Structural limits enforced on untrusted JSON before it is deserialized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationLimits {
+    /// The maximum nesting depth of arrays and objects.
+    pub max_depth: usize,
+    /// The maximum total number of JSON values.
+    pub max_elements: usize,
+}
+
+impl ValidationLimits {
+    pub const DEFAULT_MAX_DEPTH: usize = 32;
+    pub const DEFAULT_MAX_ELEMENTS: usize = 10_000;
+
+    /// Walks the given JSON `value` and checks it against the limits.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::ExceededMaxDepth`] or [`Error::ExceededMaxElements`] if a limit
+    /// is exceeded.
+    pub fn check(&self, value: &Value) -> Result<()> {
+        let mut elements: usize = 0;
+        let mut stack: Vec<(&Value, usize)> = vec![(value, 1)];
+
+        while let Some((value, depth)) = stack.pop() {
+            if depth > self.max_depth {
+                return Err(Error::ExceededMaxDepth(self.max_depth));
+            }
+
+            elements += 1;
+
+            if elements > self.max_elements {
+                return Err(Error::ExceededMaxElements(self.max_elements));
+            }
+
+            match value {
+                Value::Array(array) => stack.extend(array.iter().map(|value| (value, depth + 1))),
+                Value::Object(object) => stack.extend(object.values().map(|value| (value, depth + 1))),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            max_elements: Self::DEFAULT_MAX_ELEMENTS,
+        }
+    }
+}
+
+/// The refresh service type resolved by [`CredentialValidator::resolve_refresh`].
+const MANUAL_REFRESH_SERVICE: &str = "ManualRefreshService2018";
+
+/// Decides whether the terms-of-use of a credential are acceptable to the application.
+///
+/// The [`CredentialValidator`] invokes the evaluator once for every [`Policy`] in
+/// `Credential::terms_of_use`.
+pub trait TermsEvaluator: Debug + Send + Sync {
+    /// Returns `true` if the given `policy` is accepted.
+    fn evaluate(&self, policy: &Policy) -> bool;
+}
+
+/// A [`TermsEvaluator`] accepting every [`Policy`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AcceptAllTerms;
+
+impl TermsEvaluator for AcceptAllTerms {
+    fn evaluate(&self, _policy: &Policy) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CredentialValidator {
+    limits: ValidationLimits,
+    terms: Arc<dyn TermsEvaluator>,
+}
+
+impl Default for CredentialValidator {
+    fn default() -> Self {
+        Self {
+            limits: ValidationLimits::default(),
+            terms: Arc::new(AcceptAllTerms),
+        }
+    }
+}
 
 impl CredentialValidator {
+    /// Creates a new [`CredentialValidator`] with the default [`ValidationLimits`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`CredentialValidator`] enforcing the given [`ValidationLimits`] in
+    /// [`check`](Self::check) and [`check_presentation`](Self::check_presentation).
+    pub fn with_limits(limits: ValidationLimits) -> Self {
+        Self {
+            limits,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the [`TermsEvaluator`] used to accept or reject the terms-of-use of validated
+    /// credentials. Defaults to [`AcceptAllTerms`].
+    pub fn with_terms_evaluator<E>(mut self, evaluator: E) -> Self
+    where
+        E: TermsEvaluator + 'static,
+    {
+        self.terms = Arc::new(evaluator);
+        self
+    }
+
+    /// Returns the [`ValidationLimits`] enforced on JSON input.
+    pub fn limits(&self) -> ValidationLimits {
+        self.limits
+    }
+
+    /// Returns the endpoint of the manual refresh service of an expired `Credential`, if any.
+    ///
+    /// The endpoint is not contacted; a wallet is expected to prompt the user to refresh the
+    /// credential. Returns `None` if the credential has not expired or does not list a
+    /// `ManualRefreshService2018` entry in its `refreshService`.
+    pub fn resolve_refresh<T>(credential: &Credential<T>) -> Option<&Url> {
+        Self::resolve_refresh_at(credential, Timestamp::now_utc())
+    }
+
+    fn resolve_refresh_at<T>(credential: &Credential<T>, now: Timestamp) -> Option<&Url> {
+        if !matches!(credential.expiration_date, Some(expiration) if expiration <= now) {
+            return None;
+        }
+
+        credential
+            .refresh_service
+            .iter()
+            .find(|service| service.types.iter().any(|type_| type_ == MANUAL_REFRESH_SERVICE))
+            .map(|service| &service.id)
+    }
+
+    /// Parses the given JSON `data` and checks it against the configured limits.
+    fn parse_limited(&self, data: &str) -> Result<Value> {
+        let value: Value = Value::from_json(data)?;
+
+        self.limits.check(&value)?;
+
+        Ok(value)
+    }
+
     /// Deserializes the given JSON-encoded `Credential` and validates
     /// all associated DID documents.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `data` exceeds the configured [`ValidationLimits`].
     pub async fn check<T>(&self, data: &str, client: &ClientMap) -> Result<CredentialValidation<T>>
     where
-        T: DeserializeOwned + Serialize,
+        T: Clone + DeserializeOwned + Serialize,
     {
-        self.validate_credential(Credential::from_json(data)?, client)
+        let value: Value = self.parse_limited(data)?;
+
+        self.validate_credential(Credential::from_json_value(value)?, client)
             .await
     }
 
     /// Deserializes the given JSON-encoded `Presentation` and
     /// validates all associated DID documents/`Credential`s.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `data` exceeds the configured [`ValidationLimits`].
     pub async fn check_presentation<T, U>(
         &self,
         data: &str,
@@ -68,7 +302,9 @@ impl CredentialValidator {
         T: Clone + DeserializeOwned + Serialize,
         U: Clone + DeserializeOwned + Serialize,
     {
-        self.validate_presentation(Presentation::from_json(data)?, client)
+        let value: Value = self.parse_limited(data)?;
+
+        self.validate_presentation(Presentation::from_json_value(value)?, client)
             .await
     }
 
@@ -83,7 +319,7 @@ impl CredentialValidator {
         client: &ClientMap,
     ) -> Result<CredentialValidation<T>>
     where
-        T: Serialize,
+        T: Clone + Serialize,
     {
         // Resolve the issuer DID Document and validate the digital signature.
         let issuer_url: &str = credential.issuer.url().as_str();
@@ -97,32 +333,85 @@ impl CredentialValidator {
         let mut subjects: BTreeMap<String, DocumentValidation> = BTreeMap::new();
 
         // Resolve all credential subjects with `id`s - we assume all ids are DIDs.
-        for id in credential
-            .credential_subject
-            .iter()
-            .filter_map(|subject| subject.id.as_ref())
-        {
+        for id in credential.subject_dids() {
             subjects.insert(
                 id.to_string(),
                 self.validate_document(id.as_str(), client).await?,
             );
         }
 
-        // Verify the credential signature using the issuers DID Document
-        let credential_verified: bool = issuer_doc.document.verify_data(&credential).is_ok();
+        Ok(self.verify_credential(credential, issuer_doc, subjects))
+    }
+
+    /// Verifies the `Credential` proof against the already resolved DID documents, evaluates
+    /// its terms-of-use and summarizes the outcome of each check.
+    fn verify_credential<T>(
+        &self,
+        credential: Credential<T>,
+        issuer: DocumentValidation,
+        subjects: BTreeMap<String, DocumentValidation>,
+    ) -> CredentialValidation<T>
+    where
+        T: Clone + Serialize,
+    {
+        // Verify the credential signature(s) using the issuers DID Document - a proof set is
+        // accepted if any of its proofs was created by the issuer
+        let credential_verified: bool = credential
+            .verify_proofs(ProofPolicy::Any, |credential| {
+                issuer.document.verify_data(credential).is_ok()
+            })
+            .is_ok();
 
         // Check if all subjects have valid signatures
         let subjects_verified: bool = subjects.values().all(|subject| subject.verified);
 
+        // Check if all terms-of-use policies are accepted
+        let terms: Vec<bool> = credential
+            .terms_of_use
+            .iter()
+            .map(|policy| self.terms.evaluate(policy))
+            .collect();
+        let terms_verified: bool = terms.iter().all(|accepted| *accepted);
+
         // The credential is truly verified if all associated documents are verified
-        let verified: bool = issuer_doc.verified && credential_verified && subjects_verified;
+        let verified: bool =
+            issuer.verified && credential_verified && subjects_verified && terms_verified;
+
+        let mut summary: Vec<ValidationCheck> = vec![
+            ValidationCheck::Issuer {
+                did: issuer.did.to_string(),
+                verified: issuer.verified,
+            },
+            ValidationCheck::CredentialProof {
+                verified: credential_verified,
+            },
+        ];
+
+        summary.extend(subjects.iter().map(|(did, subject)| ValidationCheck::Subject {
+            did: did.clone(),
+            verified: subject.verified,
+        }));
 
-        Ok(CredentialValidation {
+        summary.extend(
+            credential
+                .terms_of_use
+                .iter()
+                .zip(terms)
+                .enumerate()
+                .map(|(index, (policy, verified))| ValidationCheck::TermsOfUse {
+                    index,
+                    id: policy.id.as_ref().map(ToString::to_string),
+                    verified,
+                }),
+        );
+
+        CredentialValidation {
             credential,
-            issuer: issuer_doc,
+            issuer,
             subjects,
             verified,
-        })
+            summary,
+        }
     }
 
     /// Validates the `Presentation` proof and all relevant DID documents.
@@ -154,24 +443,111 @@ impl CredentialValidator {
             credentials.push(self.validate_credential(credential.clone(), client).await?);
         }
 
+        Ok(Self::verify_presentation(presentation, holder_doc, credentials))
+    }
+
+    /// Validates the `Presentation` like [`CredentialValidator::validate_presentation`],
+    /// additionally requiring the holder proof to be bound to the verifier `challenge` and, if
+    /// given, `domain`.
+    ///
+    /// This prevents a presentation created for one verifier from being replayed to another.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`Error::InvalidPresentationChallenge`] if the proof challenge or domain do
+    /// not match the expected values, or if the presentation cannot be validated.
+    pub async fn validate_presentation_with_challenge<T, U>(
+        &self,
+        presentation: Presentation<T, U>,
+        challenge: &str,
+        domain: Option<&str>,
+        client: &ClientMap,
+    ) -> Result<PresentationValidation<T, U>>
+    where
+        T: Clone + Serialize,
+        U: Clone + Serialize,
+    {
+        Self::check_challenge(&presentation, challenge, domain)?;
+
+        self.validate_presentation(presentation, client).await
+    }
+
+    /// Checks that the proof of the `Presentation` was created for the given `challenge` and,
+    /// if given, `domain`.
+    ///
+    /// The proof options are covered by the signature, which is verified separately.
+    fn check_challenge<T, U>(
+        presentation: &Presentation<T, U>,
+        challenge: &str,
+        domain: Option<&str>,
+    ) -> Result<()> {
+        let proof: &Signature = presentation
+            .proof()
+            .ok_or(Error::InvalidPresentationChallenge)?;
+
+        if proof.challenge() != Some(challenge) {
+            return Err(Error::InvalidPresentationChallenge);
+        }
+
+        if domain.is_some() && proof.domain() != domain {
+            return Err(Error::InvalidPresentationChallenge);
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the `Presentation` proof against the already resolved holder DID Document and
+    /// summarizes the outcome of each check.
+    fn verify_presentation<T, U>(
+        presentation: Presentation<T, U>,
+        holder: DocumentValidation,
+        credentials: Vec<CredentialValidation<U>>,
+    ) -> PresentationValidation<T, U>
+    where
+        T: Serialize,
+        U: Serialize,
+    {
         // Verify the presentation signature using the holders DID Document
-        let presentation_verified: bool = holder_doc.document.verify_data(&presentation).is_ok();
+        let presentation_verified: bool = holder.document.verify_data(&presentation).is_ok();
 
         // Check if all credentials are verified
         let credentials_verified: bool = credentials.iter().all(|credential| credential.verified);
 
         // The presentation is truly verified if all associated documents are verified
-        let verified: bool = holder_doc.verified && presentation_verified && credentials_verified;
+        let verified: bool = holder.verified && presentation_verified && credentials_verified;
 
-        Ok(PresentationValidation {
+        let mut summary: Vec<ValidationCheck> = vec![
+            ValidationCheck::Holder {
+                did: holder.did.to_string(),
+                verified: holder.verified,
+            },
+            ValidationCheck::PresentationProof {
+                verified: presentation_verified,
+            },
+        ];
+
+        summary.extend(
+            credentials
+                .iter()
+                .enumerate()
+                .map(|(index, credential)| ValidationCheck::Credential {
+                    index,
+                    id: credential.credential.id.as_ref().map(ToString::to_string),
+                    verified: credential.verified,
+                }),
+        );
+
+        PresentationValidation {
             presentation,
-            holder: holder_doc,
+            holder,
             credentials,
             verified,
-        })
+            summary,
+        }
     }
 
     /// Resolves the document from the Tangle, which performs checks on all signatures etc.
+    #[tracing::instrument(skip(self, did, client), fields(did = %did.as_ref()))]
     async fn validate_document(
         &self,
         did: impl AsRef<str>,
@@ -180,13 +556,472 @@ impl CredentialValidator {
         let did: RunnercDID = did.as_ref().parse()?;
 
         let document: RunnercDocument = client.read_document(&did).await?;
-        // // TODO: check if document is deactivated, does that matter?
 
-        Ok(DocumentValidation {
+        Ok(Self::check_document(did, document))
+    }
+
+    /// Checks the state of a resolved DID document.
+    ///
+    /// The `created` and `updated` timestamps and the `deactivated` status of the document are
+    /// recorded in the `metadata`. Deactivated documents are not verified and the reason is
+    /// recorded as well.
+    fn check_document(did: RunnercDID, document: RunnercDocument) -> DocumentValidation {
+        let mut metadata: Object = Object::new();
+        let verified: bool = !document.is_deactivated();
+
+        metadata.insert(
+            "created".to_owned(),
+            Value::from(document.created().to_rfc3339()),
+        );
+        metadata.insert(
+            "updated".to_owned(),
+            Value::from(document.updated().to_rfc3339()),
+        );
+        metadata.insert("deactivated".to_owned(), Value::from(!verified));
+
+        if !verified {
+            metadata.insert("reason".to_owned(), Value::from("deactivated"));
+        }
+
+        DocumentValidation {
             did,
             document,
+            metadata,
+            verified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use identity_core::crypto::KeyPair;
+    use identity_core::crypto::SignatureOptions;
+    use identity_core::json;
+    use identity_credential::credential::CredentialBuilder;
+    use identity_credential::credential::Refresh;
+    use identity_credential::credential::Subject;
+    use identity_credential::presentation::PresentationBuilder;
+    use tracing_test::traced_test;
+
+    use super::*;
+    use crate::runnerc::Client;
+    use crate::runnerc::MemoryBackend;
+    use crate::runnerc::Network;
+    use crate::utils::serve_memory_kvstore;
+
+    fn document_validation(document: &RunnercDocument) -> DocumentValidation {
+        DocumentValidation {
+            did: document.id().clone(),
+            document: document.clone(),
             metadata: Object::new(),
             verified: true,
+        }
+    }
+
+    fn credential(issuer: &RunnercDocument, id: &str) -> Credential {
+        let subject: Subject = Subject::from_json_value(json!({
+            "id": "did:example:subject",
+            "degree": "Bachelor of Science and Arts",
+        }))
+        .unwrap();
+
+        CredentialBuilder::default()
+            .id(Url::parse(id).unwrap())
+            .issuer(Url::parse(issuer.id().to_string()).unwrap())
+            .type_("UniversityDegreeCredential")
+            .subject(subject)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_check_over_nested_credential() {
+        let mut nested: Value = json!("leaf");
+        for _ in 0..ValidationLimits::DEFAULT_MAX_DEPTH {
+            nested = json!({ "nested": nested });
+        }
+
+        let data: String = json!({
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": "VerifiableCredential",
+            "issuer": "did:idns:issuer",
+            "issuanceDate": "2021-01-01T00:00:00Z",
+            "credentialSubject": nested,
+        })
+        .to_string();
+
+        let result: Result<CredentialValidation> =
+            CredentialValidator::new().check(&data, &ClientMap::new()).await;
+
+        assert!(matches!(
+            result,
+            Err(Error::ExceededMaxDepth(ValidationLimits::DEFAULT_MAX_DEPTH))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_presentation_oversized() {
+        let limits: ValidationLimits = ValidationLimits {
+            max_depth: ValidationLimits::DEFAULT_MAX_DEPTH,
+            max_elements: 100,
+        };
+
+        let credentials: Vec<Value> = (0..100)
+            .map(|index| json!({ "id": format!("https://example.edu/credentials/{}", index) }))
+            .collect();
+
+        let data: String = json!({
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": "VerifiablePresentation",
+            "holder": "did:idns:holder",
+            "verifiableCredential": credentials,
         })
+        .to_string();
+
+        let result: Result<PresentationValidation> = CredentialValidator::with_limits(limits)
+            .check_presentation(&data, &ClientMap::new())
+            .await;
+
+        assert!(matches!(result, Err(Error::ExceededMaxElements(100))));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_validate_document_spans() {
+        let did: RunnercDID =
+            RunnercDID::parse("did:idns:H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV").unwrap();
+
+        let mut client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+        client.kvstore_url = serve_memory_kvstore();
+
+        // The DID has not been published, so the KVStore lookup fails.
+        let result: Result<DocumentValidation> = CredentialValidator::new()
+            .validate_document(did.to_string(), &ClientMap::from_client(client))
+            .await;
+
+        assert!(matches!(result, Err(Error::DIDNotFound(_))));
+
+        let span: String = format!("validate_document{{did={}}}", did);
+        assert!(logs_contain(&span));
+        assert!(logs_contain(&format!("{}:read_document{{did={}}}", span, did)));
+    }
+
+    #[test]
+    fn test_limits_check() {
+        let limits: ValidationLimits = ValidationLimits {
+            max_depth: 3,
+            max_elements: 5,
+        };
+
+        assert!(limits.check(&json!({ "a": [1, 2] })).is_ok());
+        assert!(matches!(
+            limits.check(&json!({ "a": [[1]] })),
+            Err(Error::ExceededMaxDepth(3))
+        ));
+        assert!(matches!(
+            limits.check(&json!([1, 2, 3, 4, 5])),
+            Err(Error::ExceededMaxElements(5))
+        ));
+    }
+
+    fn expiring_credential(expiration_date: &str) -> CredentialBuilder {
+        CredentialBuilder::default()
+            .issuer(Url::parse("did:idns:issuer").unwrap())
+            .subject(Subject::from_json_value(json!({ "degree": "Bachelor" })).unwrap())
+            .issuance_date(Timestamp::parse("2010-01-01T00:00:00Z").unwrap())
+            .expiration_date(Timestamp::parse(expiration_date).unwrap())
+    }
+
+    #[test]
+    fn test_resolve_refresh() {
+        let now: Timestamp = Timestamp::parse("2021-01-01T00:00:00Z").unwrap();
+        let endpoint: Url = Url::parse("https://example.edu/refresh/3732").unwrap();
+
+        let credential: Credential = expiring_credential("2020-01-01T00:00:00Z")
+            .refresh(Refresh::new(
+                Url::parse("https://example.edu/other").unwrap(),
+                "OtherRefreshService".to_owned(),
+            ))
+            .refresh(Refresh::new(endpoint.clone(), "ManualRefreshService2018".to_owned()))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            CredentialValidator::resolve_refresh_at(&credential, now),
+            Some(&endpoint)
+        );
+        assert_eq!(
+            CredentialValidator::resolve_refresh(&credential),
+            Some(&endpoint)
+        );
+
+        // A credential that has not expired yet does not need to be refreshed.
+        let before: Timestamp = Timestamp::parse("2019-01-01T00:00:00Z").unwrap();
+        assert_eq!(
+            CredentialValidator::resolve_refresh_at(&credential, before),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_refresh_without_service() {
+        let now: Timestamp = Timestamp::parse("2021-01-01T00:00:00Z").unwrap();
+
+        let credential: Credential = expiring_credential("2020-01-01T00:00:00Z")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            CredentialValidator::resolve_refresh_at(&credential, now),
+            None
+        );
+    }
+
+    #[derive(Debug)]
+    struct RejectTerms(&'static str);
+
+    impl TermsEvaluator for RejectTerms {
+        fn evaluate(&self, policy: &Policy) -> bool {
+            !policy.types.iter().any(|type_| type_ == self.0)
+        }
+    }
+
+    #[test]
+    fn test_terms_evaluator_rejects_policy() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+        let mut credential: Credential = CredentialBuilder::default()
+            .id(Url::parse("https://example.edu/credentials/1").unwrap())
+            .issuer(Url::parse(document.id().to_string()).unwrap())
+            .type_("UniversityDegreeCredential")
+            .subject(Subject::from_json_value(json!({ "degree": "Bachelor" })).unwrap())
+            .policy(Policy::new("IssuerPolicy".to_owned()))
+            .policy(Policy::with_id::<_, ()>(
+                "HolderPolicy".to_owned(),
+                Url::parse("https://example.edu/policies/holder").unwrap(),
+            ))
+            .build()
+            .unwrap();
+
+        document
+            .sign_data(&mut credential, keypair.private(), "#sign-0")
+            .unwrap();
+
+        let accepted: CredentialValidation = CredentialValidator::new().verify_credential(
+            credential.clone(),
+            document_validation(&document),
+            BTreeMap::new(),
+        );
+
+        assert!(accepted.verified);
+        assert_eq!(accepted.failed_checks().count(), 0);
+
+        let rejected: CredentialValidation = CredentialValidator::new()
+            .with_terms_evaluator(RejectTerms("HolderPolicy"))
+            .verify_credential(credential, document_validation(&document), BTreeMap::new());
+
+        assert!(!rejected.verified);
+        assert!(rejected.summary.contains(&ValidationCheck::TermsOfUse {
+            index: 0,
+            id: None,
+            verified: true,
+        }));
+        assert_eq!(
+            rejected.failed_checks().collect::<Vec<_>>(),
+            vec![&ValidationCheck::TermsOfUse {
+                index: 1,
+                id: Some("https://example.edu/policies/holder".to_owned()),
+                verified: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_presentation_summary_lists_failing_credential() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+        let mut valid: Credential = credential(&document, "https://example.edu/credentials/1");
+        let mut tampered: Credential = credential(&document, "https://example.edu/credentials/2");
+
+        document
+            .sign_data(&mut valid, keypair.private(), "#sign-0")
+            .unwrap();
+        document
+            .sign_data(&mut tampered, keypair.private(), "#sign-0")
+            .unwrap();
+
+        // Modify the credential after it has been signed.
+        tampered.types.push("TamperedCredential".to_owned());
+
+        let credentials: Vec<CredentialValidation> = vec![valid.clone(), tampered.clone()]
+            .into_iter()
+            .map(|credential| {
+                CredentialValidator::new().verify_credential(
+                    credential,
+                    document_validation(&document),
+                    BTreeMap::new(),
+                )
+            })
+            .collect();
+
+        assert!(credentials[0].verified);
+        assert_eq!(credentials[0].failed_checks().count(), 0);
+        assert!(!credentials[1].verified);
+        assert_eq!(
+            credentials[1].failed_checks().collect::<Vec<_>>(),
+            vec![&ValidationCheck::CredentialProof { verified: false }]
+        );
+
+        let mut presentation: Presentation = PresentationBuilder::default()
+            .holder(Url::parse(document.id().to_string()).unwrap())
+            .credential(valid)
+            .credential(tampered)
+            .build()
+            .unwrap();
+
+        document
+            .sign_data(&mut presentation, keypair.private(), "#sign-0")
+            .unwrap();
+
+        let validation: PresentationValidation = CredentialValidator::verify_presentation(
+            presentation,
+            document_validation(&document),
+            credentials,
+        );
+
+        assert!(!validation.verified);
+        assert_eq!(validation.failed_credentials(), vec![1]);
+        assert_eq!(
+            validation.failed_checks().collect::<Vec<_>>(),
+            vec![&ValidationCheck::Credential {
+                index: 1,
+                id: Some("https://example.edu/credentials/2".to_owned()),
+                verified: false,
+            }]
+        );
+        assert!(validation.summary.contains(&ValidationCheck::PresentationProof { verified: true }));
+    }
+
+    #[test]
+    fn test_presentation_challenge() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+        let mut presentation: Presentation = PresentationBuilder::default()
+            .holder(Url::parse(document.id().to_string()).unwrap())
+            .build()
+            .unwrap();
+
+        // Without a proof there is nothing binding the presentation to a challenge.
+        assert!(matches!(
+            CredentialValidator::check_challenge(&presentation, "challenge", None),
+            Err(Error::InvalidPresentationChallenge)
+        ));
+
+        document
+            .signer(keypair.private())
+            .method("#sign-0")
+            .options(SignatureOptions::with_challenge("challenge").domain("example.com"))
+            .sign(&mut presentation)
+            .unwrap();
+
+        assert!(CredentialValidator::check_challenge(&presentation, "challenge", None).is_ok());
+        assert!(CredentialValidator::check_challenge(
+            &presentation,
+            "challenge",
+            Some("example.com")
+        )
+        .is_ok());
+        assert!(matches!(
+            CredentialValidator::check_challenge(&presentation, "other", None),
+            Err(Error::InvalidPresentationChallenge)
+        ));
+        assert!(matches!(
+            CredentialValidator::check_challenge(&presentation, "challenge", Some("example.org")),
+            Err(Error::InvalidPresentationChallenge)
+        ));
+
+        // The challenge is covered by the holder signature.
+        let validation: PresentationValidation = CredentialValidator::verify_presentation(
+            presentation.clone(),
+            document_validation(&document),
+            Vec::new(),
+        );
+        assert!(validation.verified);
+
+        let proof: Signature = presentation.proof().unwrap().clone();
+        let mut value: Value = serde_json::to_value(&proof).unwrap();
+        value["challenge"] = json!("other");
+        presentation.proof = Some(serde_json::from_value(value).unwrap());
+
+        let validation: PresentationValidation = CredentialValidator::verify_presentation(
+            presentation,
+            document_validation(&document),
+            Vec::new(),
+        );
+        assert!(!validation.verified);
+    }
+
+    #[test]
+    fn test_document_metadata() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+        document.set_created(Timestamp::parse("2021-01-01T00:00:00Z").unwrap());
+        document.set_updated(Timestamp::parse("2021-06-01T12:30:00Z").unwrap());
+
+        let issuer: DocumentValidation =
+            CredentialValidator::check_document(document.id().clone(), document);
+
+        assert_eq!(
+            issuer.metadata.get("created"),
+            Some(&json!("2021-01-01T00:00:00Z"))
+        );
+        assert_eq!(
+            issuer.metadata.get("updated"),
+            Some(&json!("2021-06-01T12:30:00Z"))
+        );
+        assert_eq!(issuer.metadata.get("deactivated"), Some(&json!(false)));
+    }
+
+    #[test]
+    fn test_deactivated_issuer() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+        let issuer: DocumentValidation =
+            CredentialValidator::check_document(document.id().clone(), document.clone());
+        assert!(issuer.verified);
+        assert_eq!(issuer.metadata.get("deactivated"), Some(&json!(false)));
+        assert_eq!(issuer.metadata.get("reason"), None);
+
+        document.set_deactivated(true);
+
+        let mut credential: Credential = credential(&document, "https://example.edu/credentials/1");
+        document
+            .sign_data(&mut credential, keypair.private(), "#sign-0")
+            .unwrap();
+
+        let issuer: DocumentValidation =
+            CredentialValidator::check_document(document.id().clone(), document.clone());
+        assert!(!issuer.verified);
+        assert_eq!(issuer.metadata.get("deactivated"), Some(&json!(true)));
+        assert_eq!(issuer.metadata.get("reason"), Some(&json!("deactivated")));
+
+        let validation: CredentialValidation =
+            CredentialValidator::new().verify_credential(credential, issuer, BTreeMap::new());
+
+        // The proof itself is valid, but the issuer is deactivated.
+        assert!(!validation.verified);
+        assert_eq!(
+            validation.failed_checks().collect::<Vec<_>>(),
+            vec![&ValidationCheck::Issuer {
+                did: document.id().to_string(),
+                verified: false,
+            }]
+        );
     }
 }
@@ -15,8 +15,12 @@ use serde;
 use serde::Deserialize;
 use serde::Serialize;
 
+use identity_core::crypto::KeyPair;
+use identity_core::crypto::KeyType;
 use identity_core::utils::decode_b58;
+use identity_core::utils::decode_multibase;
 use identity_core::utils::encode_b58;
+use identity_core::utils::encode_multibase;
 use identity_did::did::BaseDIDUrl;
 use identity_did::did::CoreDID;
 use identity_did::did::DIDError;
@@ -33,6 +37,15 @@ use crate::try_construct_did;
 // The hash size of BLAKE2b-256 (32-bytes)
 const BLAKE2B_256_LEN: usize = 32;
 
+// The method name of `did:key` identifiers.
+const DID_KEY_METHOD: &str = "key";
+
+// The varint-encoded multicodec prefix of Ed25519 public keys (`ed25519-pub`, 0xed).
+const ED25519_PUB_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+// The size of an Ed25519 public key (32-bytes)
+const ED25519_PUBLIC_KEY_LEN: usize = KeyType::Ed25519.public_key_len();
+
 /// A DID URL conforming to the Runnerc DID method specification.
 ///
 /// See [`DIDUrl`].
@@ -53,7 +66,10 @@ impl RunnercDID {
     /// The URL scheme for Decentralized Identifiers.
     pub const SCHEME: &'static str = CoreDID::SCHEME;
 
-    /// The Runnerc DID method name (`"Runnerc"`).
+    /// The Runnerc DID method name (`"idns"`).
+    ///
+    /// This is the only accepted method name. It must be lowercase; other names such as
+    /// `"runnerc"` or `"IDNS"` are rejected.
     pub const METHOD: &'static str = "idns";
 
     /// The default Tangle network (`"main"`).
@@ -97,19 +113,43 @@ impl RunnercDID {
     ///
     /// # Errors
     ///
-    /// Returns `Err` if the input is not a valid [`RunnercDID`].
+    /// Returns `Err` if the input is not a valid [`RunnercDID`], including a
+    /// [method name][RunnercDID::METHOD] that is not lowercase, e.g. `"did:IDNS:123"`.
     pub fn parse(input: impl AsRef<str>) -> Result<Self> {
         CoreDID::parse(input)
             .map_err(Into::into)
             .and_then(Self::try_from_owned)
     }
 
+    /// Parses each of the given `inputs` as an [`RunnercDID`], see [`RunnercDID::parse`].
+    ///
+    /// Returns the successfully parsed DIDs in input order and the errors of the remaining
+    /// inputs, paired with their index in `inputs`.
+    pub fn parse_many<T>(inputs: &[T]) -> (Vec<Self>, Vec<(usize, Error)>)
+    where
+        T: AsRef<str>,
+    {
+        let mut dids: Vec<Self> = Vec::with_capacity(inputs.len());
+        let mut errors: Vec<(usize, Error)> = Vec::new();
+
+        for (index, input) in inputs.iter().enumerate() {
+            match Self::parse(input) {
+                Ok(did) => dids.push(did),
+                Err(error) => errors.push((index, error)),
+            }
+        }
+
+        (dids, errors)
+    }
+
     /// Creates a new [`RunnercDID`] with a tag derived from the given `public` key.
     ///
     /// # Errors
     ///
-    /// Returns `Err` if the input does not form a valid [`RunnercDID`].
+    /// Returns `Err` if `public` is not a valid Ed25519 public key or the input does not form a
+    /// valid [`RunnercDID`].
     pub fn new(public: &[u8]) -> Result<Self> {
+        KeyType::Ed25519.check_public_key(public)?;
         try_construct_did!(public).map_err(Into::into)
     }
 
@@ -117,14 +157,98 @@ impl RunnercDID {
     ///
     /// # Errors
     ///
-    /// Returns `Err` if the input does not form a valid [`RunnercDID`] or the `network` is invalid.
+    /// Returns `Err` if `public` is not a valid Ed25519 public key, the input does not form a
+    /// valid [`RunnercDID`] or the `network` is invalid.
     /// See [`NetworkName`] for validation requirements.
     pub fn new_with_network(public: &[u8], network: impl TryInto<NetworkName>) -> Result<Self> {
+        KeyType::Ed25519.check_public_key(public)?;
         let network_name = network.try_into().map_err(|_| Error::InvalidNetworkName)?;
         try_construct_did!(public, network_name.as_ref()).map_err(Into::into)
     }
 
-    /// Checks if the given `DID` has a valid Runnerc DID `method` (i.e. `"runnerc"`).
+    /// Creates a new [`RunnercDID`] with a tag derived from the public key of the given `keypair`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the key type of `keypair` is not supported or the input does not form a
+    /// valid [`RunnercDID`].
+    pub fn from_keypair(keypair: &KeyPair) -> Result<Self> {
+        Self::check_key_type(keypair.type_())?;
+        Self::new(keypair.public().as_ref())
+    }
+
+    /// Creates a new [`RunnercDID`] from the public key of the given `keypair` and `network`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the key type of `keypair` is not supported, the input does not form a
+    /// valid [`RunnercDID`] or the `network` is invalid.
+    /// See [`NetworkName`] for validation requirements.
+    pub fn from_keypair_with_network(
+        keypair: &KeyPair,
+        network: impl TryInto<NetworkName>,
+    ) -> Result<Self> {
+        Self::check_key_type(keypair.type_())?;
+        Self::new_with_network(keypair.public().as_ref(), network)
+    }
+
+    /// Checks that keys of the given `key_type` can be used to derive a [`RunnercDID`].
+    fn check_key_type(key_type: KeyType) -> Result<()> {
+        match key_type {
+            KeyType::Ed25519 => Ok(()),
+        }
+    }
+
+    /// Creates the [`RunnercDID`] corresponding to the Ed25519 public key encoded in the given
+    /// [`did:key`](https://w3c-ccg.github.io/did-method-key/) identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the input is not a valid `did:key` or does not encode an Ed25519 key.
+    pub fn from_did_key(did_key: impl AsRef<str>) -> Result<Self> {
+        let did: CoreDID = CoreDID::parse(did_key)?;
+
+        if did.method() != DID_KEY_METHOD {
+            return Err(Error::InvalidDID(DIDError::InvalidMethodName));
+        }
+
+        let data: Vec<u8> = decode_multibase(did.method_id())?;
+
+        match data.strip_prefix(&ED25519_PUB_MULTICODEC[..]) {
+            Some(public) if public.len() == ED25519_PUBLIC_KEY_LEN => Self::new(public),
+            _ => Err(Error::InvalidDID(DIDError::InvalidMethodId)),
+        }
+    }
+
+    /// Returns the [`did:key`](https://w3c-ccg.github.io/did-method-key/) identifier of the
+    /// Ed25519 `public` key this [`RunnercDID`] was derived from.
+    ///
+    /// The key is required since the tag is a hash of the public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `public` is not an Ed25519 public key or does not match the tag.
+    pub fn to_did_key(&self, public: &[u8]) -> Result<CoreDID> {
+        KeyType::Ed25519.check_public_key(public)?;
+
+        if Self::encode_key(public) != self.tag() {
+            return Err(Error::InvalidDID(DIDError::InvalidMethodId));
+        }
+
+        let mut data: Vec<u8> = Vec::with_capacity(ED25519_PUB_MULTICODEC.len() + public.len());
+        data.extend_from_slice(&ED25519_PUB_MULTICODEC);
+        data.extend_from_slice(public);
+
+        CoreDID::parse(format!(
+            "{}:{}:{}",
+            Self::SCHEME,
+            DID_KEY_METHOD,
+            encode_multibase(&data, None)
+        ))
+        .map_err(Into::into)
+    }
+
+    /// Checks if the given `DID` has a valid Runnerc DID `method` (i.e. `"idns"`).
     ///
     /// # Errors
     ///
@@ -145,7 +269,8 @@ impl RunnercDID {
     pub fn check_method_id(did: &CoreDID) -> Result<()> {
         let segments: Vec<&str> = did.method_id().split(':').collect();
 
-        if segments.is_empty() || segments.len() > 3 {
+        // Only `<tag>` and `<network>:<tag>` are supported, see [`Segments`].
+        if segments.is_empty() || segments.len() > 2 {
             return Err(Error::InvalidDID(DIDError::InvalidMethodId));
         }
 
@@ -191,6 +316,9 @@ impl RunnercDID {
     }
 
     /// Returns the Tangle `network` of the `DID`, if it is valid.
+    ///
+    /// DIDs without a network segment belong to the [default network][Self::DEFAULT_NETWORK],
+    /// so `did:idns:<tag>` and `did:idns:main:<tag>` return the same [`Network`].
     pub fn network(&self) -> Result<Network> {
         Network::try_from_name(self.network_str().to_owned())
     }
@@ -205,17 +333,57 @@ impl RunnercDID {
         self.segments().tag()
     }
 
-    #[doc(hidden)]
+    /// Returns the [`Segments`] of the `DID` `method_id`.
     pub fn segments(&self) -> Segments<'_> {
         Segments(self.method_id())
     }
 
+    /// Returns the `DID` without the `did:` scheme and with an explicit network segment, for
+    /// display purposes.
+    ///
+    /// Unlike [`DID::authority`], the network is always included, so DIDs on the
+    /// [default network][Self::DEFAULT_NETWORK] display the same whether or not they were
+    /// normalized.
+    ///
+    /// E.g.
+    /// - `"did:idns:123" -> "idns:main:123"`
+    /// - `"did:idns:dev:123" -> "idns:dev:123"`
+    pub fn authority_only(&self) -> String {
+        format!("{}:{}:{}", Self::METHOD, self.network_str(), self.tag())
+    }
+
+    /// Returns the [`authority_only`][Self::authority_only] form of the `DID` with the tag
+    /// truncated to its first `prefix_len` and last `suffix_len` characters, for display
+    /// purposes.
+    ///
+    /// The tag is kept in full if it is not longer than the truncated form.
+    ///
+    /// E.g.
+    /// - `"did:idns:H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV"` with `(4, 3)` ->
+    ///   `"idns:main:H3C2…qPV"`
+    pub fn short(&self, prefix_len: usize, suffix_len: usize) -> String {
+        let tag: &str = self.tag();
+
+        // The tag is base58-encoded, so byte offsets are character boundaries.
+        if prefix_len.saturating_add(suffix_len) >= tag.len() {
+            return self.authority_only();
+        }
+
+        format!(
+            "{}:{}:{}\u{2026}{}",
+            Self::METHOD,
+            self.network_str(),
+            &tag[..prefix_len],
+            &tag[tag.len() - suffix_len..]
+        )
+    }
+
     /// Normalizes the DID `method_id` by removing the default network segment if present.
     ///
     /// E.g.
     /// - `"did:idns:main:123" -> "did:idns:123"` is normalized
     /// - `"did:idns:dev:123" -> "did:idns:dev:123"` is unchanged
-    fn normalize(mut did: CoreDID) -> CoreDID {
+    pub(crate) fn normalize(mut did: CoreDID) -> CoreDID {
         let segments: Segments<'_> = Segments(did.method_id());
 
         if segments.count() == 2 && segments.network() == Self::DEFAULT_NETWORK {
@@ -368,14 +536,27 @@ impl From<RunnercDID> for String {
 #[cfg(test)]
 mod tests {
     use identity_core::crypto::KeyPair;
+    use identity_core::Error as CoreError;
     use identity_did::did::CoreDID;
+    use identity_did::did::DIDError;
     use identity_did::did::DID;
 
     use crate::did::RunnercDID;
     use crate::did::RunnercDIDUrl;
+    use crate::error::Error;
 
     const TAG: &str = "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV";
 
+    // Public key of Test 1 of RFC 8032 and its `did:key` and `did:idns` forms.
+    const ED25519_PUBLIC_KEY: [u8; 32] = [
+        0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7,
+        0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64, 0x07, 0x3a,
+        0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25,
+        0xaf, 0x02, 0x1a, 0x68, 0xf7, 0x07, 0x51, 0x1a,
+    ];
+    const ED25519_DID_KEY: &str = "did:key:z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw";
+    const ED25519_DID_IDNS: &str = "did:idns:96Z5efhrC5cvEysan2v49R9YxjMvtSH2oLpCPdpyPB1U";
+
     #[test]
     fn test_parse_did_valid() {
         assert!(RunnercDID::parse(format!("did:idns:{}", TAG)).is_ok());
@@ -424,17 +605,18 @@ mod tests {
 
     #[test]
     fn test_parse_did_invalid() {
-        // A non-"runnerc" DID method is invalid.
+        // A non-"idns" DID method is invalid.
         assert!(RunnercDID::parse("did:foo::").is_err());
         // An empty DID method is invalid.
         assert!(RunnercDID::parse("did:::").is_err());
         assert!(RunnercDID::parse(format!("did::main:{}", TAG)).is_err());
-        // A non-"runnerc" DID method is invalid.
+        // A non-"idns" DID method is invalid.
         assert!(RunnercDID::parse("did:idns---::").is_err());
         // An empty `runnerc-specific-idstring` is invalid.
         assert!(RunnercDID::parse("did:idns:").is_err());
         // Too many components is invalid.
         assert!(RunnercDID::parse(format!("did:idns:custom:shard-1:random:{}", TAG)).is_err());
+        assert!(RunnercDID::parse(format!("did:idns:custom:shard:{}", TAG)).is_err());
         // Explicit empty network name is invalid (omitting it is still fine)
         assert!(RunnercDID::parse(format!("did:idns::{}", TAG)).is_err());
         // Invalid network name is invalid.
@@ -503,6 +685,27 @@ mod tests {
         assert_eq!(did.network_str(), "foo");
     }
 
+    #[test]
+    fn test_from_keypair() {
+        let key: KeyPair = KeyPair::new_ed25519().unwrap();
+
+        let did: RunnercDID = RunnercDID::from_keypair(&key).unwrap();
+        assert_eq!(did, RunnercDID::new(key.public().as_ref()).unwrap());
+        assert_eq!(did.network_str(), RunnercDID::DEFAULT_NETWORK);
+
+        let did: RunnercDID = RunnercDID::from_keypair_with_network(&key, "foo").unwrap();
+        assert_eq!(
+            did,
+            RunnercDID::new_with_network(key.public().as_ref(), "foo").unwrap()
+        );
+        assert_eq!(did.network_str(), "foo");
+
+        assert!(RunnercDID::from_keypair_with_network(&key, "").is_err());
+
+        // NOTE: Ed25519 is currently the only `KeyType`, so no unsupported key type can be
+        // constructed yet.
+    }
+
     #[test]
     fn test_normalize() {
         let key: KeyPair = KeyPair::new_ed25519().unwrap();
@@ -514,6 +717,24 @@ mod tests {
         assert_eq!(did1, did2);
     }
 
+    #[test]
+    fn test_method_name() {
+        let did: RunnercDID = RunnercDID::parse(format!("did:idns:{}", TAG)).unwrap();
+        assert_eq!(did.method(), RunnercDID::METHOD);
+
+        // The method name must be lowercase.
+        for method in ["IDNS", "Idns", "iDnS"] {
+            assert!(RunnercDID::parse(format!("did:{}:{}", method, TAG)).is_err());
+        }
+
+        // Other method names, including "runnerc", are rejected.
+        assert!(RunnercDID::parse(format!("did:runnerc:{}", TAG)).is_err());
+        assert!(RunnercDID::parse(format!("did:RUNNERC:{}", TAG)).is_err());
+        assert!(RunnercDID::parse(format!("did:idns1:{}", TAG)).is_err());
+        let did: CoreDID = format!("did:runnerc:{}", TAG).parse().unwrap();
+        assert!(RunnercDID::try_from_owned(did).is_err());
+    }
+
     #[test]
     fn test_setter() {
         let key: KeyPair = KeyPair::new_ed25519().unwrap();
@@ -528,4 +749,147 @@ mod tests {
         assert_eq!(did_url.query(), Some("diff=true"));
         assert_eq!(did_url.fragment(), Some("foo"));
     }
+
+    #[test]
+    fn test_did_key_round_trip() {
+        let public: &[u8] = &ED25519_PUBLIC_KEY;
+
+        let did: RunnercDID = RunnercDID::from_did_key(ED25519_DID_KEY).unwrap();
+        assert_eq!(did.as_str(), ED25519_DID_IDNS);
+        assert_eq!(did, RunnercDID::new(public).unwrap());
+
+        let did_key: CoreDID = did.to_did_key(public).unwrap();
+        assert_eq!(did_key.as_str(), ED25519_DID_KEY);
+        assert_eq!(RunnercDID::from_did_key(did_key.as_str()).unwrap(), did);
+    }
+
+    #[test]
+    fn test_new_invalid_key_length() {
+        let inputs: [&[u8]; 3] = [&[], &[0; 16], &[0; 33]];
+
+        for public in inputs {
+            assert!(matches!(
+                RunnercDID::new(public),
+                Err(Error::CoreError(CoreError::InvalidKeyLength(len, 32))) if len == public.len()
+            ));
+            assert!(matches!(
+                RunnercDID::new_with_network(public, "dev"),
+                Err(Error::CoreError(CoreError::InvalidKeyLength(_, 32)))
+            ));
+        }
+
+        assert!(RunnercDID::new(&[0; 32]).is_ok());
+    }
+
+    #[test]
+    fn test_did_key_invalid() {
+        let public: &[u8] = &ED25519_PUBLIC_KEY;
+        let other: KeyPair = KeyPair::new_ed25519().unwrap();
+        let did: RunnercDID = RunnercDID::parse(ED25519_DID_IDNS).unwrap();
+
+        // Not a `did:key`.
+        assert!(RunnercDID::from_did_key(ED25519_DID_IDNS).is_err());
+        // Not an Ed25519 multicodec prefix (X25519).
+        assert!(RunnercDID::from_did_key(
+            "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F"
+        )
+        .is_err());
+        // A key that did not derive the tag.
+        assert!(did.to_did_key(other.public().as_ref()).is_err());
+        assert!(did.to_did_key(&public[..31]).is_err());
+    }
+
+    #[test]
+    fn test_authority_only() {
+        let did: RunnercDID = RunnercDID::parse(format!("did:idns:{}", TAG)).unwrap();
+        assert_eq!(did.authority_only(), format!("idns:main:{}", TAG));
+
+        let did: RunnercDID = RunnercDID::parse(format!("did:idns:main:{}", TAG)).unwrap();
+        assert_eq!(did.authority_only(), format!("idns:main:{}", TAG));
+
+        let did: RunnercDID = RunnercDID::parse(format!("did:idns:dev:{}", TAG)).unwrap();
+        assert_eq!(did.authority_only(), format!("idns:dev:{}", TAG));
+    }
+
+    #[test]
+    fn test_short() {
+        let did: RunnercDID = RunnercDID::parse(format!("did:idns:{}", TAG)).unwrap();
+        assert_eq!(did.short(4, 3), "idns:main:H3C2\u{2026}qPV");
+        assert_eq!(did.short(0, 4), "idns:main:\u{2026}mqPV");
+        assert_eq!(did.short(4, 0), "idns:main:H3C2\u{2026}");
+
+        let did: RunnercDID = RunnercDID::parse(format!("did:idns:dev:{}", TAG)).unwrap();
+        assert_eq!(did.short(6, 6), "idns:dev:H3C2AV\u{2026}wXmqPV");
+
+        // The tag is not truncated if it is not longer than the truncated form.
+        assert_eq!(did.short(TAG.len(), 0), did.authority_only());
+        assert_eq!(did.short(TAG.len() / 2, TAG.len()), did.authority_only());
+        assert_eq!(did.short(usize::MAX, usize::MAX), did.authority_only());
+    }
+
+    #[test]
+    fn test_parse_many() {
+        let valid: String = format!("did:idns:{}", TAG);
+        let dev: String = format!("did:idns:dev:{}", TAG);
+        let inputs: [&str; 5] = [
+            &valid,
+            "did:example:123",
+            &dev,
+            "not a did",
+            "did:idns:main:shard:extra:0",
+        ];
+
+        let (dids, errors): (Vec<RunnercDID>, Vec<(usize, Error)>) =
+            RunnercDID::parse_many(&inputs);
+
+        assert_eq!(dids.len(), 2);
+        assert_eq!(dids[0].as_str(), valid);
+        assert_eq!(dids[1].as_str(), dev);
+
+        let indices: Vec<usize> = errors.iter().map(|(index, _)| *index).collect();
+
+        assert_eq!(indices, [1, 3, 4]);
+        assert!(matches!(
+            errors[0].1,
+            Error::InvalidDID(DIDError::InvalidMethodName)
+        ));
+
+        let (dids, errors): (Vec<RunnercDID>, Vec<(usize, Error)>) =
+            RunnercDID::parse_many::<&str>(&[]);
+        assert!(dids.is_empty());
+        assert!(errors.is_empty());
+    }
+
+    fn assert_round_trip(input: &str) {
+        if let Ok(did) = RunnercDID::parse(input) {
+            let reparsed: RunnercDID = RunnercDID::parse(did.to_string()).unwrap();
+            assert_eq!(reparsed, did);
+            assert_eq!(reparsed.to_string(), did.to_string());
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn test_fuzz_parse_no_panic(s in "\\PC*") {
+            assert_round_trip(&s);
+        }
+
+        #[test]
+        fn test_fuzz_parse_segments_no_panic(
+            s in r"did:idns:([a-zA-Z0-9\-]{0,8}:){0,3}[1-9A-HJ-NP-Za-km-z]{0,50}"
+        ) {
+            assert_round_trip(&s);
+        }
+
+        #[test]
+        fn test_fuzz_parse_valid_tag(
+            network in "(main:|dev:|[a-z0-9]{1,6}:)?",
+            key in proptest::collection::vec(proptest::num::u8::ANY, 0..64)
+        ) {
+            let input: String = format!("did:idns:{}{}", network, RunnercDID::encode_key(&key));
+            let did: RunnercDID = RunnercDID::parse(&input).unwrap();
+            assert_eq!(did.tag(), RunnercDID::encode_key(&key));
+            assert_round_trip(&input);
+        }
+    }
 }
@@ -1,55 +1,153 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use identity_did::did::DIDError;
+
 use crate::did::RunnercDID;
+use crate::error::Error;
+use crate::error::Result;
 
 macro_rules! get {
   (@network $this:expr) => {
     &$this.0[..get!(@head $this)]
   };
+  (@shard $this:expr) => {
+    &$this.0[get!(@head $this) + 1..get!(@tail $this)]
+  };
   (@tag $this:expr) => {
     &$this.0[get!(@tail $this) + 1..]
   };
   (@head $this:expr) => {
-    // unwrap is fine - we only operate on valid DIDs
+    // unwrap is fine - we only operate on valid method ids
     $this.0.find(':').unwrap()
   };
   (@tail $this:expr) => {
-    // unwrap is fine - we only operate on valid DIDs
+    // unwrap is fine - we only operate on valid method ids
     $this.0.rfind(':').unwrap()
   };
 }
 
-#[doc(hidden)]
+/// The colon-separated segments of a [`RunnercDID`] `method_id`.
+///
+/// A `method_id` has one of the following forms:
+/// - `<tag>` on the [default network][RunnercDID::DEFAULT_NETWORK],
+/// - `<network>:<tag>`,
+/// - `<network>:<shard>:<tag>`.
+///
+/// NOTE: [`RunnercDID`] currently only supports the first two forms.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Segments<'id>(pub(crate) &'id str);
 
 impl<'id> Segments<'id> {
-  pub fn is_default_network(&self) -> bool {
-    match self.count() {
-      1 => true,
-      2 => get!(@network self) == RunnercDID::DEFAULT_NETWORK,
-      _ => unreachable!("Segments::is_default_network called for invalid RunnercDID DID"),
+  /// Splits the given DID `method_id` into [`Segments`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if `method_id` does not consist of one to three non-empty segments.
+  pub fn new(method_id: &'id str) -> Result<Self> {
+    let count: usize = method_id.split(':').count();
+
+    if count > 3 || method_id.split(':').any(str::is_empty) {
+      return Err(Error::InvalidDID(DIDError::InvalidMethodId));
     }
+
+    Ok(Self(method_id))
   }
 
+  /// Returns `true` if the segments refer to the [default network][RunnercDID::DEFAULT_NETWORK].
+  pub fn is_default_network(&self) -> bool {
+    self.network() == RunnercDID::DEFAULT_NETWORK
+  }
+
+  /// Returns the network name, falling back to the [default network][RunnercDID::DEFAULT_NETWORK]
+  /// if the `method_id` consists of a tag only.
   pub fn network(&self) -> &'id str {
     match self.count() {
       1 => RunnercDID::DEFAULT_NETWORK,
-      2 => get!(@network self),
+      2 | 3 => get!(@network self),
       _ => unreachable!("Segments::network called for invalid RunnercDID DID"),
     }
   }
 
+  /// Returns the shard segment of a `<network>:<shard>:<tag>` method id, if present.
+  pub fn shard(&self) -> Option<&'id str> {
+    match self.count() {
+      1 | 2 => None,
+      3 => Some(get!(@shard self)),
+      _ => unreachable!("Segments::shard called for invalid RunnercDID DID"),
+    }
+  }
+
+  /// Returns the tag, which is always the last segment.
   pub fn tag(&self) -> &'id str {
     match self.count() {
       1 => self.0,
-      2 => get!(@tag self),
+      2 | 3 => get!(@tag self),
       _ => unreachable!("Segments::tag called for invalid RunnercDID DID"),
     }
   }
 
+  /// Returns the number of segments.
   pub fn count(&self) -> usize {
     self.0.split(':').count()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const TAG: &str = "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV";
+
+  #[test]
+  fn test_one_segment() {
+    let segments: Segments<'_> = Segments::new(TAG).unwrap();
+
+    assert_eq!(segments.count(), 1);
+    assert_eq!(segments.network(), RunnercDID::DEFAULT_NETWORK);
+    assert!(segments.is_default_network());
+    assert_eq!(segments.shard(), None);
+    assert_eq!(segments.tag(), TAG);
+  }
+
+  #[test]
+  fn test_two_segments() {
+    let method_id: String = format!("dev:{}", TAG);
+    let segments: Segments<'_> = Segments::new(&method_id).unwrap();
+
+    assert_eq!(segments.count(), 2);
+    assert_eq!(segments.network(), "dev");
+    assert!(!segments.is_default_network());
+    assert_eq!(segments.shard(), None);
+    assert_eq!(segments.tag(), TAG);
+  }
+
+  #[test]
+  fn test_three_segments() {
+    let method_id: String = format!("main:shard-1:{}", TAG);
+    let segments: Segments<'_> = Segments::new(&method_id).unwrap();
+
+    assert_eq!(segments.count(), 3);
+    assert_eq!(segments.network(), "main");
+    assert!(segments.is_default_network());
+    assert_eq!(segments.shard(), Some("shard-1"));
+    assert_eq!(segments.tag(), TAG);
+  }
+
+  #[test]
+  fn test_invalid_segments() {
+    assert!(Segments::new("").is_err());
+    assert!(Segments::new(&format!("dev::{}", TAG)).is_err());
+    assert!(Segments::new(&format!(":{}", TAG)).is_err());
+    assert!(Segments::new(&format!("main:shard:extra:{}", TAG)).is_err());
+  }
+
+  #[test]
+  fn test_did_segments() {
+    let did: RunnercDID = RunnercDID::parse(format!("did:idns:dev:{}", TAG)).unwrap();
+
+    assert_eq!(did.segments().network(), did.network_str());
+    assert_eq!(did.segments().tag(), did.tag());
+    assert_eq!(did.segments().shard(), None);
+  }
+}
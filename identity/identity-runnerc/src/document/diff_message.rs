@@ -25,6 +25,9 @@ use crate::runnerc::MessageId;
 use crate::runnerc::MessageIdExt;
 use crate::runnerc::TangleRef;
 
+/// The properties of a DID Document which change with every update and never conflict.
+const METADATA_PROPERTIES: &[&str] = &["updated", "previousMessageId", "proof"];
+
 /// Defines the difference between two DID [`Document`]s' JSON representations.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct DiffMessage {
@@ -76,6 +79,25 @@ impl DiffMessage {
     &self.previous_message_id
   }
 
+  /// Returns the Tangle message id of the DID Document diff.
+  ///
+  /// This is null until the diff has been published.
+  pub fn message_id(&self) -> &MessageId {
+    &self.message_id
+  }
+
+  /// Returns the names of the DID Document fields changed by the diff, e.g. `"service"`,
+  /// without merging it.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the diff cannot be deserialized.
+  pub fn changed_fields(&self) -> Result<Vec<String>> {
+    let data: DiffDocument = DiffDocument::from_json(&self.diff)?;
+
+    Ok(data.changed_fields())
+  }
+
   /// Returns a reference to the DID Document proof.
   pub fn proof(&self) -> Option<&Signature> {
     self.proof.as_ref()
@@ -90,6 +112,20 @@ impl DiffMessage {
 
     Ok(this.serde_into()?)
   }
+
+  /// Returns a new DID Document which is the result of merging `self` with the given Document,
+  /// failing if a field changed by `self` was also changed in `document` since `base`.
+  ///
+  /// The timestamp of the last update, the previous message id and the proof of the documents
+  /// are not considered conflicting. See [`CoreDocument::merge_strict`].
+  pub fn merge_strict(&self, base: &RunnercDocument, document: &RunnercDocument) -> Result<RunnercDocument> {
+    let data: DiffDocument = DiffDocument::from_json(&self.diff)?;
+    let base: CoreDocument = base.serde_into()?;
+    let core: CoreDocument = document.serde_into()?;
+    let this: CoreDocument = core.merge_strict_with_metadata(&base, data, METADATA_PROPERTIES)?;
+
+    Ok(this.serde_into()?)
+  }
 }
 
 impl TangleRef for DiffMessage {
@@ -135,3 +171,41 @@ impl SetSignature for DiffMessage {
 impl TryMethod for DiffMessage {
   const TYPE: MethodUriType = MethodUriType::Relative;
 }
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Url;
+  use identity_core::crypto::KeyPair;
+  use identity_did::did::CoreDIDUrl;
+  use identity_did::service::Service;
+  use identity_did::service::ServiceEndpoint;
+
+  use super::*;
+
+  #[test]
+  fn test_changed_fields() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let service: Service = Service::builder(Default::default())
+      .id(CoreDIDUrl::parse(format!("{}#linked-domain", document.id())).unwrap())
+      .type_("LinkedDomains")
+      .service_endpoint(ServiceEndpoint::One(Url::parse("https://example.com").unwrap()))
+      .build()
+      .unwrap();
+
+    let mut updated: RunnercDocument = document.clone();
+    assert!(updated.insert_service(service));
+
+    let message_id: MessageId = MessageId::new("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_owned());
+    let diff: DiffMessage = DiffMessage::new(&document, &updated, message_id.clone()).unwrap();
+
+    assert_eq!(diff.changed_fields().unwrap(), vec!["service".to_owned()]);
+    assert_eq!(diff.id(), document.id());
+    assert_eq!(diff.previous_message_id(), &message_id);
+    assert!(diff.message_id().is_null());
+
+    // The document itself is not modified.
+    assert!(document.service().is_empty());
+  }
+}
@@ -0,0 +1,26 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Timestamp;
+
+use crate::runnerc::MessageId;
+
+/// Metadata of an IOTA DID Document, following the shape of the `didDocumentMetadata` of a
+/// [DID resolution result](https://www.w3.org/TR/did-core/#did-document-metadata).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DocumentMetadata {
+  pub created: Timestamp,
+  pub updated: Timestamp,
+  #[serde(
+    rename = "previousMessageId",
+    default = "MessageId::null",
+    skip_serializing_if = "MessageId::is_null"
+  )]
+  pub previous_message_id: MessageId,
+  #[serde(default)]
+  pub deactivated: bool,
+}
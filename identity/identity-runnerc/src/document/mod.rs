@@ -2,13 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub use self::diff_message::DiffMessage;
+pub use self::document_metadata::DocumentMetadata;
 pub use self::runnerc_document::RunnercDocument;
 pub use self::runnerc_document::RunnercDocumentSigner;
 pub use self::runnerc_document::RunnercDocumentVerifier;
 pub use self::runnerc_verification_method::RunnercVerificationMethod;
 pub use self::properties::Properties;
+pub use self::signed_challenge::SignedChallenge;
 
 mod diff_message;
+mod document_metadata;
 mod runnerc_document;
 mod runnerc_verification_method;
 mod properties;
+mod signed_challenge;
@@ -21,6 +21,8 @@ pub struct Properties {
     skip_serializing_if = "MessageId::is_null"
   )]
   pub(crate) previous_message_id: MessageId,
+  #[serde(default, skip_serializing_if = "is_false")]
+  pub(crate) deactivated: bool,
   #[serde(flatten)]
   pub(crate) properties: Object,
 }
@@ -31,6 +33,7 @@ impl Properties {
       created: Timestamp::now_utc(),
       updated: Timestamp::now_utc(),
       previous_message_id: MessageId::null(),
+      deactivated: false,
       properties: Object::new(),
     }
   }
@@ -41,3 +44,7 @@ impl Default for Properties {
     Self::new()
   }
 }
+
+fn is_false(value: &bool) -> bool {
+  !value
+}
@@ -6,34 +6,42 @@ use core::convert::TryInto;
 use core::fmt::Debug;
 use core::fmt::Display;
 use core::fmt::Formatter;
+use core::mem::replace;
+use core::mem::take;
+use std::collections::HashSet;
 
 use serde;
 use serde::Deserialize;
 use serde::Serialize;
 
 use identity_core::common::Object;
+use identity_core::common::ObjectExt;
 use identity_core::common::Timestamp;
 use identity_core::common::Url;
+use identity_core::common::Value;
+use identity_core::convert::FromJson;
 use identity_core::convert::SerdeInto;
-use identity_core::crypto::Ed25519;
-use identity_core::crypto::JcsEd25519;
 use identity_core::crypto::KeyPair;
 use identity_core::crypto::PrivateKey;
 use identity_core::crypto::PublicKey;
 use identity_core::crypto::SetSignature;
 use identity_core::crypto::Signature;
-use identity_core::crypto::Signer;
+use identity_core::crypto::SignatureOptions;
 use identity_core::crypto::TrySignature;
 use identity_core::crypto::TrySignatureMut;
-use identity_core::crypto::Verifier;
+use identity_core::utils::split_pointer;
+use identity_did::did::CoreDID;
 use identity_did::did::CoreDIDUrl;
-use identity_did::did::DID;
 use identity_did::document::CoreDocument;
+use identity_did::service::DIDCommServiceEndpoint;
 use identity_did::service::Service;
 use identity_did::utils::OrderedSet;
+use identity_did::verifiable::sign_for_method_type;
+use identity_did::verifiable::verify_for_method_type;
 use identity_did::verifiable::DocumentSigner;
 use identity_did::verifiable::DocumentVerifier;
 use identity_did::verifiable::Properties as VerifiableProperties;
+use identity_did::verification::MethodDataEncoding;
 use identity_did::verification::MethodQuery;
 use identity_did::verification::MethodRef;
 use identity_did::verification::MethodRelationship;
@@ -46,8 +54,10 @@ use identity_did::verification::VerificationMethod;
 use crate::did::RunnercDID;
 use crate::did::RunnercDIDUrl;
 use crate::document::DiffMessage;
+use crate::document::DocumentMetadata;
 use crate::document::Properties as BaseProperties;
 use crate::document::RunnercVerificationMethod;
+use crate::document::SignedChallenge;
 use crate::error::Error;
 use crate::error::Result;
 use crate::runnerc::MessageId;
@@ -173,7 +183,7 @@ impl RunnercDocument {
 
     Ok(Self {
       document: document.serde_into()?,
-      message_id: MessageId::new(String::from(document.id().as_str())),
+      message_id: MessageId::null(),
     })
   }
 
@@ -187,10 +197,45 @@ impl RunnercDocument {
 
     Ok(Self {
       document: document.serde_into()?,
-      message_id: MessageId::new(String::from(document.id().as_str())),
+      message_id: MessageId::null(),
     })
   }
 
+  /// Converts a generic DID [`CoreDocument`] to an IOTA DID Document published with the
+  /// given `message_id`, e.g. the CID under which the document was retrieved from storage.
+  ///
+  /// Unlike [`RunnercDocument::try_from_core`], the [`TangleRef::message_id`] of the
+  /// document is set to `message_id` instead of [`MessageId::null`].
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the document is not a valid IOTA DID Document.
+  pub fn from_document_with_message_id(document: CoreDocument, message_id: MessageId) -> Result<Self> {
+    let mut document: Self = Self::try_from_core(document)?;
+    document.message_id = message_id;
+    Ok(document)
+  }
+
+  /// Deserializes a DID Document from the given JSON `json`, rejecting any fields that are not
+  /// part of the DID Document data model.
+  ///
+  /// Unlike [`FromJson::from_json`], unexpected fields are not silently collected into the
+  /// custom [`properties`](RunnercDocument::properties).
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::UnexpectedFields`] if the document contains unknown fields, or if the
+  /// document is otherwise invalid.
+  pub fn from_json_strict(json: &(impl AsRef<str> + ?Sized)) -> Result<Self> {
+    let document: Self = Self::from_json(json)?;
+
+    if !document.properties().is_empty() {
+      return Err(Error::UnexpectedFields(document.properties().keys().cloned().collect()));
+    }
+
+    Ok(document)
+  }
+
   /// Performs validation that a [`CoreDocument`] adheres to the IOTA spec.
   ///
   /// # Errors
@@ -228,6 +273,18 @@ impl RunnercDocument {
       }
     }
 
+    // Validate that no two embedded verification methods share a fragment, across the
+    // `verificationMethod` set and all verification relationships, since this would make
+    // method resolution ambiguous.
+    let mut fragments: HashSet<&str> = HashSet::new();
+    for method in document.methods() {
+      if let Some(fragment) = method.id().fragment() {
+        if !fragments.insert(fragment) {
+          return Err(Error::InvalidDoc(identity_did::Error::MethodAlreadyExists));
+        }
+      }
+    }
+
     Ok(())
   }
 
@@ -263,6 +320,28 @@ impl RunnercDocument {
     &mut self.document
   }
 
+  /// Applies `f` to a copy of the underlying [`CoreDocument`] and replaces the document with
+  /// the result if it is still a valid IOTA DID Document.
+  ///
+  /// This is a safe alternative to [`RunnercDocument::as_document_mut`]: if validation fails
+  /// `self` remains unmodified.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` if the updated document is not a valid IOTA DID Document.
+  pub fn update<F>(&mut self, f: F) -> Result<()>
+  where
+    F: FnOnce(&mut BaseDocument),
+  {
+    let mut document: BaseDocument = self.document.clone();
+    f(&mut document);
+
+    Self::validate_core_document(&document)?;
+    self.document = document;
+
+    Ok(())
+  }
+
   // ===========================================================================
   // Properties
   // ===========================================================================
@@ -281,6 +360,72 @@ impl RunnercDocument {
     unsafe { self.document.controller().map(|did| RunnercDID::new_unchecked_ref(did)) }
   }
 
+  /// Normalizes the document id, the controller and the DIDs of all verification methods and
+  /// method references to their canonical form, e.g. `did:idns:main:<tag>` to `did:idns:<tag>`.
+  ///
+  /// This allows logically equal documents to compare equal, regardless of whether their DIDs
+  /// specify the default network explicitly.
+  ///
+  /// NOTE: changing the DIDs invalidates the proof(s) of a signed document.
+  pub fn canonicalize_controllers(&mut self) {
+    let document: &mut BaseDocument = &mut self.document;
+
+    *document.id_mut() = RunnercDID::normalize(document.id().clone());
+
+    if let Some(controller) = document.controller_mut() {
+      *controller = RunnercDID::normalize(controller.clone());
+    }
+
+    let methods: OrderedSet<VerificationMethod> = take(document.verification_method_mut());
+    *document.verification_method_mut() = methods.into_vec().into_iter().map(canonicalize_method).collect();
+
+    canonicalize_references(document.authentication_mut());
+    canonicalize_references(document.assertion_method_mut());
+    canonicalize_references(document.key_agreement_mut());
+    canonicalize_references(document.capability_delegation_mut());
+    canonicalize_references(document.capability_invocation_mut());
+  }
+
+  /// Re-encodes the key data of all embedded verification methods as `publicKeyMultibase`.
+  ///
+  /// See [`RunnercDocument::normalize_key_data_with_encoding`].
+  pub fn normalize_key_data(&mut self) -> Result<()> {
+    self.normalize_key_data_with_encoding(MethodDataEncoding::Multibase)
+  }
+
+  /// Re-encodes the key data of all embedded verification methods with the given `encoding`.
+  ///
+  /// This allows documents to be compared and hashed consistently, regardless of whether their
+  /// methods use `publicKeyBase58` or `publicKeyMultibase` data. `publicKeyJwk` data is left
+  /// unchanged.
+  ///
+  /// NOTE: changing the key data invalidates the proof(s) of a signed document.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the key data of a method cannot be decoded, in which case `self` remains
+  /// unmodified.
+  pub fn normalize_key_data_with_encoding(&mut self, encoding: MethodDataEncoding) -> Result<()> {
+    let mut document: BaseDocument = self.document.clone();
+
+    let methods: OrderedSet<VerificationMethod> = take(document.verification_method_mut());
+    *document.verification_method_mut() = methods
+      .into_vec()
+      .into_iter()
+      .map(|method| normalize_method_key_data(method, encoding))
+      .collect::<Result<_>>()?;
+
+    normalize_references_key_data(document.authentication_mut(), encoding)?;
+    normalize_references_key_data(document.assertion_method_mut(), encoding)?;
+    normalize_references_key_data(document.key_agreement_mut(), encoding)?;
+    normalize_references_key_data(document.capability_delegation_mut(), encoding)?;
+    normalize_references_key_data(document.capability_invocation_mut(), encoding)?;
+
+    self.document = document;
+
+    Ok(())
+  }
+
   /// Returns a reference to the [`CoreDocument`] alsoKnownAs set.
   pub fn also_known_as(&self) -> &[Url] {
     self.document.also_known_as()
@@ -288,13 +433,20 @@ impl RunnercDocument {
 
   /// Returns the first [`RunnercVerificationMethod`] with a capability invocation relationship
   /// capable of signing this DID document.
+  ///
+  /// Capability invocation methods which cannot be resolved or have a key type unsupported for
+  /// signing documents, e.g. Merkle Key Collections, are skipped.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::MissingSigningKey`] if there is no such method.
   pub fn default_signing_method(&self) -> Result<&RunnercVerificationMethod> {
     self
       .as_document()
       .capability_invocation()
-      .head()
-      .map(|method_ref| self.as_document().resolve_method_ref(method_ref))
-      .flatten()
+      .iter()
+      .filter_map(|method_ref| self.as_document().resolve_method_ref(method_ref))
+      .find(|method| Self::check_signing_method(method).is_ok())
       .map(|method: &VerificationMethod<_>|
         // SAFETY: validity of methods checked in `RunnercVerificationMethod::check_validity`.
         unsafe { RunnercVerificationMethod::new_unchecked_ref(method) })
@@ -333,6 +485,27 @@ impl RunnercDocument {
     self.document.properties_mut().previous_message_id = value.into();
   }
 
+  /// Returns whether the DID document has been deactivated.
+  pub fn is_deactivated(&self) -> bool {
+    self.document.properties().deactivated
+  }
+
+  /// Sets whether the DID document has been deactivated.
+  pub fn set_deactivated(&mut self, value: bool) {
+    self.document.properties_mut().deactivated = value;
+  }
+
+  /// Returns the [`DocumentMetadata`] of the DID document, suitable for populating the
+  /// `didDocumentMetadata` of a DID resolution result.
+  pub fn metadata(&self) -> DocumentMetadata {
+    DocumentMetadata {
+      created: self.created(),
+      updated: self.updated(),
+      previous_message_id: self.previous_message_id().clone(),
+      deactivated: self.is_deactivated(),
+    }
+  }
+
   /// Returns a reference to the custom DID Document properties.
   pub fn properties(&self) -> &Object {
     &self.document.properties().properties
@@ -343,11 +516,87 @@ impl RunnercDocument {
     &mut self.document.properties_mut().properties
   }
 
+  /// Returns the custom property referenced by the JSON Pointer ([RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901))
+  /// `pointer`, e.g. `"/profile/tags/0"`.
+  ///
+  /// Returns `None` if `pointer` is malformed or does not reference a property. The empty pointer
+  /// is not supported since the custom properties are not a single [`Value`].
+  pub fn get_property(&self, pointer: &str) -> Option<&Value> {
+    self.properties().pointer(pointer)
+  }
+
+  /// Sets the custom property referenced by the JSON Pointer `pointer` to `value`, returning the
+  /// previous value, if any.
+  ///
+  /// Missing intermediate objects are created. An array element is replaced if the array index
+  /// exists; the index `-` or the array length appends a new element.
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidJsonPointer`] if `pointer` is malformed, traverses a value which is
+  /// neither an object nor an array, or references an array index out of bounds.
+  pub fn set_property(&mut self, pointer: &str, value: Value) -> Result<Option<Value>> {
+    let invalid = || Error::InvalidJsonPointer(pointer.to_owned());
+    let (key, mut path): (String, &str) = split_pointer(pointer).ok_or_else(invalid)?;
+
+    if path.is_empty() {
+      return Ok(self.properties_mut().insert(key, value));
+    }
+
+    let mut tokens: Vec<String> = Vec::new();
+    while let Some((token, rest)) = split_pointer(path) {
+      tokens.push(token);
+      path = rest;
+    }
+    let (last, parents): (&String, &[String]) = tokens.split_last().ok_or_else(invalid)?;
+
+    let mut target: &mut Value = self
+      .properties_mut()
+      .entry(key)
+      .or_insert_with(|| Value::Object(Default::default()));
+
+    for token in parents {
+      target = match target {
+        Value::Object(object) => object
+          .entry(token.as_str())
+          .or_insert_with(|| Value::Object(Default::default())),
+        Value::Array(array) => array_index(token)
+          .and_then(|index| array.get_mut(index))
+          .ok_or_else(invalid)?,
+        _ => return Err(invalid()),
+      };
+    }
+
+    match target {
+      Value::Object(object) => Ok(object.insert(last.clone(), value)),
+      Value::Array(array) if last == "-" => {
+        array.push(value);
+        Ok(None)
+      }
+      Value::Array(array) => match array_index(last) {
+        Some(index) if index < array.len() => Ok(Some(replace(&mut array[index], value))),
+        Some(index) if index == array.len() => {
+          array.push(value);
+          Ok(None)
+        }
+        _ => Err(invalid()),
+      },
+      _ => Err(invalid()),
+    }
+  }
+
   /// Returns a reference to the [`proof`](Signature), if one exists.
+  ///
+  /// If the document is co-signed, this is the first proof of the proof set.
   pub fn proof(&self) -> Option<&Signature> {
     self.document.proof()
   }
 
+  /// Returns all [`proofs`](Signature) of the document, e.g. of co-signing controllers.
+  pub fn proofs(&self) -> &[Signature] {
+    self.document.proofs()
+  }
+
   // ===========================================================================
   // Services
   // ===========================================================================
@@ -357,10 +606,28 @@ impl RunnercDocument {
     self.document.service()
   }
 
+  /// Returns all [`Service`]s in the document with the given `type_`, in document order.
+  pub fn services_by_type(&self, type_: &str) -> Vec<&Service> {
+    self
+      .document
+      .service()
+      .iter()
+      .filter(|service| service.type_() == type_)
+      .collect()
+  }
+
   /// Add a new [`Service`] to the document.
+  ///
+  /// Returns `false` if the service has no fragment, if a service with the same id already exists,
+  /// or if it is a [DIDComm messaging service][DIDCommServiceEndpoint::SERVICE_TYPE] whose endpoint
+  /// is not a valid [`DIDCommServiceEndpoint`].
   pub fn insert_service(&mut self, service: Service) -> bool {
     if service.id().fragment().is_none() {
       false
+    } else if service.type_() == DIDCommServiceEndpoint::SERVICE_TYPE
+      && DIDCommServiceEndpoint::check_structure(service.service_endpoint()).is_err()
+    {
+      false
     } else {
       self.document.service_mut().append(service)
     }
@@ -406,7 +673,8 @@ impl RunnercDocument {
   /// Attaches the relationship to the given method, if the method exists.
   ///
   /// Note: The method needs to be in the set of verification methods,
-  /// so it cannot be an embedded one.
+  /// so it cannot be an embedded one. See
+  /// [`RunnercDocument::attach_method_relationship_promoting`].
   pub fn attach_method_relationship(
     &mut self,
     did_url: RunnercDIDUrl,
@@ -416,6 +684,23 @@ impl RunnercDocument {
     Ok(self.document.attach_method_relationship(core_did_url, relationship)?)
   }
 
+  /// Attaches the relationship to the given method, if the method exists.
+  ///
+  /// An embedded method is first moved into the set of verification methods, leaving a
+  /// reference in the relationship it was embedded in.
+  pub fn attach_method_relationship_promoting(
+    &mut self,
+    did_url: RunnercDIDUrl,
+    relationship: MethodRelationship,
+  ) -> Result<bool> {
+    let core_did_url: CoreDIDUrl = CoreDIDUrl::from(did_url);
+    Ok(
+      self
+        .document
+        .attach_method_relationship_promoting(core_did_url, relationship)?,
+    )
+  }
+
   /// Detaches the given relationship from the given method, if the method exists.
   pub fn detach_method_relationship(
     &mut self,
@@ -488,8 +773,73 @@ impl RunnercDocument {
   where
     Q: Into<MethodQuery<'query>>,
   {
-    // Ensure signing method has a capability invocation verification relationship.
+    self.sign_self_with_scope(private_key, method_query, MethodScope::capability_invocation())
+  }
+
+  /// Signs this DID document with the verification method specified by `method_query`, which
+  /// must have the verification relationship specified by `scope`.
+  ///
+  /// Intended for legacy documents signed with e.g. an authentication method; prefer
+  /// [`RunnercDocument::sign_self`], which requires a capability invocation method.
+  ///
+  /// NOTE: [`RunnercDocument::verify_document`] only accepts capability invocation signatures.
+  ///
+  /// # Errors
+  ///
+  /// Fails if an unsupported verification method is used or the signature operation fails.
+  pub fn sign_self_with_scope<'query, Q>(
+    &mut self,
+    private_key: &PrivateKey,
+    method_query: Q,
+    scope: MethodScope,
+  ) -> Result<()>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    // Ensure signing method has the requested verification relationship.
     let method: &VerificationMethod<_> = self
+      .as_document()
+      .try_resolve_method_with_scope(method_query.into(), scope)?;
+    let _ = Self::check_signing_method(method)?;
+
+    // Specify the full method DID Url if the verification method id does not match the document id.
+    let method_did: &RunnercDID = RunnercDID::try_from_borrowed(method.id().did())?;
+    let method_id: String = if method_did == self.id() {
+      method.try_into_fragment()?
+    } else {
+      method.id().to_string()
+    };
+
+    // Sign document; Merkle Key Collections were rejected by `check_signing_method`.
+    let method: VerificationMethod = method.clone();
+    sign_for_method_type(&method, self, method_id, private_key, None, SignatureOptions::default())?;
+
+    Ok(())
+  }
+
+  /// Adds a signature created with the capability invocation method specified by `method_query`
+  /// of the `signer` DID document to the proof set of this DID document.
+  ///
+  /// This allows several controllers to co-sign a document. Unlike [`RunnercDocument::sign_self`],
+  /// existing proofs are retained; each proof is created over the document without any proofs.
+  ///
+  /// NOTE: does not validate whether `private_key` corresponds to the verification method.
+  /// See [`RunnercDocument::verify_document`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the method is not found in the `signer` document, an unsupported verification
+  /// method is used or the signature operation fails.
+  pub fn co_sign<'query, Q>(
+    &mut self,
+    signer: &RunnercDocument,
+    private_key: &PrivateKey,
+    method_query: Q,
+  ) -> Result<()>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    let method: &VerificationMethod<_> = signer
       .as_document()
       .try_resolve_method_with_scope(method_query.into(), MethodScope::capability_invocation())?;
     let _ = Self::check_signing_method(method)?;
@@ -502,16 +852,19 @@ impl RunnercDocument {
       method.id().to_string()
     };
 
-    // Sign document.
-    match method.key_type() {
-      MethodType::Ed25519VerificationKey2018 => {
-        JcsEd25519::<Ed25519>::create_signature(self, method_id, private_key.as_ref())?;
-      }
-      MethodType::MerkleKeyCollection2021 => {
-        // Merkle Key Collections cannot be used to sign documents.
-        return Err(Error::InvalidDocumentSigningMethodType);
-      }
-    }
+    let mut unsigned: RunnercDocument = self.clone();
+    unsigned.document.clear_proofs();
+    sign_for_method_type(
+      method,
+      &mut unsigned,
+      method_id,
+      private_key,
+      None,
+      SignatureOptions::default(),
+    )?;
+
+    let signature: Signature = unsigned.try_signature()?.clone();
+    self.document.push_proof(signature);
 
     Ok(())
   }
@@ -522,9 +875,12 @@ impl RunnercDocument {
     self.document.signer(private_key)
   }
 
-  /// Verifies that the signature on the DID document `signed` was generated by a valid method from
+  /// Verifies that a signature on the DID document `signed` was generated by a valid method from
   /// the `signer` DID document.
   ///
+  /// If `signed` is co-signed by several controllers, it suffices that any of its proofs was
+  /// generated by the `signer`.
+  ///
   /// # Errors
   ///
   /// Fails if:
@@ -532,26 +888,47 @@ impl RunnercDocument {
   /// - The method is not found in the `signer` document.
   /// - An unsupported verification method is used.
   /// - The signature verification operation fails.
+  ///
+  /// If no proof verifies, the error of the first proof is returned.
   pub fn verify_document(signed: &RunnercDocument, signer: &RunnercDocument) -> Result<()> {
-    // Ensure signing key has a capability invocation verification relationship.
     let signature: &Signature = signed.try_signature()?;
+
+    Self::verify_proof(signed, signer, signature).or_else(|error| {
+      let cosigned: bool = signed.proofs()[1..]
+        .iter()
+        .any(|signature| Self::verify_proof(signed, signer, signature).is_ok());
+
+      if cosigned {
+        Ok(())
+      } else {
+        Err(error)
+      }
+    })
+  }
+
+  /// Verifies the single `signature` of the proof set of `signed` with a method from the `signer`
+  /// DID document.
+  fn verify_proof(signed: &RunnercDocument, signer: &RunnercDocument, signature: &Signature) -> Result<()> {
+    // A relative method reference is relative to the signed document.
+    let method_url: CoreDIDUrl = signed.as_document().method_url(signature.verification_method())?;
+
+    // Ensure signing key has a capability invocation verification relationship.
     let method: &VerificationMethod<_> = signer
       .as_document()
-      .try_resolve_method_with_scope(signature, MethodScope::capability_invocation())?;
+      .try_resolve_method_with_scope(&method_url, MethodScope::capability_invocation())?;
+    RunnercDocumentVerifier::check_method_url(method, &method_url)?;
 
-    // Verify signature.
-    let public: PublicKey = method.key_data().try_decode()?.into();
-    match method.key_type() {
-      MethodType::Ed25519VerificationKey2018 => {
-        JcsEd25519::<Ed25519>::verify_signature(signed, public.as_ref())?;
-      }
-      MethodType::MerkleKeyCollection2021 => {
-        // Merkle Key Collections cannot be used to sign documents.
-        return Err(identity_did::error::Error::InvalidMethodType.into());
-      }
+    // Merkle Key Collections cannot be used to sign documents.
+    if let MethodType::MerkleKeyCollection2021 = method.key_type() {
+      return Err(identity_did::error::Error::InvalidMethodType.into());
     }
 
-    Ok(())
+    // Each proof is created over the document without the other proofs.
+    let mut single: RunnercDocument = signed.clone();
+    single.document.set_proof(signature.clone());
+
+    // Verify signature.
+    verify_for_method_type(method, &single).map_err(Into::into)
   }
 
   /// Verifies a self-signed signature on this DID document.
@@ -576,14 +953,17 @@ impl RunnercDocument {
 
     // Validate the hash of the public key matches the DID tag.
     let signature: &Signature = document.try_signature()?;
-    let method: &VerificationMethod<_> = document.as_document().try_resolve_method(signature)?;
-    let public: PublicKey = method.key_data().try_decode()?.into();
+    let method_url: CoreDIDUrl = document.as_document().method_url(signature.verification_method())?;
+    let method: &VerificationMethod<_> = document.as_document().try_resolve_method(&method_url)?;
+    RunnercDocumentVerifier::check_method_url(method, &method_url)?;
+    // SAFETY: Validity of verification methods checked in DID Document constructors.
+    let public: PublicKey = unsafe { RunnercVerificationMethod::new_unchecked_ref(method) }.public_key()?;
     if document.id().tag() != RunnercDID::encode_key(public.as_ref()) {
       return Err(Error::InvalidRootDocument);
     }
     log::debug!("====");
-    // Validate the document is signed correctly.
-    document.verify_self_signed()
+    // Validate the document is signed correctly by the key matching the DID tag.
+    Self::verify_proof(document, document, signature)
   }
 
   /// Creates a new [`RunnercDocumentVerifier`] that can be used to verify signatures
@@ -635,6 +1015,25 @@ impl RunnercDocument {
     self.verifier().verify(data).map_err(Into::into)
   }
 
+  /// Verifies a detached `signature` over the raw `data` bytes was created using the
+  /// verification method specified by `method_query`.
+  ///
+  /// This is useful when the proof is supplied externally rather than embedded in `data`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the method is not found, an unsupported verification method is used,
+  /// or the verification operation fails.
+  pub fn verify_external<'query, Q>(&self, data: &[u8], signature: &[u8], method_query: Q) -> Result<()>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    self
+      .verifier()
+      .verify_detached(data, signature, method_query)
+      .map_err(Into::into)
+  }
+
   /// Verifies the signature of the provided `data` was created using a verification method
   /// in this DID Document with the verification relationship specified by `scope`.
   ///
@@ -648,6 +1047,61 @@ impl RunnercDocument {
     self.verifier().verify_with_scope(data, scope).map_err(Into::into)
   }
 
+  /// Signs the verifier-issued `nonce` with the verification method specified by `method_query`
+  /// to prove control of this DID, e.g. when authenticating.
+  ///
+  /// The nonce is bound to the proof as its `challenge`, so the signed challenge cannot be
+  /// replayed for another nonce.
+  ///
+  /// NOTE: does not validate whether `private_key` corresponds to the verification method.
+  /// See [`RunnercDocument::verify_challenge`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if an unsupported verification method is used or the signature operation fails.
+  pub fn sign_challenge<'query, 's: 'query, Q>(
+    &'s self,
+    nonce: impl Into<String>,
+    private_key: &'query PrivateKey,
+    method_query: Q,
+  ) -> Result<SignedChallenge>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    let nonce: String = nonce.into();
+    let mut challenge: SignedChallenge = SignedChallenge::new(self.id().clone(), nonce.clone());
+
+    self
+      .signer(private_key)
+      .method(method_query)
+      .options(SignatureOptions::with_challenge(nonce))
+      .sign(&mut challenge)?;
+
+    Ok(challenge)
+  }
+
+  /// Verifies that `signed` answers the challenge `expected_nonce` and was created using a
+  /// verification method in this DID Document with the verification relationship specified by
+  /// `scope`, usually [`MethodScope::authentication`].
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidChallenge`] if `signed` is for another DID or nonce, or if the
+  /// verification operation fails.
+  pub fn verify_challenge(&self, signed: &SignedChallenge, expected_nonce: &str, scope: MethodScope) -> Result<()> {
+    if signed.id() != self.id() || signed.nonce() != expected_nonce {
+      return Err(Error::InvalidChallenge);
+    }
+
+    let proof: &Signature = signed.try_signature()?;
+
+    if proof.challenge() != Some(expected_nonce) {
+      return Err(Error::InvalidChallenge);
+    }
+
+    self.verify_data_with_scope(signed, scope)
+  }
+
   // ===========================================================================
   // Diffs
   // ===========================================================================
@@ -669,16 +1123,49 @@ impl RunnercDocument {
     private_key: &'query PrivateKey,
     method_query: Q,
   ) -> Result<DiffMessage>
+  where
+    Q: Into<MethodQuery<'query>>,
+  {
+    self.diff_with_scope(
+      other,
+      message_id,
+      private_key,
+      method_query,
+      MethodScope::capability_invocation(),
+    )
+  }
+
+  /// Creates a `DiffMessage` representing the changes between `self` and `other`, signed by
+  /// the method specified by `method_query`, which must have the verification relationship
+  /// specified by `scope`.
+  ///
+  /// Intended for diffs of data authorized by other relationships; prefer
+  /// [`RunnercDocument::diff`] for document updates.
+  ///
+  /// NOTE: [`RunnercDocument::merge`] only accepts capability invocation signatures, use
+  /// [`RunnercDocument::verify_diff_with_scope`] to verify the returned diff.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the diff operation or signature operation fails.
+  pub fn diff_with_scope<'query, 's: 'query, Q>(
+    &'query self,
+    other: &Self,
+    message_id: MessageId,
+    private_key: &'query PrivateKey,
+    method_query: Q,
+    scope: MethodScope,
+  ) -> Result<DiffMessage>
   where
     Q: Into<MethodQuery<'query>>,
   {
     let mut diff: DiffMessage = DiffMessage::new(self, other, message_id)?;
 
-    // Ensure the signing method has a capability invocation verification relationship.
+    // Ensure the signing method has the requested verification relationship.
     let method_query = method_query.into();
     let _ = self
       .as_document()
-      .try_resolve_method_with_scope(method_query.clone(), MethodScope::capability_invocation())?;
+      .try_resolve_method_with_scope(method_query.clone(), scope)?;
 
     self.sign_data(&mut diff, private_key, method_query)?;
 
@@ -692,7 +1179,17 @@ impl RunnercDocument {
   ///
   /// Fails if an unsupported verification method is used or the verification operation fails.
   pub fn verify_diff(&self, diff: &DiffMessage) -> Result<()> {
-    self.verify_data_with_scope(diff, MethodScope::capability_invocation())
+    self.verify_diff_with_scope(diff, MethodScope::capability_invocation())
+  }
+
+  /// Verifies the signature of the `diff` was created using a method in this DID Document
+  /// with the verification relationship specified by `scope`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if an unsupported verification method is used or the verification operation fails.
+  pub fn verify_diff_with_scope(&self, diff: &DiffMessage, scope: MethodScope) -> Result<()> {
+    self.verify_data_with_scope(diff, scope)
   }
 
   /// Verifies a `DiffMessage` signature and merges the changes into `self`.
@@ -706,9 +1203,43 @@ impl RunnercDocument {
   ///
   /// Fails if the merge operation or signature operation fails.
   pub fn merge(&mut self, diff: &DiffMessage) -> Result<()> {
+    *self = self.merge_preview(diff)?;
+
+    Ok(())
+  }
+
+  /// Verifies a `DiffMessage` signature and returns the document resulting from merging the
+  /// changes into `self`, without modifying `self`.
+  ///
+  /// This allows inspecting the outcome of a diff before committing it with
+  /// [`RunnercDocument::merge`].
+  ///
+  /// # Errors
+  ///
+  /// Fails if the merge operation or signature operation fails.
+  pub fn merge_preview(&self, diff: &DiffMessage) -> Result<RunnercDocument> {
+    self.verify_diff(diff)?;
+
+    diff.merge(self)
+  }
+
+  /// Verifies a `DiffMessage` signature and merges the changes into `self`, rejecting the diff
+  /// if it touches a field that was changed locally since `base`, the document the diff was
+  /// created from.
+  ///
+  /// If merging fails `self` remains unmodified, otherwise `self` represents
+  /// the merged document state.
+  ///
+  /// See [`RunnercDocument::merge`].
+  ///
+  /// # Errors
+  ///
+  /// Fails with a [`MergeConflict`][identity_core::diff::Error::MergeConflict] if the diff
+  /// conflicts with local changes, or if the merge operation or signature operation fails.
+  pub fn merge_strict(&mut self, base: &RunnercDocument, diff: &DiffMessage) -> Result<()> {
     self.verify_diff(diff)?;
 
-    *self = diff.merge(self)?;
+    *self = diff.merge_strict(base, self)?;
 
     Ok(())
   }
@@ -816,3 +1347,784 @@ impl TangleRef for RunnercDocument {
     RunnercDocument::set_previous_message_id(self, message_id)
   }
 }
+
+/// Parses a JSON Pointer array index, which must not have leading zeros.
+fn array_index(token: &str) -> Option<usize> {
+  if (token.len() > 1 && token.starts_with('0')) || !token.bytes().all(|byte| byte.is_ascii_digit()) {
+    return None;
+  }
+
+  token.parse().ok()
+}
+
+fn canonicalize_references(references: &mut OrderedSet<MethodRef>) {
+  *references = take(references)
+    .into_vec()
+    .into_iter()
+    .map(|reference| match reference {
+      MethodRef::Embed(method) => MethodRef::Embed(canonicalize_method(method)),
+      MethodRef::Refer(did_url) => MethodRef::Refer(canonicalize_did_url(did_url)),
+    })
+    .collect();
+}
+
+fn canonicalize_method(mut method: VerificationMethod) -> VerificationMethod {
+  *method.id_mut() = canonicalize_did_url(method.id().clone());
+  *method.controller_mut() = RunnercDID::normalize(method.controller().clone());
+  method
+}
+
+fn canonicalize_did_url(did_url: CoreDIDUrl) -> CoreDIDUrl {
+  let did: CoreDID = RunnercDID::normalize(did_url.did().clone());
+  CoreDIDUrl::new(did, Some(did_url.url().clone()))
+}
+
+fn normalize_references_key_data(references: &mut OrderedSet<MethodRef>, encoding: MethodDataEncoding) -> Result<()> {
+  *references = take(references)
+    .into_vec()
+    .into_iter()
+    .map(|reference| match reference {
+      MethodRef::Embed(method) => normalize_method_key_data(method, encoding).map(MethodRef::Embed),
+      MethodRef::Refer(did_url) => Ok(MethodRef::Refer(did_url)),
+    })
+    .collect::<Result<_>>()?;
+
+  Ok(())
+}
+
+fn normalize_method_key_data(
+  mut method: VerificationMethod,
+  encoding: MethodDataEncoding,
+) -> Result<VerificationMethod> {
+  method.key_data_mut().normalize(encoding)?;
+  Ok(method)
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Value;
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use identity_core::crypto::merkle_key::Sha256;
+  use identity_core::crypto::Ed25519;
+  use identity_core::crypto::JcsEd25519;
+  use identity_core::crypto::KeyCollection;
+  use identity_core::crypto::KeyPair;
+  use identity_core::crypto::Sign;
+  use identity_core::crypto::Signer;
+  use identity_core::json;
+  use identity_did::did::DID;
+  use identity_did::service::ServiceEndpoint;
+  use identity_did::verification::MethodData;
+
+  use super::*;
+
+  const DATA: &[u8] = b"detached payload";
+
+  #[test]
+  fn test_verify_external() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let signature: [u8; 64] = Ed25519::sign(DATA, keypair.private().as_ref()).unwrap();
+
+    assert!(document.verify_external(DATA, &signature, "#sign-0").is_ok());
+    assert!(document.verify_external(b"tampered", &signature, "#sign-0").is_err());
+    assert!(document.verify_external(DATA, &signature, "#missing").is_err());
+  }
+
+  #[test]
+  fn test_deserialize_duplicate_methods() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let mut json: serde_json::Value = document.to_json_value().unwrap();
+    assert!(RunnercDocument::from_json_value(json.clone()).is_ok());
+
+    let method: serde_json::Value = json["capabilityInvocation"][0].clone();
+    assert!(method["id"].as_str().unwrap().ends_with("#sign-0"));
+
+    // Two `#sign-0` methods in the same set.
+    json["verificationMethod"] = json!([method.clone(), method.clone()]);
+    assert!(RunnercDocument::from_json_value(json.clone()).is_err());
+
+    // A `#sign-0` method in `verificationMethod` shadowing the embedded capability invocation.
+    json["verificationMethod"] = json!([method]);
+    assert!(RunnercDocument::from_json_value(json).is_err());
+  }
+
+  #[test]
+  fn test_attach_method_relationship_promoting() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let auth_keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: RunnercVerificationMethod =
+      RunnercVerificationMethod::from_did(document.id().clone(), auth_keypair.type_(), auth_keypair.public(), "auth")
+        .unwrap();
+    let method_url: RunnercDIDUrl = method.id();
+    document.insert_method(method, MethodScope::authentication()).unwrap();
+
+    assert!(document
+      .attach_method_relationship(method_url.clone(), MethodRelationship::AssertionMethod)
+      .is_err());
+    assert!(document
+      .attach_method_relationship_promoting(method_url, MethodRelationship::AssertionMethod)
+      .unwrap());
+
+    assert!(document.resolve_method("#auth").is_some());
+    assert!(document.default_signing_method().is_ok());
+
+    // The document stays valid and survives a round-trip.
+    document.sign_self(keypair.private(), "#sign-0").unwrap();
+    assert!(RunnercDocument::verify_document(&document, &document).is_ok());
+
+    let json: String = document.to_json().unwrap();
+    assert_eq!(RunnercDocument::from_json(&json).unwrap(), document);
+  }
+
+  #[test]
+  fn test_sign_self_with_scope() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let auth_keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: RunnercVerificationMethod =
+      RunnercVerificationMethod::from_did(document.id().clone(), auth_keypair.type_(), auth_keypair.public(), "auth")
+        .unwrap();
+    document.insert_method(method, MethodScope::authentication()).unwrap();
+
+    // The default still requires a capability invocation method.
+    assert!(document.sign_self(auth_keypair.private(), "#auth").is_err());
+    assert!(document
+      .sign_self_with_scope(auth_keypair.private(), "#auth", MethodScope::capability_invocation())
+      .is_err());
+
+    document
+      .sign_self_with_scope(auth_keypair.private(), "#auth", MethodScope::authentication())
+      .unwrap();
+
+    assert!(document.signature().is_some());
+    assert!(document
+      .verify_data_with_scope(&document, MethodScope::authentication())
+      .is_ok());
+    assert!(RunnercDocument::verify_document(&document, &document).is_err());
+
+    // Capability invocation methods can still be used explicitly.
+    document
+      .sign_self_with_scope(keypair.private(), "#sign-0", MethodScope::capability_invocation())
+      .unwrap();
+    assert!(RunnercDocument::verify_document(&document, &document).is_ok());
+  }
+
+  #[test]
+  fn test_co_sign() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let keypair_a: KeyPair = KeyPair::new_ed25519().unwrap();
+    let controller_a: RunnercDocument = RunnercDocument::new(&keypair_a).unwrap();
+    let keypair_b: KeyPair = KeyPair::new_ed25519().unwrap();
+    let controller_b: RunnercDocument = RunnercDocument::new(&keypair_b).unwrap();
+
+    document.co_sign(&controller_a, keypair_a.private(), "#sign-0").unwrap();
+    document.co_sign(&controller_b, keypair_b.private(), "#sign-0").unwrap();
+
+    assert_eq!(document.proofs().len(), 2);
+    assert!(document.proofs()[0]
+      .verification_method()
+      .starts_with(&controller_a.id().to_string()));
+
+    // Each controller's proof verifies on its own.
+    assert!(RunnercDocument::verify_document(&document, &controller_a).is_ok());
+    assert!(RunnercDocument::verify_document(&document, &controller_b).is_ok());
+    assert!(document.verify_self_signed().is_err());
+
+    // The proof set survives a round-trip.
+    let json: Value = document.to_json_value().unwrap();
+    assert!(json["proof"].is_array());
+    let document: RunnercDocument = RunnercDocument::from_json_value(json).unwrap();
+    assert!(RunnercDocument::verify_document(&document, &controller_b).is_ok());
+
+    // Modifying the document invalidates all proofs.
+    let mut tampered: RunnercDocument = document.clone();
+    tampered.properties_mut().insert("foo".into(), Value::from("bar"));
+    assert!(RunnercDocument::verify_document(&tampered, &controller_a).is_err());
+    assert!(RunnercDocument::verify_document(&tampered, &controller_b).is_err());
+
+    // Signing replaces the proof set.
+    document.sign_self(keypair.private(), "#sign-0").unwrap();
+    assert_eq!(document.proofs().len(), 1);
+    assert!(document.verify_self_signed().is_ok());
+    assert!(RunnercDocument::verify_document(&document, &controller_a).is_err());
+  }
+
+  #[test]
+  fn test_challenge() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let auth_keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: RunnercVerificationMethod = RunnercVerificationMethod::from_did(
+      document.id().clone(),
+      auth_keypair.type_(),
+      auth_keypair.public(),
+      "auth",
+    )
+    .unwrap();
+    document.insert_method(method, MethodScope::authentication()).unwrap();
+
+    let signed: SignedChallenge = document
+      .sign_challenge("nonce-1", auth_keypair.private(), "#auth")
+      .unwrap();
+    assert_eq!(signed.id(), document.id());
+    assert_eq!(signed.nonce(), "nonce-1");
+    assert_eq!(signed.proof().unwrap().challenge(), Some("nonce-1"));
+
+    assert!(document
+      .verify_challenge(&signed, "nonce-1", MethodScope::authentication())
+      .is_ok());
+
+    // The nonce must match.
+    assert!(matches!(
+      document.verify_challenge(&signed, "nonce-2", MethodScope::authentication()),
+      Err(Error::InvalidChallenge)
+    ));
+
+    // The nonce is bound to the proof.
+    let mut replayed: SignedChallenge = signed.clone();
+    replayed.nonce = "nonce-2".to_owned();
+    assert!(matches!(
+      document.verify_challenge(&replayed, "nonce-2", MethodScope::authentication()),
+      Err(Error::InvalidChallenge)
+    ));
+
+    // The method must have the requested verification relationship.
+    let signed: SignedChallenge = document
+      .sign_challenge("nonce-1", keypair.private(), "#sign-0")
+      .unwrap();
+    assert!(document
+      .verify_challenge(&signed, "nonce-1", MethodScope::authentication())
+      .is_err());
+    assert!(document
+      .verify_challenge(&signed, "nonce-1", MethodScope::capability_invocation())
+      .is_ok());
+
+    // The challenge must be for this DID.
+    let other: RunnercDocument = RunnercDocument::new(&KeyPair::new_ed25519().unwrap()).unwrap();
+    assert!(matches!(
+      other.verify_challenge(&signed, "nonce-1", MethodScope::capability_invocation()),
+      Err(Error::InvalidChallenge)
+    ));
+  }
+
+  #[test]
+  fn test_verify_method_reference_forms() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    let other: RunnercDocument = RunnercDocument::new(&KeyPair::new_ed25519().unwrap()).unwrap();
+
+    // Methods of the document itself are referenced by fragment.
+    document.sign_self(keypair.private(), "#sign-0").unwrap();
+    assert_eq!(document.try_signature().unwrap().verification_method(), "#sign-0");
+    assert!(document.verify_self_signed().is_ok());
+    assert!(RunnercDocument::verify_root_document(&document).is_ok());
+
+    // The equivalent absolute DID Url refers to the same method.
+    let method_url: RunnercDIDUrl = document.id().to_url().join("#sign-0").unwrap();
+    JcsEd25519::<Ed25519>::create_signature(&mut document, method_url.to_string(), keypair.private().as_ref()).unwrap();
+    assert!(document.verify_self_signed().is_ok());
+    assert!(RunnercDocument::verify_root_document(&document).is_ok());
+
+    // A method of another DID with the same fragment does not.
+    let method_url: RunnercDIDUrl = other.id().to_url().join("#sign-0").unwrap();
+    JcsEd25519::<Ed25519>::create_signature(&mut document, method_url.to_string(), keypair.private().as_ref()).unwrap();
+    assert!(document.verify_self_signed().is_err());
+    assert!(RunnercDocument::verify_root_document(&document).is_err());
+  }
+
+  #[test]
+  fn test_canonicalize_controllers() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    let controller: RunnercDID = RunnercDID::from_keypair(&KeyPair::new_ed25519().unwrap()).unwrap();
+
+    let mut json: serde_json::Value = document.to_json_value().unwrap();
+    json["controller"] = json!(controller.to_string());
+    let implicit: RunnercDocument = RunnercDocument::from_json_value(json.clone()).unwrap();
+
+    json["controller"] = json!(format!("did:idns:main:{}", controller.tag()));
+    json["capabilityInvocation"][0]["controller"] = json!(format!("did:idns:main:{}", document.id().tag()));
+    let mut explicit: RunnercDocument = RunnercDocument::from_json_value(json).unwrap();
+    assert_ne!(explicit, implicit);
+
+    explicit.canonicalize_controllers();
+    assert_eq!(explicit, implicit);
+    assert_eq!(explicit.controller(), Some(&controller));
+
+    // Canonical documents are unchanged.
+    let mut canonical: RunnercDocument = implicit.clone();
+    canonical.canonicalize_controllers();
+    assert_eq!(canonical, implicit);
+  }
+
+  #[test]
+  fn test_default_signing_method_fallback() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let collection: KeyCollection = KeyCollection::new_ed25519(4).unwrap();
+    let merkle_key: RunnercVerificationMethod =
+      RunnercVerificationMethod::create_merkle_key::<Sha256>(document.id().clone(), &collection, "merkle").unwrap();
+    let merkle_key_url: RunnercDIDUrl = merkle_key.id();
+    document
+      .insert_method(merkle_key, MethodScope::capability_invocation())
+      .unwrap();
+
+    let new_keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: RunnercVerificationMethod = RunnercVerificationMethod::from_did(
+      document.id().clone(),
+      new_keypair.type_(),
+      new_keypair.public(),
+      "sign-1",
+    )
+    .unwrap();
+    let method_url: RunnercDIDUrl = method.id();
+    document
+      .insert_method(method, MethodScope::capability_invocation())
+      .unwrap();
+
+    // Remove the default method, leaving the Merkle Key Collection at the head.
+    let default_url: RunnercDIDUrl = document.default_signing_method().unwrap().id();
+    document.remove_method(default_url).unwrap();
+    assert_eq!(
+      document.as_document().capability_invocation().head().unwrap().id(),
+      &CoreDIDUrl::from(merkle_key_url)
+    );
+
+    assert_eq!(document.default_signing_method().unwrap().id(), method_url);
+
+    document.sign_self(new_keypair.private(), "#sign-1").unwrap();
+    assert!(document.verify_self_signed().is_ok());
+
+    // Fails without a usable capability invocation method.
+    document.remove_method(method_url).unwrap();
+    assert!(matches!(
+      document.default_signing_method(),
+      Err(Error::MissingSigningKey)
+    ));
+  }
+
+  #[test]
+  fn test_property_pointer() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    // Intermediate objects are created.
+    assert_eq!(document.set_property("/profile/name", json!("Alice")).unwrap(), None);
+    assert_eq!(document.get_property("/profile/name"), Some(&json!("Alice")));
+    assert_eq!(document.get_property("/profile"), Some(&json!({ "name": "Alice" })));
+
+    assert_eq!(
+      document.set_property("/profile/name", json!("Bob")).unwrap(),
+      Some(json!("Alice"))
+    );
+    assert_eq!(document.properties()["profile"]["name"], json!("Bob"));
+
+    // Array elements are replaced or appended.
+    document.set_property("/profile/tags", json!(["a", "b"])).unwrap();
+    assert_eq!(
+      document.set_property("/profile/tags/1", json!("c")).unwrap(),
+      Some(json!("b"))
+    );
+    assert_eq!(document.set_property("/profile/tags/-", json!("d")).unwrap(), None);
+    assert_eq!(document.get_property("/profile/tags"), Some(&json!(["a", "c", "d"])));
+    assert_eq!(document.get_property("/profile/tags/2"), Some(&json!("d")));
+
+    // Reference tokens are unescaped.
+    document.set_property("/a~1b/c~0d", json!(1)).unwrap();
+    assert_eq!(document.properties()["a/b"]["c~d"], json!(1));
+    assert_eq!(document.get_property("/a~1b/c~0d"), Some(&json!(1)));
+
+    assert_eq!(document.get_property("/profile/missing"), None);
+    assert_eq!(document.get_property("profile"), None);
+    assert_eq!(document.get_property(""), None);
+
+    for pointer in [
+      "",
+      "profile/name",
+      "/profile/name/first",
+      "/profile/tags/5",
+      "/profile/tags/01",
+    ] {
+      assert!(matches!(
+        document.set_property(pointer, json!(null)),
+        Err(Error::InvalidJsonPointer(_))
+      ));
+    }
+  }
+
+  #[test]
+  fn test_from_document_with_message_id() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    let message_id: MessageId = MessageId::new("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_owned());
+
+    let core: CoreDocument = CoreDocument::from_json(&document.to_json().unwrap()).unwrap();
+    let stored: RunnercDocument =
+      RunnercDocument::from_document_with_message_id(core.clone(), message_id.clone()).unwrap();
+
+    assert_eq!(TangleRef::message_id(&stored), &message_id);
+    assert_eq!(stored.id(), document.id());
+
+    // Without an explicit message id the message id is null.
+    let converted: RunnercDocument = RunnercDocument::try_from_core(core).unwrap();
+    assert!(TangleRef::message_id(&converted).is_null());
+  }
+
+  #[test]
+  fn test_parsed_message_id_null() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    assert!(TangleRef::message_id(&document).is_null());
+
+    let parsed: RunnercDocument = RunnercDocument::from_json(&document.to_json().unwrap()).unwrap();
+    assert!(TangleRef::message_id(&parsed).is_null());
+    assert_ne!(TangleRef::message_id(&parsed).to_string(), parsed.id().to_string());
+  }
+
+  #[test]
+  fn test_from_json_strict() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let json: String = document.to_json().unwrap();
+    assert_eq!(RunnercDocument::from_json_strict(&json).unwrap(), document);
+
+    let mut value: serde_json::Value = document.to_json_value().unwrap();
+    value["unexpected"] = json!("sibling");
+    let json: String = value.to_string();
+
+    // Lenient parsing keeps the field as a custom property.
+    let lenient: RunnercDocument = RunnercDocument::from_json(&json).unwrap();
+    assert_eq!(lenient.properties().get("unexpected"), Some(&json!("sibling")));
+
+    // Strict parsing rejects it.
+    assert!(matches!(
+      RunnercDocument::from_json_strict(&json),
+      Err(Error::UnexpectedFields(fields)) if fields == vec!["unexpected".to_owned()]
+    ));
+  }
+
+  #[test]
+  fn test_metadata() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    document.set_created(Timestamp::parse("2021-01-01T00:00:00Z").unwrap());
+    document.set_updated(Timestamp::parse("2021-06-01T00:00:00Z").unwrap());
+    let message_id: MessageId = MessageId::new("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_owned());
+    document.set_previous_message_id(message_id);
+    document.set_deactivated(true);
+
+    let metadata: DocumentMetadata = document.metadata();
+    assert_eq!(metadata.created, document.created());
+    assert_eq!(metadata.updated, document.updated());
+    assert_eq!(&metadata.previous_message_id, document.previous_message_id());
+    assert_eq!(metadata.deactivated, document.is_deactivated());
+    assert!(metadata.deactivated);
+
+    // The deactivation status survives a serialization round-trip.
+    let json: String = document.to_json().unwrap();
+    assert!(RunnercDocument::from_json(&json).unwrap().is_deactivated());
+  }
+
+  #[test]
+  fn test_merge_strict_conflict() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let base: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    let message_id: MessageId = MessageId::new("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_owned());
+
+    // Two writers concurrently update the properties of the same base document.
+    let mut local: RunnercDocument = base.clone();
+    local.properties_mut().insert("writer".into(), Value::from("local"));
+    let mut remote: RunnercDocument = base.clone();
+    remote.properties_mut().insert("writer".into(), Value::from("remote"));
+
+    let diff: DiffMessage = base.diff(&remote, message_id, keypair.private(), "#sign-0").unwrap();
+
+    let mut strict: RunnercDocument = local.clone();
+    let error: Error = strict.merge_strict(&base, &diff).unwrap_err();
+    assert!(matches!(
+      error,
+      Error::DiffError(identity_core::diff::Error::MergeConflict(fields)) if fields == ["writer"]
+    ));
+    assert_eq!(strict, local);
+
+    // Without local changes the diff applies cleanly.
+    let mut clean: RunnercDocument = base.clone();
+    clean.merge_strict(&base, &diff).unwrap();
+    assert_eq!(clean, remote);
+
+    // The lenient merge overwrites the local change.
+    let mut lenient: RunnercDocument = local;
+    lenient.merge(&diff).unwrap();
+    assert_eq!(lenient, remote);
+  }
+
+  #[test]
+  fn test_merge_strict_disjoint_changes() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut base: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    base.sign_self(keypair.private(), "#sign-0").unwrap();
+    let message_id: MessageId = MessageId::new("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_owned());
+
+    // Each writer edits a different part of the document, then updates and re-signs it.
+    let service: Service = Service::builder(Default::default())
+      .id(base.id().to_url().join("#linked-domain").unwrap().into())
+      .type_("LinkedDomains")
+      .service_endpoint(ServiceEndpoint::One(Url::parse("https://example.com").unwrap()))
+      .build()
+      .unwrap();
+
+    let mut local: RunnercDocument = base.clone();
+    assert!(local.insert_service(service));
+    local.set_updated(Timestamp::parse("2021-06-01T00:00:00Z").unwrap());
+    local.sign_self(keypair.private(), "#sign-0").unwrap();
+
+    let mut remote: RunnercDocument = base.clone();
+    remote.properties_mut().insert("writer".into(), Value::from("remote"));
+    remote.set_updated(Timestamp::parse("2021-07-01T00:00:00Z").unwrap());
+    remote.sign_self(keypair.private(), "#sign-0").unwrap();
+
+    let diff: DiffMessage = base.diff(&remote, message_id, keypair.private(), "#sign-0").unwrap();
+
+    let mut merged: RunnercDocument = local.clone();
+    merged.merge_strict(&base, &diff).unwrap();
+    assert_eq!(merged.service(), local.service());
+    assert_eq!(merged.properties().get("writer"), Some(&Value::from("remote")));
+    assert_eq!(merged.updated(), remote.updated());
+  }
+
+  #[test]
+  fn test_merge_preview() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    let message_id: MessageId = MessageId::new("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_owned());
+
+    let mut updated: RunnercDocument = document.clone();
+    updated.properties_mut().insert("foo".into(), Value::from("bar"));
+    let diff: DiffMessage = document
+      .diff(&updated, message_id, keypair.private(), "#sign-0")
+      .unwrap();
+
+    let original: RunnercDocument = document.clone();
+    let preview: RunnercDocument = document.merge_preview(&diff).unwrap();
+    assert_eq!(preview, updated);
+    assert_eq!(document, original);
+
+    // Committing the diff yields the previewed document.
+    let mut merged: RunnercDocument = document.clone();
+    merged.merge(&diff).unwrap();
+    assert_eq!(merged, preview);
+
+    // The diff signature is still verified.
+    let other: KeyPair = KeyPair::new_ed25519().unwrap();
+    let message_id: MessageId = MessageId::new("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_owned());
+    let forged: DiffMessage = RunnercDocument::new(&other)
+      .unwrap()
+      .diff(&updated, message_id, other.private(), "#sign-0")
+      .unwrap();
+    assert!(document.merge_preview(&forged).is_err());
+    assert_eq!(document, original);
+  }
+
+  #[test]
+  fn test_update() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    // A valid mutation is committed.
+    let url: Url = Url::parse("https://example.com/alias").unwrap();
+    document
+      .update(|document| document.also_known_as_mut().push(url.clone()))
+      .unwrap();
+    assert_eq!(document.as_document().also_known_as(), &[url]);
+
+    // An invalid DID is rejected and leaves the document unchanged.
+    let original: RunnercDocument = document.clone();
+    assert!(document
+      .update(|document| *document.id_mut() = "did:example:1234".parse().unwrap())
+      .is_err());
+    assert_eq!(document, original);
+
+    // A method shadowing the fragment of an existing method is rejected.
+    let method: VerificationMethod = document.default_signing_method().unwrap().clone().into();
+    assert!(document
+      .update(|document| {
+        document.verification_method_mut().append(method);
+      })
+      .is_err());
+    assert_eq!(document, original);
+  }
+
+  #[test]
+  fn test_diff_with_scope() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+    let message_id: MessageId = MessageId::new("QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u".to_owned());
+
+    let assertion_keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: RunnercVerificationMethod = RunnercVerificationMethod::from_did(
+      document.id().clone(),
+      assertion_keypair.type_(),
+      assertion_keypair.public(),
+      "assertion",
+    )
+    .unwrap();
+    document.insert_method(method, MethodScope::assertion_method()).unwrap();
+
+    let mut updated: RunnercDocument = document.clone();
+    updated.properties_mut().insert("credential".into(), Value::from("revoked"));
+
+    // The default scope requires a capability invocation method.
+    assert!(document
+      .diff(&updated, message_id.clone(), assertion_keypair.private(), "#assertion")
+      .is_err());
+
+    let diff: DiffMessage = document
+      .diff_with_scope(
+        &updated,
+        message_id,
+        assertion_keypair.private(),
+        "#assertion",
+        MethodScope::assertion_method(),
+      )
+      .unwrap();
+
+    assert!(document
+      .verify_diff_with_scope(&diff, MethodScope::assertion_method())
+      .is_ok());
+    assert!(document
+      .verify_diff_with_scope(&diff, MethodScope::authentication())
+      .is_err());
+    assert!(document.verify_diff(&diff).is_err());
+    assert!(document.clone().merge(&diff).is_err());
+  }
+
+  #[test]
+  fn test_normalize_key_data() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    // Add base58-encoded methods, both to the verificationMethod set and embedded.
+    for (fragment, scope) in [
+      ("key-b58", MethodScope::VerificationMethod),
+      ("auth-b58", MethodScope::authentication()),
+    ] {
+      let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+      let method: RunnercVerificationMethod =
+        RunnercVerificationMethod::from_did(document.id().clone(), keypair.type_(), keypair.public(), fragment)
+          .unwrap();
+      let mut method: VerificationMethod = method.into();
+      *method.key_data_mut() = MethodData::new_b58(keypair.public());
+
+      document
+        .insert_method(RunnercVerificationMethod::try_from_core(method).unwrap(), scope)
+        .unwrap();
+    }
+
+    let keys: Vec<Vec<u8>> = document
+      .methods()
+      .map(|method| method.key_data().try_decode().unwrap())
+      .collect();
+    let encodings: HashSet<Option<MethodDataEncoding>> =
+      document.methods().map(|method| method.key_data().encoding()).collect();
+
+    assert_eq!(keys.len(), 3);
+    assert_eq!(encodings.len(), 2);
+
+    for encoding in [MethodDataEncoding::Base58, MethodDataEncoding::Multibase] {
+      document.normalize_key_data_with_encoding(encoding).unwrap();
+
+      for (method, key) in document.methods().zip(keys.iter()) {
+        assert_eq!(method.key_data().encoding(), Some(encoding));
+        assert_eq!(&method.key_data().try_decode().unwrap(), key);
+      }
+    }
+
+    let mut normalized: RunnercDocument = document.clone();
+    normalized.normalize_key_data().unwrap();
+    assert_eq!(normalized, document);
+  }
+
+  #[test]
+  fn test_normalize_key_data_invalid() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    *document
+      .try_resolve_method_mut(RunnercDocument::DEFAULT_METHOD_FRAGMENT)
+      .unwrap()
+      .key_data_mut() = MethodData::PublicKeyBase58("0OIl".into());
+
+    let original: RunnercDocument = document.clone();
+
+    assert!(document.normalize_key_data().is_err());
+    assert_eq!(document, original);
+  }
+
+  #[test]
+  fn test_insert_service_didcomm() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    let service = |fragment: &str, endpoint: &str| -> Service {
+      Service::builder(Default::default())
+        .id(document.id().to_url().join(fragment).unwrap().into())
+        .type_(DIDCommServiceEndpoint::SERVICE_TYPE)
+        .service_endpoint(ServiceEndpoint::from_json(endpoint).unwrap())
+        .build()
+        .unwrap()
+    };
+
+    let valid: Service = service(
+      "#didcomm-1",
+      r#"{"uri":"https://example.com/path","accept":["didcomm/v2"],"routingKeys":[]}"#,
+    );
+    let invalid: Service = service("#didcomm-2", r#"{"uri":["https://example.com/path"],"routingKeys":[]}"#);
+
+    assert!(document.insert_service(valid));
+    assert!(!document.insert_service(invalid));
+    assert_eq!(document.service().len(), 1);
+  }
+
+  #[test]
+  fn test_services_by_type() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+
+    for (fragment, type_) in [
+      ("#domain-1", "LinkedDomains"),
+      ("#hub", "IdentityHub"),
+      ("#domain-2", "LinkedDomains"),
+    ] {
+      let service: Service = Service::builder(Default::default())
+        .id(document.id().to_url().join(fragment).unwrap().into())
+        .type_(type_)
+        .service_endpoint(ServiceEndpoint::One(Url::parse("https://example.com").unwrap()))
+        .build()
+        .unwrap();
+
+      assert!(document.insert_service(service));
+    }
+
+    let services: Vec<&Service> = document.services_by_type("LinkedDomains");
+    let fragments: Vec<&str> = services
+      .iter()
+      .map(|service| service.id().fragment().unwrap())
+      .collect();
+
+    assert_eq!(fragments, ["domain-1", "domain-2"]);
+    assert!(document.services_by_type("DIDCommMessaging").is_empty());
+  }
+}
@@ -15,11 +15,17 @@ use serde::Serialize;
 
 use identity_core::common::BitSet;
 use identity_core::convert::ToJson;
+use identity_core::crypto::merkle_key::Blake2b256;
 use identity_core::crypto::merkle_key::MerkleDigest;
+use identity_core::crypto::merkle_key::MerkleDigestTag;
+use identity_core::crypto::merkle_key::MerkleKey;
+use identity_core::crypto::merkle_key::Sha256;
+use identity_core::crypto::merkle_tree::DigestExt;
 use identity_core::crypto::KeyCollection;
 use identity_core::crypto::KeyPair;
 use identity_core::crypto::KeyType;
 use identity_core::crypto::PublicKey;
+use identity_core::Error as CoreError;
 use identity_did::did::CoreDID;
 use identity_did::did::CoreDIDUrl;
 use identity_did::did::DID;
@@ -54,7 +60,6 @@ impl RunnercVerificationMethod {
 
     MethodBuilder::default()
       .id(CoreDIDUrl::from(key))
-      .controller(did.into())
       .key_type(MethodType::MerkleKeyCollection2021)
       .key_data(MethodData::new_multibase(&keys.encode_merkle_key::<D>()))
       .build()
@@ -87,9 +92,7 @@ impl RunnercVerificationMethod {
     let tag: String = format!("#{}", fragment);
     let key: RunnercDIDUrl = did.to_url().join(tag)?;
 
-    let mut builder: MethodBuilder = MethodBuilder::default()
-      .id(CoreDIDUrl::from(key))
-      .controller(did.into());
+    let mut builder: MethodBuilder = MethodBuilder::default().id(CoreDIDUrl::from(key));
 
     match key_type {
       KeyType::Ed25519 => {
@@ -138,7 +141,8 @@ impl RunnercVerificationMethod {
   ///
   /// # Errors
   ///
-  /// Returns `Err` if the input is not a valid IOTA verification method.
+  /// Returns `Err` if the input is not a valid IOTA verification method or if its key data does
+  /// not have the length required by its key type.
   pub fn check_validity<T>(method: &VerificationMethod<T>) -> Result<()> {
     // Ensure all associated DIDs are valid IotaDIDs.
     RunnercDID::check_validity(method.id().did())?;
@@ -149,6 +153,35 @@ impl RunnercVerificationMethod {
       return Err(Error::InvalidMethodMissingFragment);
     }
 
+    Self::check_key_data(method)
+  }
+
+  /// Checks that the decoded `key_data` of the given verification method has the length required
+  /// by its `key_type`.
+  ///
+  /// `publicKeyJwk` data is not checked as it needs key-type specific handling.
+  fn check_key_data<T>(method: &VerificationMethod<T>) -> Result<()> {
+    if matches!(method.key_data(), MethodData::PublicKeyJwk(_)) {
+      return Ok(());
+    }
+
+    let data: Vec<u8> = method.key_data().try_decode()?;
+
+    match method.key_type() {
+      MethodType::Ed25519VerificationKey2018 => KeyType::Ed25519.check_public_key(&data)?,
+      MethodType::MerkleKeyCollection2021 => {
+        // A Merkle Key Collection is encoded as the algorithm tags followed by the Merkle root.
+        let root_len: usize = match MerkleKey::extract_tags(&data)?.1 {
+          MerkleDigestTag::BLAKE2B_256 => Blake2b256::OUTPUT_SIZE,
+          _ => Sha256::OUTPUT_SIZE,
+        };
+
+        if data.len() != 2 + root_len {
+          return Err(CoreError::InvalidKeyLength(data.len(), 2 + root_len).into());
+        }
+      }
+    }
+
     Ok(())
   }
 
@@ -188,6 +221,19 @@ impl RunnercVerificationMethod {
     *self.0.controller_mut() = CoreDID::from(did);
   }
 
+  /// Returns the public key of the method, decoded from its key data.
+  ///
+  /// For a Merkle Key Collection this is the encoded collection key rather than a single
+  /// public key.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the key data cannot be decoded, e.g. `publicKeyJwk` data which needs key-type
+  /// specific handling.
+  pub fn public_key(&self) -> Result<PublicKey> {
+    self.0.key_data().try_decode().map(Into::into).map_err(Into::into)
+  }
+
   /// Revokes the public key of a Merkle Key Collection at the specified `index`.
   pub fn revoke_merkle_key(&mut self, index: usize) -> Result<bool> {
     if !matches!(self.key_type(), MethodType::MerkleKeyCollection2021) {
@@ -252,3 +298,90 @@ impl Revocation for RunnercVerificationMethod {
     self.0.properties().revocation()
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_create_merkle_key_root_encoded() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let did: RunnercDID = RunnercDID::new(keypair.public().as_ref()).unwrap();
+    let keys: KeyCollection = KeyCollection::new_ed25519(8).unwrap();
+
+    let method: RunnercVerificationMethod =
+      RunnercVerificationMethod::create_merkle_key::<Sha256>(did, &keys, "merkle").unwrap();
+
+    assert_eq!(
+      method.key_data(),
+      &MethodData::PublicKeyMultibase(keys.merkle_root_encoded::<Sha256>())
+    );
+  }
+
+  #[test]
+  fn test_public_key() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: RunnercVerificationMethod = RunnercVerificationMethod::from_keypair(&keypair, "key-1").unwrap();
+
+    assert_eq!(method.public_key().unwrap().as_ref(), keypair.public().as_ref());
+  }
+
+  #[test]
+  fn test_public_key_jwk() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: RunnercVerificationMethod = RunnercVerificationMethod::from_keypair(&keypair, "key-1").unwrap();
+
+    let mut core: VerificationMethod = method.into();
+    *core.key_data_mut() = MethodData::PublicKeyJwk(Default::default());
+    let method: RunnercVerificationMethod = RunnercVerificationMethod::try_from_core(core).unwrap();
+
+    assert!(matches!(
+      method.public_key(),
+      Err(Error::InvalidDoc(identity_did::Error::InvalidKeyData))
+    ));
+  }
+
+  #[test]
+  fn test_check_validity_key_length() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let method: RunnercVerificationMethod = RunnercVerificationMethod::from_keypair(&keypair, "key-1").unwrap();
+
+    assert!(RunnercVerificationMethod::check_validity(&method).is_ok());
+
+    let mut core: VerificationMethod = method.into();
+    *core.key_data_mut() = MethodData::new_multibase(&[0; 16]);
+
+    assert!(matches!(
+      RunnercVerificationMethod::check_validity(&core),
+      Err(Error::CoreError(CoreError::InvalidKeyLength(16, 32)))
+    ));
+    assert!(RunnercVerificationMethod::try_from_core(core.clone()).is_err());
+
+    *core.key_data_mut() = MethodData::new_b58(&[0; 32]);
+
+    assert!(RunnercVerificationMethod::check_validity(&core).is_ok());
+  }
+
+  #[test]
+  fn test_check_validity_merkle_key_length() {
+    let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+    let did: RunnercDID = RunnercDID::new(keypair.public().as_ref()).unwrap();
+    let keys: KeyCollection = KeyCollection::new_ed25519(8).unwrap();
+
+    let method: RunnercVerificationMethod =
+      RunnercVerificationMethod::create_merkle_key::<Blake2b256>(did, &keys, "merkle").unwrap();
+
+    assert!(RunnercVerificationMethod::check_validity(&method).is_ok());
+
+    let mut data: Vec<u8> = method.key_data().try_decode().unwrap();
+    data.truncate(18);
+
+    let mut core: VerificationMethod = method.into();
+    *core.key_data_mut() = MethodData::new_multibase(&data);
+
+    assert!(matches!(
+      RunnercVerificationMethod::check_validity(&core),
+      Err(Error::CoreError(CoreError::InvalidKeyLength(18, 34)))
+    ));
+  }
+}
@@ -0,0 +1,74 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::crypto::SetSignature;
+use identity_core::crypto::Signature;
+use identity_core::crypto::TrySignature;
+use identity_core::crypto::TrySignatureMut;
+use identity_did::verification::MethodUriType;
+use identity_did::verification::TryMethod;
+
+use crate::did::RunnercDID;
+
+/// A verifier-issued nonce signed by the controller of a DID to prove control of it.
+///
+/// See [`RunnercDocument::sign_challenge`](crate::document::RunnercDocument::sign_challenge).
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SignedChallenge {
+  pub(crate) did: RunnercDID,
+  pub(crate) nonce: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) proof: Option<Signature>,
+}
+
+impl SignedChallenge {
+  /// Creates a new unsigned `SignedChallenge` for the given `did` and `nonce`.
+  pub fn new(did: RunnercDID, nonce: impl Into<String>) -> Self {
+    Self {
+      did,
+      nonce: nonce.into(),
+      proof: None,
+    }
+  }
+
+  /// Returns the DID whose control is proven.
+  pub fn id(&self) -> &RunnercDID {
+    &self.did
+  }
+
+  /// Returns the nonce issued by the verifier.
+  pub fn nonce(&self) -> &str {
+    &*self.nonce
+  }
+
+  /// Returns a reference to the challenge proof.
+  pub fn proof(&self) -> Option<&Signature> {
+    self.proof.as_ref()
+  }
+}
+
+impl TrySignature for SignedChallenge {
+  fn signature(&self) -> Option<&Signature> {
+    self.proof.as_ref()
+  }
+}
+
+impl TrySignatureMut for SignedChallenge {
+  fn signature_mut(&mut self) -> Option<&mut Signature> {
+    self.proof.as_mut()
+  }
+}
+
+impl SetSignature for SignedChallenge {
+  fn set_signature(&mut self, value: Signature) {
+    self.proof = Some(value);
+  }
+}
+
+impl TryMethod for SignedChallenge {
+  const TYPE: MethodUriType = MethodUriType::Relative;
+}
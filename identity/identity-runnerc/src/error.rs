@@ -33,10 +33,16 @@ pub enum Error {
   InvalidMethodMissingFragment,
   #[error("Invalid Root Document")]
   InvalidRootDocument,
+  #[error("Invalid Document Update: {0}")]
+  InvalidDocumentUpdate(&'static str),
   #[error("Invalid Network Name")]
   InvalidNetworkName,
   #[error("Invalid Presentation Holder")]
   InvalidPresentationHolder,
+  #[error("Invalid Presentation Challenge")]
+  InvalidPresentationChallenge,
+  #[error("Invalid Challenge")]
+  InvalidChallenge,
   #[error("Chain Error: {error}")]
   ChainError { error: &'static str },
   #[error("Missing Signing Key")]
@@ -53,4 +59,32 @@ pub enum Error {
   CompressionError,
   #[error("invalid message flags")]
   InvalidMessageFlags,
+  #[error("IPFS Error: {0}")]
+  IpfsError(String),
+  #[error("IPFS Pin Error: {0}")]
+  PinError(String),
+  #[error("KVStore Unreachable: {0}")]
+  KvStoreUnreachable(String),
+  #[error("KVStore Entry Not Found: {0}")]
+  KvStoreNotFound(String),
+  #[error("KVStore Bad Response: {0}")]
+  KvStoreBadResponse(String),
+  #[error("Timed out waiting for confirmation of {0}")]
+  ConfirmationTimeout(String),
+  #[error("Timed out: {0}")]
+  Timeout(String),
+  #[error("Document Drift: stored CID {stored} does not match recomputed CID {recomputed}")]
+  DocumentDrift { stored: String, recomputed: String },
+  #[error("Payload exceeds the maximum nesting depth of {0}")]
+  ExceededMaxDepth(usize),
+  #[error("Payload exceeds the maximum element count of {0}")]
+  ExceededMaxElements(usize),
+  #[error("Unexpected Fields: {0:?}")]
+  UnexpectedFields(Vec<String>),
+  #[error("Invalid JSON Pointer: {0}")]
+  InvalidJsonPointer(String),
+  #[error("DID Configuration Unavailable: {0}")]
+  DIDConfigurationUnavailable(String),
+  #[error("Invalid Linked Domain: {0}")]
+  InvalidLinkedDomain(&'static str),
 }
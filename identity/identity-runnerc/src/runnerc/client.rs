@@ -1,22 +1,51 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 // use bee_rest_api::types::dtos::LedgerInclusionStateDto;
-use crate::ipfs_api::IpfsApi;
 use crate::{
     did::RunnercDID,
     document::{DiffMessage, RunnercDocument},
-    error::{Error::DIDNotFound, Result},
-    runnerc::{Message, MessageId, Network, Receipt, TangleResolve},
+    error::{
+        Error::{
+            ChainError, ConfirmationTimeout, DIDNotFound, DocumentDrift, InvalidDocumentUpdate,
+            KvStoreNotFound, Timeout,
+        },
+        Result,
+    },
+    runnerc::{
+        message::{decode_message, decode_payload, encode_message, encode_payload},
+        HealthStatus, IpfsBackend, Message, MessageId, Network, Receipt, ServiceStatus, TangleRef,
+        TangleResolve,
+    },
 };
-use bytes::{BufMut, BytesMut};
+use futures::stream::Stream;
 use futures::stream::StreamExt;
+use futures::stream::TryStreamExt;
 use identity_core::convert::{FromJson, ToJson};
-use std::io::Cursor;
+use std::future::Future;
+use std::time::Duration;
 use tokio::runtime::Handle;
 
+/// Interval between two checks of [`Client::await_confirmation`].
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Maximum number of documents published at the same time by [`Client::publish_documents`].
+const PUBLISH_CONCURRENCY: usize = 4;
+
+/// Default timeout of [`Client::connect_timeout`] and [`Client::read_timeout`].
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Prefix of the IPFS pubsub topic on which the updates of a DID are announced.
+const DOCUMENT_TOPIC_PREFIX: &str = "idns/document/";
+
 /// Client for performing IOTA Identity operations on the Tangle.
 pub struct Client {
     pub(crate) network: Network,
+    pub(crate) pin_on_publish: bool,
+    pub(crate) compress_on_publish: bool,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) read_timeout: Duration,
+    pub(crate) kvstore_url: String,
+    pub(crate) backend: Box<dyn IpfsBackend>,
 }
 
 impl Client {
@@ -27,7 +56,21 @@ impl Client {
 
     /// Creates a new [`Client`] with default settings for the given [`Network`].
     pub async fn from_network(network: Network) -> Result<Self> {
-        Ok(Self { network })
+        Ok(Self::with_backend(network, Box::new(crate::utils::get_ipfs_client())))
+    }
+
+    /// Creates a new [`Client`] with default settings for the given [`Network`], storing
+    /// content on the given IPFS `backend`.
+    pub(crate) fn with_backend(network: Network, backend: Box<dyn IpfsBackend>) -> Self {
+        Self {
+            network,
+            pin_on_publish: true,
+            compress_on_publish: false,
+            connect_timeout: DEFAULT_TIMEOUT,
+            read_timeout: DEFAULT_TIMEOUT,
+            kvstore_url: crate::utils::KVSTORE_URL.to_owned(),
+            backend,
+        }
     }
 
     /// Returns the IOTA [`Network`] that the [`Client`] is configured to use.
@@ -35,60 +78,364 @@ impl Client {
         self.network.clone()
     }
 
+    /// Returns whether published content is pinned on the IPFS node.
+    pub fn pin_on_publish(&self) -> bool {
+        self.pin_on_publish
+    }
+
+    /// Sets whether published content is pinned on the IPFS node (enabled by default).
+    pub fn set_pin_on_publish(&mut self, value: bool) {
+        self.pin_on_publish = value;
+    }
+
+    /// Returns whether published content is compressed before being stored on the IPFS node.
+    pub fn compress_on_publish(&self) -> bool {
+        self.compress_on_publish
+    }
+
+    /// Sets whether published content is brotli-compressed before being stored on the IPFS
+    /// node (disabled by default).
+    ///
+    /// Compressed content is marked so that [`Client::get_value`] can transparently
+    /// decompress it, regardless of this setting.
+    pub fn set_compress_on_publish(&mut self, value: bool) {
+        self.compress_on_publish = value;
+    }
+
+    /// Returns the timeout for connecting to the KVStore.
+    pub fn connect_timeout(&self) -> Duration {
+        self.connect_timeout
+    }
+
+    /// Sets the timeout for connecting to the KVStore (30 seconds by default).
+    pub fn set_connect_timeout(&mut self, value: Duration) {
+        self.connect_timeout = value;
+    }
+
+    /// Returns the timeout for completing a single KVStore request or IPFS operation.
+    pub fn read_timeout(&self) -> Duration {
+        self.read_timeout
+    }
+
+    /// Sets the timeout for completing a single KVStore request or IPFS operation (30 seconds
+    /// by default).
+    ///
+    /// Operations exceeding it fail with [`Timeout`].
+    pub fn set_read_timeout(&mut self, value: Duration) {
+        self.read_timeout = value;
+    }
+
+    /// Runs the network `operation`, failing with [`Timeout`] if it does not complete within
+    /// [`Client::read_timeout`].
+    async fn timeout<T, F>(&self, name: &str, operation: F) -> Result<T>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        match tokio::time::timeout(self.read_timeout, operation).await {
+            Ok(result) => result,
+            Err(_) => Err(Timeout(format!("{} after {:?}", name, self.read_timeout))),
+        }
+    }
+
+    /// Sends the KVStore request `path` and returns the response body.
+    ///
+    /// See [`crate::utils::kvstore_get`] for the KVStore errors.
+    async fn ciddb_get(&self, path: &str) -> Result<String> {
+        let request = crate::utils::kvstore_get(&self.kvstore_url, path, self.connect_timeout);
+
+        self.timeout("KVStore request", request).await
+    }
+
+    /// Pins the content specified by `cid` on the IPFS node so that it is not
+    /// garbage-collected.
+    pub async fn pin(&self, cid: &str) -> Result<()> {
+        self.timeout("IPFS pin", self.backend.pin(cid))
+            .await
+            .map_err(|err| {
+                tracing::error!("IPFS pin失败:{:#?}", err);
+                err
+            })
+    }
+
+    /// Removes the pin of the content specified by `cid` from the IPFS node.
+    pub async fn unpin(&self, cid: &str) -> Result<()> {
+        self.timeout("IPFS unpin", self.backend.unpin(cid))
+            .await
+            .map_err(|err| {
+                tracing::error!("IPFS unpin失败:{:#?}", err);
+                err
+            })
+    }
+
+    /// Checks whether the IPFS node and the KVStore are reachable.
+    ///
+    /// Both subsystems are checked concurrently, each within [`Client::read_timeout`]. An
+    /// unreachable subsystem is reported in the returned [`HealthStatus`] rather than as an
+    /// error.
+    pub async fn health(&self) -> Result<HealthStatus> {
+        self.health_at(&self.kvstore_url).await
+    }
+
+    /// Checks the health of the IPFS node and of the KVStore at `kvstore_url`.
+    async fn health_at(&self, kvstore_url: &str) -> Result<HealthStatus> {
+        let ipfs = self.timeout("IPFS version", self.backend.version());
+        let kvstore = self.timeout(
+            "KVStore health",
+            crate::utils::kvstore_health(kvstore_url, self.connect_timeout),
+        );
+        let (ipfs, kvstore): (Result<String>, Result<()>) = futures::join!(ipfs, kvstore);
+
+        Ok(HealthStatus {
+            ipfs: ServiceStatus::from_result(ipfs),
+            kvstore: ServiceStatus::from_result(kvstore),
+        })
+    }
+
+    /// Waits until the content published with `receipt` is retrievable from the IPFS node,
+    /// and pinned if [`Client::pin_on_publish`] is enabled.
+    ///
+    /// Since publishing is content-addressed, this is what confirmation means: once it
+    /// returns, the published DID can be resolved.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ConfirmationTimeout`] if the content is not confirmed within `timeout`.
+    pub async fn await_confirmation(&self, receipt: &Receipt, timeout: Duration) -> Result<()> {
+        let cid: String = receipt.message_string_id();
+
+        if poll_until(timeout, CONFIRMATION_POLL_INTERVAL, || self.is_confirmed(&cid)).await {
+            Ok(())
+        } else {
+            Err(ConfirmationTimeout(cid))
+        }
+    }
+
+    /// Returns whether the content specified by `cid` is available on the IPFS node.
+    async fn is_confirmed(&self, cid: &str) -> bool {
+        if self.pin_on_publish && !self.backend.is_pinned(cid).await {
+            return false;
+        }
+
+        self.backend.contains(cid).await
+    }
+
     /// 存储值，并返回内容ID
     pub async fn set_value(&self, value: &String) -> Option<String> {
+        self.add_value(value).await.ok()
+    }
+
+    /// Stores `value` on the IPFS node and returns its content ID.
+    async fn add_value(&self, value: &str) -> Result<String> {
         //
         //保存到到IPFS
-        let data = Cursor::new(value.clone());
         tracing::debug!("保存文档到IPFS:{}", value);
+        let data: Vec<u8> =
+            encode_payload(value.as_bytes(), self.compress_on_publish).map_err(|e| {
+                tracing::error!("压缩失败:{:#?}", e);
+                e
+            })?;
 
-        let client = crate::utils::get_ipfs_client();
-
-        if let res_result = client.add(data).await {
-            match res_result {
-                Ok(res) => {
-                    tracing::debug!("保存到IPFS:{:#?}", res);
-                    Some(res.hash)
-                }
-                Err(e) => {
-                    tracing::error!("保存到IPFS失败:{:#?}", e);
-                    None
-                }
+        match self.timeout("IPFS add", self.backend.add(data)).await {
+            Ok(cid) => {
+                tracing::debug!("保存到IPFS:{:#?}", cid);
+                Ok(cid)
+            }
+            Err(e) => {
+                tracing::error!("保存到IPFS失败:{:#?}", e);
+                Err(e)
             }
-        } else {
-            None
         }
     }
 
+    /// Computes the content ID of `value` without storing it on the IPFS node.
+    async fn compute_cid(&self, value: &str) -> Result<String> {
+        let data: Vec<u8> = encode_payload(value.as_bytes(), self.compress_on_publish)?;
+
+        self.timeout("IPFS hash", self.backend.hash(data)).await
+    }
+
+    /// 读取内容ID对应的值，压缩的内容会被自动解压
     pub async fn get_value(&self, cid: &String) -> Option<String> {
+        self.cat_value(cid).await.ok()
+    }
+
+    /// Reads the value of the content specified by `cid` from the IPFS node, decompressing it
+    /// if necessary.
+    async fn cat_value(&self, cid: &str) -> Result<String> {
         //
-        let cid_copy = cid.clone();
-        let client = crate::utils::get_ipfs_client();
         //读取数据
-        let mut stream = client.cat(cid_copy.as_str());
-        let mut buf = BytesMut::with_capacity(40960);
-        while let Some(parts) = stream.next().await {
-            // bytes.
-            if let Ok(bs) = parts {
-                buf.put(bs);
-            }
+        let buf: Vec<u8> = self
+            .timeout("IPFS cat", self.backend.cat(cid))
+            .await
+            .map_err(|e| {
+                tracing::error!("读取IPFS失败:{:#?}", e);
+                e
+            })?;
+        let data: Vec<u8> = decode_payload(&buf).map_err(|e| {
+            tracing::error!("解压失败:{:#?}", e);
+            e
+        })?;
+        String::from_utf8(data).map_err(|_err| DIDNotFound(String::from("invalid UTF-8")))
+    }
+
+    /// Stores the binary `data`, e.g. an image referenced by a DID Document, verbatim on the
+    /// IPFS node and returns its content ID.
+    ///
+    /// Unlike [`Client::set_value`], the data is neither compressed nor required to be UTF-8. The
+    /// content is pinned if [`Client::pin_on_publish`] is enabled.
+    pub async fn add_raw(&self, data: &[u8]) -> Result<String> {
+        let cid: String = self
+            .timeout("IPFS add", self.backend.add(data.to_vec()))
+            .await?;
+
+        if self.pin_on_publish {
+            self.pin(&cid).await?;
         }
-        Some(String::from_utf8(buf.to_vec()).unwrap())
+
+        Ok(cid)
+    }
+
+    /// Reads the binary content specified by `cid` verbatim from the IPFS node.
+    ///
+    /// See [`Client::add_raw`].
+    pub async fn get_raw(&self, cid: &str) -> Result<Vec<u8>> {
+        self.timeout("IPFS cat", self.backend.cat(cid)).await
     }
 
     /// Publishes an [`RunnercDocument`] to the Tangle.
     /// This method calls `publish_json_with_retry` with its default `interval` and `max_attempts`
     /// values for increasing the probability that the message will be referenced by a milestone.
+    ///
+    /// The content ID of the document is then announced to the [subscribers][Client::subscribe]
+    /// of its DID; a failed announcement does not fail the publish.
+    #[tracing::instrument(skip(self, document), fields(did = %document.id()))]
     pub async fn publish_document(&self, document: &RunnercDocument) -> Result<Receipt> {
-        self.publish_json_with_retry(
-            format!("{:#?}", document.id()).as_str(),
-            document,
-            None,
-            None,
+        let receipt: Receipt = self
+            .publish_json_with_retry(
+                format!("{:#?}", document.id()).as_str(),
+                document,
+                None,
+                None,
+            )
+            .await?;
+
+        if let Err(error) = self
+            .announce(document.id(), &receipt.message_string_id())
+            .await
+        {
+            tracing::warn!("announce failed > {}", error);
+        }
+
+        Ok(receipt)
+    }
+
+    /// Announces the document of `did` stored under `cid` to the subscribers of `did`.
+    async fn announce(&self, did: &RunnercDID, cid: &str) -> Result<()> {
+        let topic: String = document_topic(did);
+
+        self.timeout(
+            "IPFS pubsub publish",
+            self.backend.publish_topic(&topic, cid.as_bytes().to_vec()),
         )
         .await
     }
 
+    /// Subscribes to the updates of the [`RunnercDocument`] of `did`, yielding every version
+    /// published with [`Client::publish_document`] while the stream is alive.
+    ///
+    /// Anyone can announce to the IPFS pubsub topic of a DID, so each version is checked to be a
+    /// document of `did` signed with a capability invocation method of the document currently
+    /// resolved for `did`.
+    ///
+    /// # Errors
+    ///
+    /// The stream yields an error if the subscription fails or an announced version or the
+    /// current document cannot be read, and [`InvalidDocumentUpdate`] if it is not a document of
+    /// `did` signed by the current document.
+    pub fn subscribe(&self, did: &RunnercDID) -> impl Stream<Item = Result<RunnercDocument>> + '_ {
+        let did: RunnercDID = did.clone();
+
+        self.backend
+            .subscribe_topic(&document_topic(&did))
+            .and_then(move |message| {
+                let did: RunnercDID = did.clone();
+                async move { self.read_update(&did, message).await }
+            })
+    }
+
+    /// Reads the version of the document of `did` announced by the pubsub `message`.
+    async fn read_update(&self, did: &RunnercDID, message: Vec<u8>) -> Result<RunnercDocument> {
+        let cid: String = String::from_utf8(message)
+            .map_err(|_err| InvalidDocumentUpdate("invalid content ID"))?;
+        let value: String = self.cat_value(&cid).await?;
+        let mut document: RunnercDocument = RunnercDocument::from_json(decode_message(&value)?)?;
+
+        if document.id() != did {
+            return Err(InvalidDocumentUpdate("unexpected DID"));
+        }
+
+        // A self-signed document can claim any DID; only the current document can authorize it.
+        let current: RunnercDocument = self.read_document(did).await?;
+        RunnercDocument::verify_document(&document, &current)
+            .map_err(|_err| InvalidDocumentUpdate("invalid signature"))?;
+        document.set_message_id(MessageId::new(cid));
+
+        Ok(document)
+    }
+
+    /// Publishes each of the given [`RunnercDocument`]s, running at most a bounded number of
+    /// publish operations concurrently.
+    ///
+    /// Returns the result of every publish in the order of `documents`; a failure to publish
+    /// one document does not affect the others.
+    pub async fn publish_documents(&self, documents: &[&RunnercDocument]) -> Vec<Result<Receipt>> {
+        futures::stream::iter(documents)
+            .map(|document| self.publish_document(document))
+            .buffered(PUBLISH_CONCURRENCY)
+            .collect()
+            .await
+    }
+
+    /// Re-adds the canonical bytes of a previously published [`RunnercDocument`] to IPFS and
+    /// re-pins them, e.g. after the content has fallen out of the IPFS cache.
+    ///
+    /// The KVStore mapping of the DID is left untouched.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`DocumentDrift`] if the CID of the document differs from the CID stored
+    /// for its DID.
+    pub async fn republish(&self, document: &RunnercDocument) -> Result<Receipt> {
+        let value: String = encode_message(
+            &document
+                .to_json()
+                .map_err(|_err| DIDNotFound(String::from("保存到IPFS失败")))?,
+        );
+
+        let stored: String = self
+            .ciddb_get(format!("/didcid/get/{}", document.id()).as_str())
+            .await?;
+
+        // Verify the document has not drifted before touching the IPFS node.
+        let recomputed: String = self.compute_cid(&value).await?;
+        check_cid_stable(&stored, &recomputed)?;
+
+        let cid: String = self.add_value(&value).await?;
+        check_cid_stable(&stored, &cid)?;
+
+        self.pin(&cid).await?;
+
+        Ok(Receipt::new(
+            self.network.clone(),
+            Message {
+                network_id: 0,
+                message_id: cid.clone(),
+                payload: Some(cid),
+                nonce: 0,
+            },
+        ))
+    }
+
     /// Publishes a [`DiffMessage`] to the Tangle to form part of the diff chain for the
     /// integration. chain message specified by the given [`MessageId`].
     /// This method calls `publish_json_with_retry` with its default `interval` and `max_attempts`
@@ -102,39 +449,40 @@ impl Client {
             .await
     }
 
-    /// Compresses and publishes arbitrary JSON data to the specified index on the Tangle.
+    /// Publishes arbitrary JSON data to the specified index on the Tangle, compressing it
+    /// first if [`Client::compress_on_publish`] is enabled.
+    ///
+    /// The data is stored as a message marked with the current
+    /// [`DIDMessageVersion`][crate::runnerc::DIDMessageVersion].
     pub async fn publish_json<T: ToJson>(&self, index: &str, data: &T) -> Result<Receipt> {
         println!("index:{},data:{:#?}", index, data.to_json());
         //保存
-        let cid_option = self
-            .set_value(
+        let cid: String = self
+            .add_value(&encode_message(
                 &data
                     .to_json()
                     .map_err(|_err| DIDNotFound(String::from("保存到IPFS失败")))?,
-            )
-            .await;
-        match cid_option {
-            Some(cid) => {
-                //保存did-cid索引
-                let _ = crate::utils::_ciddb_get_request(
-                    format!("/didcid/insert/{}/{}", index, cid).as_str(),
-                )
-                .await
-                .map_err(|_err| DIDNotFound(String::from("ciddb_get_request fail!")))?;
-
-                Ok(Receipt::new(
-                    self.network.clone(),
-                    Message {
-                        network_id: 0,
-                        message_id: cid.clone(),
-                        payload: Some(cid.clone()),
-                        nonce: 0,
-                    },
-                ))
-            }
-            None => Err(DIDNotFound(String::from(""))),
+            ))
+            .await?;
+
+        if self.pin_on_publish {
+            self.pin(&cid).await?;
         }
-        // Err(DIDNotFound(String::from("")))
+
+        //保存did-cid索引
+        let _ = self
+            .ciddb_get(format!("/didcid/insert/{}/{}", index, cid).as_str())
+            .await?;
+
+        Ok(Receipt::new(
+            self.network.clone(),
+            Message {
+                network_id: 0,
+                message_id: cid.clone(),
+                payload: Some(cid.clone()),
+                nonce: 0,
+            },
+        ))
     }
 
     /// Publishes arbitrary JSON data to the specified index on the Tangle.
@@ -152,32 +500,135 @@ impl Client {
     }
 
     /// Fetch the [`RunnercDocument`] specified by the given [`RunnercDID`].
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`DIDNotFound`] if the KVStore has no entry for `did`; KVStore and IPFS
+    /// failures are returned unchanged.
+    #[tracing::instrument(skip(self, did), fields(did = %did))]
     pub async fn read_document(&self, did: &RunnercDID) -> Result<RunnercDocument> {
         //
         tracing::debug!("read_document > {}", did);
         //根据DID获取CID
-        let cid = crate::utils::_ciddb_get_request(format!("/didcid/get/{}", did).as_str())
+        let cid = self
+            .ciddb_get(format!("/didcid/get/{}", did).as_str())
             .await
-            .map_err(|_err| DIDNotFound(String::from("ciddb_get_request fail!")))?;
+            .map_err(|err| match err {
+                KvStoreNotFound(_) => DIDNotFound(did.to_string()),
+                _ => err,
+            })?;
 
         tracing::debug!("read_document CID string > |{}|", cid);
-        if let Some(msg) = self.get_value(&cid).await {
-            tracing::debug!("read_document string > |{}|", msg);
-            //反序列化
-            let core_document_result = RunnercDocument::from_json_slice(msg.as_str());
-            match core_document_result {
-                Ok(core_document) => {
-                    tracing::debug!("read_document value > {:#}", core_document);
-                    return Ok(core_document);
-                }
-                Err(err) => {
-                    tracing::error!("Json解析失败 > {:#}", err);
-                    return Err(DIDNotFound(String::from("Json解析失败！")));
+        let msg: String = self.cat_value(&cid).await?;
+
+        tracing::debug!("read_document string > |{}|", msg);
+        //反序列化
+        let core_document_result = RunnercDocument::from_json_slice(decode_message(&msg)?);
+        match core_document_result {
+            Ok(mut core_document) => {
+                // The document was published under its CID.
+                core_document.set_message_id(MessageId::new(cid.trim().to_owned()));
+                tracing::debug!("read_document value > {:#}", core_document);
+                Ok(core_document)
+            }
+            Err(err) => {
+                tracing::error!("Json解析失败 > {:#}", err);
+                Err(DIDNotFound(String::from("Json解析失败！")))
+            }
+        }
+    }
+
+    /// Resolves the [`RunnercDocument`] specified by the given [`RunnercDID`] as it was at the
+    /// message `message_id`, ignoring any later updates.
+    ///
+    /// The diff chain of the current integration document is merged up to and including the
+    /// given message. Passing the message id of the integration document returns it unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the document cannot be read, a diff fails to merge or `message_id` is not part
+    /// of the diff chain.
+    pub async fn resolve_at(
+        &self,
+        did: &RunnercDID,
+        message_id: &MessageId,
+    ) -> Result<RunnercDocument> {
+        let document: RunnercDocument = self.read_document(did).await?;
+        let integration_id: MessageId = TangleRef::message_id(&document).clone();
+
+        let mut diffs: Vec<DiffMessage> = Vec::new();
+        let mut current: MessageId = integration_id.clone();
+
+        while current != *message_id {
+            match self.read_diff(&current).await? {
+                Some(diff) => {
+                    current = diff.message_id().clone();
+                    diffs.push(diff);
                 }
+                None => break,
             }
-        } else {
-            Err(DIDNotFound(String::from("")))
         }
+
+        resolve_chain_at(document, &integration_id, &diffs, message_id)
+    }
+
+    /// Resolves the current state of the [`RunnercDocument`] specified by the given
+    /// [`RunnercDID`] by merging its whole diff chain.
+    ///
+    /// The [`TangleRef::message_id`] of the returned document is the message id of its
+    /// integration document.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the document cannot be read or a diff fails to merge.
+    pub async fn resolve_latest(&self, did: &RunnercDID) -> Result<RunnercDocument> {
+        let document: RunnercDocument = self.read_document(did).await?;
+        let integration_id: MessageId = TangleRef::message_id(&document).clone();
+
+        let mut diffs: Vec<DiffMessage> = Vec::new();
+        let mut current: MessageId = integration_id.clone();
+
+        while let Some(diff) = self.read_diff(&current).await? {
+            current = diff.message_id().clone();
+            diffs.push(diff);
+        }
+
+        let mut document: RunnercDocument =
+            resolve_chain_at(document, &integration_id, &diffs, &current)?;
+        document.set_message_id(integration_id);
+
+        Ok(document)
+    }
+
+    /// Fetches the [`DiffMessage`] following the message `message_id` in the diff chain, if any.
+    ///
+    /// # Errors
+    ///
+    /// Only a missing KVStore entry ends the diff chain; any other failure is returned, so a
+    /// stale document is never resolved.
+    async fn read_diff(&self, message_id: &MessageId) -> Result<Option<DiffMessage>> {
+        let index: String = RunnercDocument::diff_index(message_id)?;
+
+        let stored: Option<String> = match self
+            .ciddb_get(format!("/didcid/get/{}", index).as_str())
+            .await
+        {
+            Ok(cid) => Some(cid),
+            Err(KvStoreNotFound(_)) => None,
+            Err(err) => return Err(err),
+        };
+
+        let cid: String = match stored {
+            Some(cid) if !cid.trim().is_empty() => cid.trim().to_owned(),
+            _ => return Ok(None),
+        };
+
+        let json: String = self.cat_value(&cid).await?;
+
+        let mut diff: DiffMessage = DiffMessage::from_json(decode_message(&json)?)?;
+        diff.set_message_id(MessageId::new(cid));
+
+        Ok(Some(diff))
     }
 
     /// Fetch all [`Messages`][Message] from the given index on the IOTA Tangle.
@@ -198,9 +649,775 @@ impl Client {
     }
 }
 
+/// Runs `check` every `interval` until it succeeds, returning `false` if it has not
+/// succeeded within `timeout`.
+async fn poll_until<F, Fut>(timeout: Duration, interval: Duration, mut check: F) -> bool
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = bool>,
+{
+    let poll = async {
+        while !check().await {
+            tokio::time::sleep(interval).await;
+        }
+    };
+
+    tokio::time::timeout(timeout, poll).await.is_ok()
+}
+
+/// Merges the `diffs` of the diff chain following the integration message `integration_id` into
+/// `document`, stopping after the message `target`.
+fn resolve_chain_at(
+    mut document: RunnercDocument,
+    integration_id: &MessageId,
+    diffs: &[DiffMessage],
+    target: &MessageId,
+) -> Result<RunnercDocument> {
+    let mut current: &MessageId = integration_id;
+
+    for diff in diffs {
+        if current == target {
+            break;
+        }
+
+        if diff.previous_message_id() != current {
+            return Err(ChainError {
+                error: "diff does not reference the previous message",
+            });
+        }
+
+        document.merge(diff)?;
+        current = diff.message_id();
+    }
+
+    if current == target {
+        Ok(document)
+    } else {
+        Err(ChainError {
+            error: "message not found in diff chain",
+        })
+    }
+}
+
+/// Ensures the CID recomputed from a document matches the CID stored in the KVStore.
+fn check_cid_stable(stored: &str, recomputed: &str) -> Result<()> {
+    if stored.trim() == recomputed {
+        Ok(())
+    } else {
+        Err(DocumentDrift {
+            stored: stored.trim().to_owned(),
+            recomputed: recomputed.to_owned(),
+        })
+    }
+}
+
+/// Returns the IPFS pubsub topic on which the updates of `did` are announced.
+fn document_topic(did: &RunnercDID) -> String {
+    format!("{}{}", DOCUMENT_TOPIC_PREFIX, did)
+}
+
 #[async_trait::async_trait(?Send)]
 impl TangleResolve for Client {
     async fn resolve(&self, did: &RunnercDID) -> Result<RunnercDocument> {
         self.read_document(did).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crypto::hashes::blake2b::Blake2b256;
+    use crypto::hashes::Digest;
+    use futures::stream::LocalBoxStream;
+    use hyper::service::make_service_fn;
+    use hyper::service::service_fn;
+    use hyper::Body;
+    use hyper::Request;
+    use hyper::Response;
+    use hyper::Server;
+    use std::convert::Infallible;
+    use std::net::TcpListener;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    use identity_core::crypto::KeyPair;
+    use serde_json::Value;
+
+    use crate::error::Error;
+    use crate::runnerc::MemoryBackend;
+    use crate::runnerc::MessageExt;
+    use crate::utils::serve_memory_kvstore;
+
+    const CID: &str = "QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u";
+
+    /// Returns a [`Client`] storing content on a [`MemoryBackend`] and using an in-memory
+    /// KVStore.
+    fn memory_client() -> Client {
+        let mut client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+        client.kvstore_url = serve_memory_kvstore();
+        client
+    }
+
+    #[test]
+    fn test_check_cid_stable() {
+        assert!(check_cid_stable(CID, CID).is_ok());
+        // Trailing whitespace from the KVStore response is ignored.
+        assert!(check_cid_stable(&format!("{}\n", CID), CID).is_ok());
+
+        let recomputed: &str = "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o";
+        assert!(matches!(
+            check_cid_stable(CID, recomputed),
+            Err(Error::DocumentDrift { stored, recomputed: actual }) if stored == CID && actual == recomputed
+        ));
+    }
+
+    #[test]
+    fn test_resolve_chain_at() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let integration_id: MessageId = MessageId::new(CID.to_owned());
+
+        let v0: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        let mut v1: RunnercDocument = v0.clone();
+        v1.properties_mut().insert("version".into(), Value::from(1));
+        let mut v2: RunnercDocument = v1.clone();
+        v2.properties_mut().insert("version".into(), Value::from(2));
+
+        let diff1_id: MessageId = MessageId::new("QmDiff1".to_owned());
+        let diff2_id: MessageId = MessageId::new("QmDiff2".to_owned());
+
+        let mut diff1: DiffMessage = v0
+            .diff(&v1, integration_id.clone(), keypair.private(), "#sign-0")
+            .unwrap();
+        diff1.set_message_id(diff1_id.clone());
+        let mut diff2: DiffMessage = v1
+            .diff(&v2, diff1_id.clone(), keypair.private(), "#sign-0")
+            .unwrap();
+        diff2.set_message_id(diff2_id.clone());
+
+        let diffs: Vec<DiffMessage> = vec![diff1, diff2];
+
+        // Resolving at the first diff ignores the later update.
+        let resolved: RunnercDocument =
+            resolve_chain_at(v0.clone(), &integration_id, &diffs, &diff1_id).unwrap();
+        assert_eq!(resolved.properties().get("version"), Some(&Value::from(1)));
+
+        let resolved: RunnercDocument =
+            resolve_chain_at(v0.clone(), &integration_id, &diffs, &diff2_id).unwrap();
+        assert_eq!(resolved.properties().get("version"), Some(&Value::from(2)));
+
+        let resolved: RunnercDocument =
+            resolve_chain_at(v0.clone(), &integration_id, &diffs, &integration_id).unwrap();
+        assert_eq!(resolved, v0);
+
+        // A message outside of the chain cannot be resolved.
+        let unknown: MessageId = MessageId::new("QmUnknown".to_owned());
+        assert!(matches!(
+            resolve_chain_at(v0.clone(), &integration_id, &diffs, &unknown),
+            Err(Error::ChainError { .. })
+        ));
+
+        // Diffs must follow each other.
+        assert!(matches!(
+            resolve_chain_at(v0, &integration_id, &diffs[1..], &diff2_id),
+            Err(Error::ChainError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_confirmed() {
+        // The backend only has the content from the third check on.
+        let checks: AtomicUsize = AtomicUsize::new(0);
+        let confirmed: bool = poll_until(Duration::from_secs(5), Duration::from_millis(10), || {
+            let count: usize = checks.fetch_add(1, Ordering::SeqCst) + 1;
+            async move { count >= 3 }
+        })
+        .await;
+
+        assert!(confirmed);
+        assert_eq!(checks.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_timeout() {
+        // The backend never has the content.
+        let confirmed: bool =
+            poll_until(Duration::from_millis(50), Duration::from_millis(10), || async { false })
+                .await;
+
+        assert!(!confirmed);
+    }
+
+    /// Returns the [`Receipt`] of the content specified by `cid`.
+    fn receipt(cid: &str) -> Receipt {
+        Receipt::new(
+            Network::Mainnet,
+            Message {
+                network_id: 0,
+                message_id: cid.to_owned(),
+                payload: Some(cid.to_owned()),
+                nonce: 0,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_await_confirmation() {
+        let client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+        let value: String = String::from("{\"confirmed\":true}");
+        let cid: String = client.compute_cid(&value).await.unwrap();
+
+        // The content is stored and pinned while waiting for it.
+        let publish = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            client.set_value(&value).await.unwrap();
+            client.pin(&cid).await.unwrap();
+        };
+        let (confirmed, ()): (Result<()>, ()) = futures::join!(
+            client.await_confirmation(&receipt(&cid), Duration::from_secs(5)),
+            publish
+        );
+
+        assert!(confirmed.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_await_confirmation_timeout() {
+        let client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+
+        assert!(matches!(
+            client
+                .await_confirmation(&receipt(CID), Duration::from_millis(50))
+                .await,
+            Err(Error::ConfirmationTimeout(cid)) if cid == CID
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_publish_pins() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let mut client: Client = memory_client();
+        assert!(client.pin_on_publish());
+
+        let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        let cid: String = client
+            .publish_document(&document)
+            .await
+            .unwrap()
+            .message_string_id();
+        assert!(client.backend.is_pinned(&cid).await);
+
+        // Unpinning keeps the content but removes the pin.
+        client.unpin(&cid).await.unwrap();
+        assert!(client.backend.contains(&cid).await);
+        assert!(!client.backend.is_pinned(&cid).await);
+        assert!(matches!(client.unpin(&cid).await, Err(Error::PinError(_))));
+
+        client.set_pin_on_publish(false);
+
+        let document: RunnercDocument =
+            RunnercDocument::new(&KeyPair::new_ed25519().unwrap()).unwrap();
+        let cid: String = client
+            .publish_document(&document)
+            .await
+            .unwrap()
+            .message_string_id();
+        assert!(client.backend.contains(&cid).await);
+        assert!(!client.backend.is_pinned(&cid).await);
+    }
+
+    #[tokio::test]
+    async fn test_publish_documents() {
+        let client: Client = memory_client();
+
+        let documents: Vec<RunnercDocument> = (0..3)
+            .map(|_| RunnercDocument::new(&KeyPair::new_ed25519().unwrap()).unwrap())
+            .collect();
+        let references: Vec<&RunnercDocument> = documents.iter().collect();
+
+        let receipts: Vec<Result<Receipt>> = client.publish_documents(&references).await;
+        assert_eq!(receipts.len(), 3);
+
+        for (document, receipt) in documents.iter().zip(receipts) {
+            let cid: String = client
+                .compute_cid(&encode_message(&document.to_json().unwrap()))
+                .await
+                .unwrap();
+            assert_eq!(receipt.unwrap().message_string_id(), cid);
+
+            // Every document is indexed under its own DID.
+            let resolved: RunnercDocument = client.read_document(document.id()).await.unwrap();
+            assert_eq!(resolved.id(), document.id());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compressed_round_trip() {
+        let mut client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+        assert!(!client.compress_on_publish());
+        client.set_compress_on_publish(true);
+
+        let value: String = format!("{{\"data\":\"{}\"}}", "runnerc".repeat(256));
+        let cid: String = client.set_value(&value).await.unwrap();
+
+        // The stored bytes are compressed and marked.
+        let stored: Vec<u8> = client.get_raw(&cid).await.unwrap();
+        assert!(stored.len() < value.len());
+        assert_eq!(decode_payload(&stored).unwrap(), value.as_bytes());
+
+        // Reads decompress transparently, whatever the publishing setting.
+        client.set_compress_on_publish(false);
+        assert_eq!(client.get_value(&cid).await.unwrap(), value);
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_round_trip() {
+        let mut client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+
+        for compress in [false, true] {
+            client.set_compress_on_publish(compress);
+
+            let value: String = format!("{{\"compressed\":{}}}", compress);
+            let cid: String = client.set_value(&value).await.unwrap();
+            assert_eq!(client.compute_cid(&value).await.unwrap(), cid);
+            assert_eq!(client.get_value(&cid).await.unwrap(), value);
+
+            assert!(!client.is_confirmed(&cid).await);
+            client.pin(&cid).await.unwrap();
+            assert!(client.is_confirmed(&cid).await);
+            client.unpin(&cid).await.unwrap();
+            assert!(client.unpin(&cid).await.is_err());
+        }
+
+        assert!(client.get_value(&String::from(CID)).await.is_none());
+        assert!(client.pin(CID).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_publish_extract_round_trip() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        document.sign_self(keypair.private(), "#sign-0").unwrap();
+        let client: Client = memory_client();
+
+        let receipt: Receipt = client.publish_document(&document).await.unwrap();
+        let messages: Vec<Message> = client
+            .read_messages(&receipt.message_string_id())
+            .await
+            .unwrap();
+        let extracted: RunnercDocument = messages[0].try_extract_document(document.id()).unwrap();
+        assert_eq!(extracted, document);
+
+        let mut updated: RunnercDocument = document.clone();
+        updated
+            .properties_mut()
+            .insert("version".into(), Value::from(1));
+        let diff: DiffMessage = document
+            .diff(
+                &updated,
+                receipt.message_id().clone(),
+                keypair.private(),
+                "#sign-0",
+            )
+            .unwrap();
+
+        let receipt: Receipt = client
+            .publish_diff(receipt.message_id(), &diff)
+            .await
+            .unwrap();
+        let messages: Vec<Message> = client
+            .read_messages(&receipt.message_string_id())
+            .await
+            .unwrap();
+        assert_eq!(messages[0].try_extract_diff(document.id()).unwrap(), diff);
+
+        // The published messages resolve as well.
+        let resolved: RunnercDocument = client.resolve_latest(document.id()).await.unwrap();
+        assert_eq!(resolved.properties().get("version"), Some(&Value::from(1)));
+    }
+
+    #[tokio::test]
+    async fn test_read_unmarked_message() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        document.sign_self(keypair.private(), "#sign-0").unwrap();
+        let client: Client = memory_client();
+
+        // Marked messages are read.
+        let receipt: Receipt = client.publish_document(&document).await.unwrap();
+        assert!(client.read_document(document.id()).await.is_ok());
+
+        let mut updated: RunnercDocument = document.clone();
+        updated
+            .properties_mut()
+            .insert("version".into(), Value::from(1));
+        let diff: DiffMessage = document
+            .diff(
+                &updated,
+                receipt.message_id().clone(),
+                keypair.private(),
+                "#sign-0",
+            )
+            .unwrap();
+
+        // An unmarked diff is not part of the diff chain.
+        let cid: String = client.add_value(&diff.to_json().unwrap()).await.unwrap();
+        let index: String = RunnercDocument::diff_index(receipt.message_id()).unwrap();
+        client
+            .ciddb_get(&format!("/didcid/insert/{}/{}", index, cid))
+            .await
+            .unwrap();
+        assert!(matches!(
+            client.read_diff(receipt.message_id()).await,
+            Err(Error::InvalidMessageFlags)
+        ));
+
+        // Neither is an unmarked document.
+        let cid: String = client
+            .add_value(&document.to_json().unwrap())
+            .await
+            .unwrap();
+        client
+            .ciddb_get(&format!("/didcid/insert/{}/{}", document.id(), cid))
+            .await
+            .unwrap();
+        assert!(matches!(
+            client.read_document(document.id()).await,
+            Err(Error::InvalidMessageFlags)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_raw_round_trip() {
+        let client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+
+        // Not valid UTF-8.
+        let data: Vec<u8> = (0..=255).rev().collect();
+        let digest = Blake2b256::digest(&data);
+
+        let cid: String = client.add_raw(&data).await.unwrap();
+        assert!(client.is_confirmed(&cid).await);
+
+        let stored: Vec<u8> = client.get_raw(&cid).await.unwrap();
+        assert_eq!(stored, data);
+        assert_eq!(Blake2b256::digest(&stored), digest);
+
+        // The same content is stored under the same content ID.
+        assert_eq!(client.add_raw(&data).await.unwrap(), cid);
+        assert!(client.get_value(&cid).await.is_none());
+        assert!(client.get_raw(CID).await.is_err());
+    }
+
+    /// An [`IpfsBackend`] that takes far longer than any test timeout to complete an operation.
+    struct SlowBackend;
+
+    impl SlowBackend {
+        const DELAY: Duration = Duration::from_secs(60);
+    }
+
+    #[async_trait::async_trait(?Send)]
+    impl IpfsBackend for SlowBackend {
+        async fn add(&self, _data: Vec<u8>) -> Result<String> {
+            tokio::time::sleep(Self::DELAY).await;
+            Ok(CID.to_owned())
+        }
+
+        async fn hash(&self, _data: Vec<u8>) -> Result<String> {
+            tokio::time::sleep(Self::DELAY).await;
+            Ok(CID.to_owned())
+        }
+
+        async fn cat(&self, _cid: &str) -> Result<Vec<u8>> {
+            tokio::time::sleep(Self::DELAY).await;
+            Ok(Vec::new())
+        }
+
+        async fn pin(&self, _cid: &str) -> Result<()> {
+            tokio::time::sleep(Self::DELAY).await;
+            Ok(())
+        }
+
+        async fn unpin(&self, _cid: &str) -> Result<()> {
+            tokio::time::sleep(Self::DELAY).await;
+            Ok(())
+        }
+
+        async fn is_pinned(&self, _cid: &str) -> bool {
+            tokio::time::sleep(Self::DELAY).await;
+            true
+        }
+
+        async fn contains(&self, _cid: &str) -> bool {
+            tokio::time::sleep(Self::DELAY).await;
+            true
+        }
+
+        async fn version(&self) -> Result<String> {
+            tokio::time::sleep(Self::DELAY).await;
+            Ok(String::from("slow"))
+        }
+
+        async fn publish_topic(&self, _topic: &str, _data: Vec<u8>) -> Result<()> {
+            tokio::time::sleep(Self::DELAY).await;
+            Ok(())
+        }
+
+        fn subscribe_topic(&self, _topic: &str) -> LocalBoxStream<'static, Result<Vec<u8>>> {
+            futures::stream::pending().boxed_local()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_timeout() {
+        let mut client: Client = Client::with_backend(Network::Mainnet, Box::new(SlowBackend));
+        assert_eq!(client.read_timeout(), DEFAULT_TIMEOUT);
+        assert_eq!(client.connect_timeout(), DEFAULT_TIMEOUT);
+
+        client.set_read_timeout(Duration::from_millis(20));
+
+        assert!(matches!(client.pin(CID).await, Err(Error::Timeout(_))));
+        assert!(matches!(client.unpin(CID).await, Err(Error::Timeout(_))));
+        assert!(matches!(
+            client.compute_cid("{}").await,
+            Err(Error::Timeout(_))
+        ));
+        assert!(matches!(
+            client.cat_value(CID).await,
+            Err(Error::Timeout(_))
+        ));
+        assert!(matches!(
+            client.publish_json("index", &Value::Null).await,
+            Err(Error::Timeout(_))
+        ));
+
+        // The `Option` accessors report a timeout as a missing value.
+        assert!(client.set_value(&String::from("{}")).await.is_none());
+        assert!(client.get_value(&String::from(CID)).await.is_none());
+    }
+
+    /// Returns the URL of a KVStore that cannot be reached.
+    fn unreachable_kvstore() -> String {
+        // Nothing listens on a port once its listener is dropped.
+        let port: u16 = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        format!("http://127.0.0.1:{}", port)
+    }
+
+    /// Serves a KVStore that returns `cid` for `did` and answers every other request with a
+    /// body that is not valid UTF-8, returning its URL.
+    fn serve_garbled_kvstore(did: &RunnercDID, cid: &str) -> String {
+        let path: String = format!("/didcid/get/{}", did);
+        let cid: String = cid.to_owned();
+
+        let make_service = make_service_fn(move |_| {
+            let path: String = path.clone();
+            let cid: String = cid.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let body: Body = if request.uri().path() == path {
+                        Body::from(cid.clone())
+                    } else {
+                        Body::from(vec![0xff, 0xfe])
+                    };
+                    async move { Ok::<_, Infallible>(Response::new(body)) }
+                }))
+            }
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url: String = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        url
+    }
+
+    #[tokio::test]
+    async fn test_read_document_errors() {
+        let mut client: Client = memory_client();
+        let document: RunnercDocument =
+            RunnercDocument::new(&KeyPair::new_ed25519().unwrap()).unwrap();
+
+        // Only a missing KVStore entry means the DID does not exist.
+        assert!(matches!(
+            client.read_document(document.id()).await,
+            Err(Error::DIDNotFound(_))
+        ));
+
+        client.kvstore_url = unreachable_kvstore();
+        assert!(matches!(
+            client.read_document(document.id()).await,
+            Err(Error::KvStoreUnreachable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_kvstore_errors() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        document.sign_self(keypair.private(), "#sign-0").unwrap();
+
+        let mut client: Client = memory_client();
+        let receipt: Receipt = client.publish_document(&document).await.unwrap();
+
+        // A missing entry ends the diff chain.
+        assert!(client
+            .read_diff(receipt.message_id())
+            .await
+            .unwrap()
+            .is_none());
+        assert!(client.resolve_latest(document.id()).await.is_ok());
+
+        // An unreachable KVStore does not.
+        client.kvstore_url = unreachable_kvstore();
+        assert!(matches!(
+            client.read_diff(receipt.message_id()).await,
+            Err(Error::KvStoreUnreachable(_))
+        ));
+        assert!(matches!(
+            client.resolve_latest(document.id()).await,
+            Err(Error::KvStoreUnreachable(_))
+        ));
+
+        // Neither does a garbled response for the diff index.
+        client.kvstore_url = serve_garbled_kvstore(document.id(), &receipt.message_string_id());
+        assert!(client.read_document(document.id()).await.is_ok());
+        assert!(matches!(
+            client.read_diff(receipt.message_id()).await,
+            Err(Error::KvStoreBadResponse(_))
+        ));
+        assert!(matches!(
+            client.resolve_latest(document.id()).await,
+            Err(Error::KvStoreBadResponse(_))
+        ));
+        assert!(matches!(
+            client
+                .resolve_at(document.id(), &MessageId::new(CID.to_owned()))
+                .await,
+            Err(Error::KvStoreBadResponse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let kvstore_url: String = unreachable_kvstore();
+
+        let client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+        let status: HealthStatus = client.health_at(&kvstore_url).await.unwrap();
+
+        assert_eq!(status.ipfs, ServiceStatus::Up);
+        assert!(matches!(status.kvstore, ServiceStatus::Down(_)));
+        assert!(!status.is_healthy());
+
+        let mut client: Client = Client::with_backend(Network::Mainnet, Box::new(SlowBackend));
+        client.set_read_timeout(Duration::from_millis(20));
+        let status: HealthStatus = client.health_at(&kvstore_url).await.unwrap();
+
+        assert!(matches!(status.ipfs, ServiceStatus::Down(_)));
+        assert!(!status.ipfs.is_up());
+    }
+
+    #[tokio::test]
+    async fn test_subscribe() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        document.sign_self(keypair.private(), "#sign-0").unwrap();
+
+        let client: Client = memory_client();
+        let mut updates = Box::pin(client.subscribe(document.id()));
+
+        // Publishing announces the document.
+        let cid: String = client
+            .publish_document(&document)
+            .await
+            .unwrap()
+            .message_string_id();
+
+        let update: RunnercDocument = updates.next().await.unwrap().unwrap();
+        assert_eq!(update.message_id().string_id(), cid);
+
+        document.set_message_id(MessageId::new(cid.clone()));
+        assert_eq!(update, document);
+
+        // Announcements of other DIDs are not received.
+        let other: RunnercDocument =
+            RunnercDocument::new(&KeyPair::new_ed25519().unwrap()).unwrap();
+        client.announce(other.id(), &cid).await.unwrap();
+
+        // Announcements on the topic of the DID must be signed documents of the DID.
+        let cid: String = client
+            .add_value(&encode_message(&other.to_json().unwrap()))
+            .await
+            .unwrap();
+        client.announce(document.id(), &cid).await.unwrap();
+        assert!(matches!(
+            updates.next().await.unwrap(),
+            Err(Error::InvalidDocumentUpdate(_))
+        ));
+
+        let unsigned: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        let cid: String = client
+            .add_value(&encode_message(&unsigned.to_json().unwrap()))
+            .await
+            .unwrap();
+        client.announce(document.id(), &cid).await.unwrap();
+        assert!(matches!(
+            updates.next().await.unwrap(),
+            Err(Error::InvalidDocumentUpdate(_))
+        ));
+
+        // Unmarked announcements are rejected.
+        let cid: String = client
+            .add_value(&document.to_json().unwrap())
+            .await
+            .unwrap();
+        client.announce(document.id(), &cid).await.unwrap();
+        assert!(matches!(
+            updates.next().await.unwrap(),
+            Err(Error::InvalidMessageFlags)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_forged() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let mut document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        document.sign_self(keypair.private(), "#sign-0").unwrap();
+
+        let client: Client = memory_client();
+        let mut updates = Box::pin(client.subscribe(document.id()));
+        client.publish_document(&document).await.unwrap();
+        assert!(updates.next().await.unwrap().is_ok());
+
+        // A document claiming the DID, holding the key of the forger and signed with it.
+        let forger: KeyPair = KeyPair::new_ed25519().unwrap();
+        let other: RunnercDocument = RunnercDocument::new(&forger).unwrap();
+        let json: String = other
+            .to_json()
+            .unwrap()
+            .replace(&other.id().to_string(), &document.id().to_string());
+        let mut forged: RunnercDocument = RunnercDocument::from_json(&json).unwrap();
+        forged.sign_self(forger.private(), "#sign-0").unwrap();
+        assert_eq!(forged.id(), document.id());
+        assert!(forged.verify_self_signed().is_ok());
+
+        let cid: String = client
+            .add_value(&encode_message(&forged.to_json().unwrap()))
+            .await
+            .unwrap();
+        client.announce(document.id(), &cid).await.unwrap();
+        assert!(matches!(
+            updates.next().await.unwrap(),
+            Err(Error::InvalidDocumentUpdate("invalid signature"))
+        ));
+    }
+}
@@ -4,6 +4,8 @@
 use std::sync::Arc;
 
 use dashmap::DashMap;
+use identity_core::common::Timestamp;
+use identity_core::crypto::PrivateKey;
 
 use crate::did::RunnercDID;
 use crate::document::DiffMessage;
@@ -14,6 +16,7 @@ use crate::runnerc::MessageId;
 use crate::runnerc::Network;
 use crate::runnerc::NetworkName;
 use crate::runnerc::Receipt;
+use crate::runnerc::TangleRef;
 use crate::runnerc::TangleResolve;
 
 type State = DashMap<NetworkName, Arc<Client>>;
@@ -44,6 +47,22 @@ impl ClientMap {
         self.data.insert(client.network.name(), Arc::new(client));
     }
 
+    /// Removes the [`Client`] configured for the given network, returning it if present.
+    ///
+    /// Operations already holding an `Arc` to the removed client are unaffected; a new
+    /// client is created on demand the next time the network is used.
+    pub fn remove(&self, network: &NetworkName) -> Option<Arc<Client>> {
+        self.data.remove(network).map(|(_, client)| client)
+    }
+
+    /// Returns the names of all networks with a configured [`Client`], in sorted order.
+    pub fn networks(&self) -> Vec<NetworkName> {
+        let mut networks: Vec<NetworkName> =
+            self.data.iter().map(|entry| entry.key().clone()).collect();
+        networks.sort();
+        networks
+    }
+
     pub async fn publish_document(&self, document: &RunnercDocument) -> Result<Receipt> {
         let network: Network = document.id().network()?;
         let client: Arc<Client> = self.client(network).await?;
@@ -70,6 +89,31 @@ impl ClientMap {
         // Err(DIDNotFound(String::from("")))
     }
 
+    /// Compacts the diff chain of the DID Document specified by `did` into a checkpoint.
+    ///
+    /// The current state of the document is resolved, signed with `private_key` and published
+    /// as a new integration document referencing the previous integration message. Subsequent
+    /// diffs must build on the message id of the returned [`Receipt`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the document cannot be resolved, signed or published.
+    pub async fn compact(&self, did: &RunnercDID, private_key: &PrivateKey) -> Result<Receipt> {
+        let network: Network = did.network()?;
+        let client: Arc<Client> = self.client(network).await?;
+
+        let document: RunnercDocument = client.resolve_latest(did).await?;
+        let document: RunnercDocument = checkpoint(document, private_key)?;
+
+        client.publish_document(&document).await
+    }
+
+    /// Returns the [`Client`] configured for the given network, creating it if necessary.
+    ///
+    /// Networks are keyed by their normalized [`NetworkName`], so DIDs with an implicit or
+    /// explicit default network (e.g. `did:idns:<tag>` and `did:idns:main:<tag>`) share the
+    /// same client. If clients are created concurrently for the same network, only the first
+    /// one inserted is kept and returned to every caller.
     pub async fn client(&self, network: Network) -> Result<Arc<Client>> {
         let network_name = network.name();
         if let Some(client) = self.data.get(&network_name) {
@@ -78,9 +122,7 @@ impl ClientMap {
 
         let client: Arc<Client> = Client::from_network(network.clone()).await.map(Arc::new)?;
 
-        self.data.insert(network_name, Arc::clone(&client));
-
-        Ok(client)
+        Ok(Arc::clone(&self.data.entry(network_name).or_insert(client)))
     }
 }
 
@@ -90,9 +132,178 @@ impl Default for ClientMap {
     }
 }
 
+/// Prepares the resolved `document` to be published as a new integration document following
+/// its current integration message.
+fn checkpoint(mut document: RunnercDocument, private_key: &PrivateKey) -> Result<RunnercDocument> {
+    let previous: MessageId = TangleRef::message_id(&document).clone();
+    let method: String = document.default_signing_method()?.try_into_fragment()?;
+
+    document.set_previous_message_id(previous);
+    document.set_updated(Timestamp::now_utc());
+    document.sign_self(private_key, method.as_str())?;
+
+    Ok(document)
+}
+
 #[async_trait::async_trait(?Send)]
 impl TangleResolve for ClientMap {
     async fn resolve(&self, did: &RunnercDID) -> Result<RunnercDocument> {
         self.read_document(did).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use identity_core::crypto::KeyPair;
+    use identity_did::did::CoreDID;
+    use serde_json::Value;
+
+    use crate::runnerc::MemoryBackend;
+    use crate::utils::serve_memory_kvstore;
+
+    const CID: &str = "QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u";
+
+    fn versioned(document: &RunnercDocument, version: u32) -> RunnercDocument {
+        let mut document: RunnercDocument = document.clone();
+        document
+            .properties_mut()
+            .insert("version".into(), Value::from(version));
+        document
+    }
+
+    /// Returns a [`ClientMap`] with a [`Client`] storing content on a [`MemoryBackend`] and
+    /// using an in-memory KVStore.
+    fn memory_map() -> ClientMap {
+        let mut client: Client =
+            Client::with_backend(Network::Mainnet, Box::new(MemoryBackend::default()));
+        client.kvstore_url = serve_memory_kvstore();
+        ClientMap::from_client(client)
+    }
+
+    #[tokio::test]
+    async fn test_client_default_network() {
+        const TAG: &str = "H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV";
+
+        let implicit: RunnercDID = RunnercDID::parse(format!("did:idns:{}", TAG)).unwrap();
+        let explicit: RunnercDID = RunnercDID::parse(format!("did:idns:main:{}", TAG)).unwrap();
+        let core: CoreDID = format!("did:idns:main:{}", TAG).parse().unwrap();
+        let borrowed: &RunnercDID = RunnercDID::try_from_borrowed(&core).unwrap();
+
+        let clients: ClientMap = ClientMap::new();
+        let first: Arc<Client> = clients.client(implicit.network().unwrap()).await.unwrap();
+        let second: Arc<Client> = clients.client(explicit.network().unwrap()).await.unwrap();
+        let third: Arc<Client> = clients.client(borrowed.network().unwrap()).await.unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(Arc::ptr_eq(&first, &third));
+        assert_eq!(clients.networks(), vec![Network::Mainnet.name()]);
+
+        let dev: RunnercDID = RunnercDID::parse(format!("did:idns:dev:{}", TAG)).unwrap();
+        let fourth: Arc<Client> = clients.client(dev.network().unwrap()).await.unwrap();
+
+        assert!(!Arc::ptr_eq(&first, &fourth));
+        assert_eq!(clients.networks().len(), 2);
+    }
+
+    #[test]
+    fn test_checkpoint() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let integration_id: MessageId = MessageId::new(CID.to_owned());
+
+        let v0: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        let v1: RunnercDocument = versioned(&v0, 1);
+        let diff: DiffMessage = v0
+            .diff(&v1, integration_id.clone(), keypair.private(), "#sign-0")
+            .unwrap();
+
+        let mut resolved: RunnercDocument = v0.clone();
+        resolved.merge(&diff).unwrap();
+        resolved.set_message_id(integration_id.clone());
+
+        let compacted: RunnercDocument = checkpoint(resolved.clone(), keypair.private()).unwrap();
+
+        assert_eq!(compacted.properties(), resolved.properties());
+        assert_eq!(compacted.previous_message_id(), &integration_id);
+        assert!(RunnercDocument::verify_document(&compacted, &compacted).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_compact() {
+        let map: ClientMap = memory_map();
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+
+        let mut v0: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        v0.sign_self(keypair.private(), "#sign-0").unwrap();
+        let integration: Receipt = map.publish_document(&v0).await.unwrap();
+
+        // Build a diff chain of two updates.
+        let mut previous: MessageId = integration.message_id().clone();
+        let mut current: RunnercDocument = v0.clone();
+        for version in 1..=2 {
+            let next: RunnercDocument = versioned(&current, version);
+            let diff: DiffMessage = current
+                .diff(&next, previous.clone(), keypair.private(), "#sign-0")
+                .unwrap();
+            let receipt: Receipt = map.publish_diff(&previous, &diff).await.unwrap();
+            previous = receipt.message_id().clone();
+            current = next;
+        }
+
+        let client: Arc<Client> = map.client(Network::Mainnet).await.unwrap();
+        let before: RunnercDocument = client.resolve_latest(v0.id()).await.unwrap();
+        assert_eq!(before.properties().get("version"), Some(&Value::from(2)));
+
+        let receipt: Receipt = map.compact(v0.id(), keypair.private()).await.unwrap();
+
+        let after: RunnercDocument = client.resolve_latest(v0.id()).await.unwrap();
+        assert_eq!(TangleRef::message_id(&after), receipt.message_id());
+        assert_eq!(after.previous_message_id(), integration.message_id());
+        assert_eq!(after.properties(), before.properties());
+    }
+
+    #[tokio::test]
+    async fn test_remove_and_networks() {
+        let map: ClientMap = ClientMap::new();
+        assert!(map.networks().is_empty());
+
+        map.insert(Client::from_network(Network::Mainnet).await.unwrap());
+        map.insert(Client::from_network(Network::Devnet).await.unwrap());
+        assert_eq!(
+            map.networks(),
+            vec![Network::Devnet.name(), Network::Mainnet.name()]
+        );
+
+        // A client obtained before removal stays usable.
+        let held: Arc<Client> = map.client(Network::Devnet).await.unwrap();
+
+        let removed: Arc<Client> = map.remove(&Network::Devnet.name()).unwrap();
+        assert!(Arc::ptr_eq(&held, &removed));
+        assert_eq!(held.network(), Network::Devnet);
+        assert_eq!(map.networks(), vec![Network::Mainnet.name()]);
+
+        // Removing an unknown network is a no-op.
+        assert!(map.remove(&Network::Devnet.name()).is_none());
+        assert_eq!(map.networks(), vec![Network::Mainnet.name()]);
+    }
+
+    #[tokio::test]
+    async fn test_network_name_normalized() {
+        let map: ClientMap = ClientMap::new();
+
+        for (lower, upper) in [("main", "Main"), ("custom", "CUSTOM")] {
+            let lower: Arc<Client> = map
+                .client(Network::try_from_name(lower).unwrap())
+                .await
+                .unwrap();
+            let upper: Arc<Client> = map
+                .client(Network::try_from_name(upper).unwrap())
+                .await
+                .unwrap();
+            assert!(Arc::ptr_eq(&lower, &upper));
+        }
+
+        assert_eq!(map.networks().len(), 2);
+    }
+}
@@ -13,6 +13,7 @@ use serde::Serialize;
 use identity_core::common::Url;
 use identity_did::did::DID;
 
+use crate::did::RunnercDID;
 use crate::error::Error;
 use crate::error::Result;
 use crate::runnerc::MessageId;
@@ -51,9 +52,15 @@ impl ExplorerUrl {
   /// Use [`ExplorerUrl::mainnet`] or [`ExplorerUrl::devnet`] unless using a private Tangle
   /// or local explorer.
   ///
+  /// The URL must be an `http` or `https` URL without a query or fragment, so links can be
+  /// built by appending path segments.
+  ///
   /// NOTE: does not validate whether this corresponds to an actual Tangle explorer.
   pub fn new(url: Url) -> Result<Self> {
-    if url.cannot_be_a_base() {
+    if url.cannot_be_a_base() || !matches!(url.scheme(), "http" | "https") {
+      return Err(Error::InvalidExplorerURL);
+    }
+    if url.query().is_some() || url.fragment().is_some() {
       return Err(Error::InvalidExplorerURL);
     }
     Ok(Self(url))
@@ -92,25 +99,31 @@ impl ExplorerUrl {
   ///
   /// E.g. https://did.runnerc.cc/main/message/<message_id>
   pub fn message_url(&self, message_id: &MessageId) -> Result<Url> {
-    let mut url: Url = self.0.clone();
-    url
-      .path_segments_mut()
-      .map_err(|_| Error::InvalidExplorerURL)?
-      .push("message")
-      .push(&message_id.to_string());
-    Ok(url)
+    self.join("message", &message_id.to_string())
   }
 
   /// Returns the web identity resolver URL for the given DID.
   ///
   /// E.g. https://did.runnerc.cc/main/resolver/<did>
   pub fn resolver_url(&self, did: &impl DID) -> Result<Url> {
+    self.join("resolver", did.as_str())
+  }
+
+  /// Returns the web explorer URL of the given [`RunnercDID`].
+  ///
+  /// This is the [resolver URL][ExplorerUrl::resolver_url] of the DID.
+  pub fn did_url(&self, did: &RunnercDID) -> Result<Url> {
+    self.resolver_url(did)
+  }
+
+  fn join(&self, kind: &str, value: &str) -> Result<Url> {
     let mut url: Url = self.0.clone();
     url
       .path_segments_mut()
       .map_err(|_| Error::InvalidExplorerURL)?
-      .push("resolver")
-      .push(did.as_str());
+      .pop_if_empty()
+      .push(kind)
+      .push(value);
     Ok(url)
   }
 }
@@ -151,86 +164,83 @@ impl Display for ExplorerUrl {
   }
 }
 
-// #[cfg(test)]
-// mod tests {
-//   use crate::did::RunnercDID;
-
-//   use super::*;
-
-//   #[test]
-//   fn test_explorer_url() {
-//     let main_url_str: &str = "https://did.runnerc.cc/mainnet";
-//     let dev_url_str: &str = "https://did.runnerc.cc/devnet";
-//     let localhost: &str = "http://127.0.0.1:8082";
-
-//     // Valid new()
-//     assert_eq!(
-//       &ExplorerUrl::new(Url::parse(main_url_str).unwrap()).unwrap(),
-//       ExplorerUrl::mainnet()
-//     );
-//     assert_eq!(
-//       &ExplorerUrl::new(Url::parse(dev_url_str).unwrap()).unwrap(),
-//       ExplorerUrl::devnet()
-//     );
-//     assert!(ExplorerUrl::new(Url::parse(localhost).unwrap()).is_ok());
-
-//     // Valid parse()
-//     assert_eq!(&ExplorerUrl::parse(main_url_str).unwrap(), ExplorerUrl::mainnet());
-//     assert_eq!(&ExplorerUrl::parse(dev_url_str).unwrap(), ExplorerUrl::devnet());
-//     assert!(ExplorerUrl::parse(localhost).is_ok());
-
-//     // Try setting a `cannot_be_a_base` url.
-//     assert!(matches!(
-//       ExplorerUrl::new(Url::parse("data:text/plain,stuff").unwrap()).unwrap_err(),
-//       Error::InvalidExplorerURL
-//     ));
-//   }
-
-//   #[test]
-//   fn test_message_url() {
-//     let message_id: MessageId = MessageId::new([0u8; 32]);
-
-//     let explorer_main: &ExplorerUrl = ExplorerUrl::mainnet();
-//     assert_eq!(
-//       explorer_main.message_url(&message_id).unwrap(),
-//       format!("{}/{}/{}", explorer_main, "message", message_id)
-//     );
-
-//     let explorer_dev: &ExplorerUrl = ExplorerUrl::devnet();
-//     assert_eq!(
-//       explorer_dev.message_url(&message_id).unwrap(),
-//       format!("{}/{}/{}", explorer_dev, "message", message_id)
-//     );
-
-//     let localhost: &str = "http://127.0.0.1:8082";
-//     let other = ExplorerUrl::parse(localhost).unwrap();
-//     assert_eq!(
-//       other.message_url(&message_id).unwrap(),
-//       format!("{}/{}/{}", localhost, "message", message_id)
-//     );
-//   }
-
-//   #[test]
-//   fn test_resolver_url() {
-//     let did: RunnercDID = RunnercDID::parse("did:iota:H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV").unwrap();
-
-//     let explorer_main: &ExplorerUrl = ExplorerUrl::mainnet();
-//     assert_eq!(
-//       explorer_main.resolver_url(&did).unwrap(),
-//       format!("{}/{}/{}", explorer_main, "resolver", did)
-//     );
-
-//     let explorer_dev: &ExplorerUrl = ExplorerUrl::devnet();
-//     assert_eq!(
-//       explorer_dev.resolver_url(&did).unwrap(),
-//       format!("{}/{}/{}", explorer_dev, "resolver", did)
-//     );
-
-//     let localhost: &str = "http://127.0.0.1:8082";
-//     let other: ExplorerUrl = ExplorerUrl::parse(localhost).unwrap();
-//     assert_eq!(
-//       other.resolver_url(&did).unwrap(),
-//       format!("{}/{}/{}", localhost, "resolver", did)
-//     );
-//   }
-// }
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MESSAGE_ID: &str = "QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u";
+  const DID: &str = "did:idns:H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV";
+
+  #[test]
+  fn test_explorer_url() {
+    let main_url_str: &str = "https://did.runnerc.cc/main";
+    let dev_url_str: &str = "https://did.runnerc.cc/dev";
+    let localhost: &str = "http://127.0.0.1:8082";
+
+    // Valid new()
+    assert_eq!(
+      &ExplorerUrl::new(Url::parse(main_url_str).unwrap()).unwrap(),
+      ExplorerUrl::mainnet()
+    );
+    assert_eq!(
+      &ExplorerUrl::new(Url::parse(dev_url_str).unwrap()).unwrap(),
+      ExplorerUrl::devnet()
+    );
+    assert!(ExplorerUrl::new(Url::parse(localhost).unwrap()).is_ok());
+
+    // Valid parse()
+    assert_eq!(&ExplorerUrl::parse(main_url_str).unwrap(), ExplorerUrl::mainnet());
+    assert_eq!(&ExplorerUrl::parse(dev_url_str).unwrap(), ExplorerUrl::devnet());
+    assert!(ExplorerUrl::parse(localhost).is_ok());
+  }
+
+  #[test]
+  fn test_explorer_url_invalid() {
+    let invalid: &[&str] = &[
+      "data:text/plain,stuff",
+      "ftp://did.runnerc.cc/main",
+      "https://did.runnerc.cc/main?network=main",
+      "https://did.runnerc.cc/main#message",
+      "not a url",
+    ];
+
+    for url in invalid {
+      assert!(matches!(ExplorerUrl::parse(url).unwrap_err(), Error::InvalidExplorerURL));
+    }
+  }
+
+  #[test]
+  fn test_message_url() {
+    let message_id: MessageId = MessageId::new(MESSAGE_ID.to_owned());
+
+    assert_eq!(
+      ExplorerUrl::mainnet().message_url(&message_id).unwrap(),
+      format!("https://did.runnerc.cc/main/message/{}", MESSAGE_ID)
+    );
+    assert_eq!(
+      ExplorerUrl::devnet().message_url(&message_id).unwrap(),
+      format!("https://did.runnerc.cc/dev/message/{}", MESSAGE_ID)
+    );
+
+    // A trailing slash does not produce an empty path segment.
+    let localhost: ExplorerUrl = ExplorerUrl::parse("http://127.0.0.1:8082/").unwrap();
+    assert_eq!(
+      localhost.message_url(&message_id).unwrap(),
+      format!("http://127.0.0.1:8082/message/{}", MESSAGE_ID)
+    );
+  }
+
+  #[test]
+  fn test_did_url() {
+    let did: RunnercDID = RunnercDID::parse(DID).unwrap();
+
+    assert_eq!(
+      ExplorerUrl::mainnet().did_url(&did).unwrap(),
+      format!("https://did.runnerc.cc/main/resolver/{}", DID)
+    );
+    assert_eq!(
+      ExplorerUrl::devnet().did_url(&did).unwrap(),
+      ExplorerUrl::devnet().resolver_url(&did).unwrap()
+    );
+  }
+}
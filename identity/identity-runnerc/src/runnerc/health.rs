@@ -0,0 +1,43 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Result;
+
+/// The status of a subsystem checked by [`Client::health`](crate::runnerc::Client::health).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServiceStatus {
+  /// The subsystem is reachable and responding.
+  Up,
+  /// The subsystem could not be reached, with the reason of the failure.
+  Down(String),
+}
+
+impl ServiceStatus {
+  /// Returns `true` if the subsystem is reachable.
+  pub fn is_up(&self) -> bool {
+    matches!(self, Self::Up)
+  }
+
+  pub(crate) fn from_result<T>(result: Result<T>) -> Self {
+    match result {
+      Ok(_) => Self::Up,
+      Err(error) => Self::Down(error.to_string()),
+    }
+  }
+}
+
+/// The status of the subsystems used by the [`Client`](crate::runnerc::Client).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HealthStatus {
+  /// The status of the IPFS node.
+  pub ipfs: ServiceStatus,
+  /// The status of the KVStore.
+  pub kvstore: ServiceStatus,
+}
+
+impl HealthStatus {
+  /// Returns `true` if all subsystems are reachable.
+  pub fn is_healthy(&self) -> bool {
+    self.ipfs.is_up() && self.kvstore.is_up()
+  }
+}
@@ -0,0 +1,244 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Cursor;
+
+use bytes::BufMut;
+use bytes::BytesMut;
+use futures::stream::LocalBoxStream;
+use futures::stream::StreamExt;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::ipfs_api::request::Add;
+use crate::ipfs_api::IpfsApi;
+use crate::ipfs_api::IpfsClient;
+
+/// The IPFS node operations used by the [`Client`](crate::runnerc::Client).
+///
+/// Abstracting the node allows the client to be exercised without a running IPFS daemon.
+#[async_trait::async_trait(?Send)]
+pub(crate) trait IpfsBackend: Send + Sync {
+    /// Stores `data` on the node and returns its content ID.
+    async fn add(&self, data: Vec<u8>) -> Result<String>;
+
+    /// Computes the content ID of `data` without storing it.
+    async fn hash(&self, data: Vec<u8>) -> Result<String>;
+
+    /// Reads the content specified by `cid`.
+    async fn cat(&self, cid: &str) -> Result<Vec<u8>>;
+
+    /// Pins the content specified by `cid` so that it is not garbage-collected.
+    async fn pin(&self, cid: &str) -> Result<()>;
+
+    /// Removes the pin of the content specified by `cid`.
+    async fn unpin(&self, cid: &str) -> Result<()>;
+
+    /// Returns whether the content specified by `cid` is pinned.
+    async fn is_pinned(&self, cid: &str) -> bool;
+
+    /// Returns whether the content specified by `cid` is available on the node.
+    async fn contains(&self, cid: &str) -> bool;
+
+    /// Returns the version of the node, failing if the node is unreachable.
+    async fn version(&self) -> Result<String>;
+
+    /// Broadcasts `data` to the subscribers of the pubsub `topic`.
+    async fn publish_topic(&self, topic: &str, data: Vec<u8>) -> Result<()>;
+
+    /// Subscribes to the pubsub `topic`, yielding the data of every message received.
+    fn subscribe_topic(&self, topic: &str) -> LocalBoxStream<'static, Result<Vec<u8>>>;
+}
+
+#[async_trait::async_trait(?Send)]
+impl IpfsBackend for IpfsClient {
+    async fn add(&self, data: Vec<u8>) -> Result<String> {
+        IpfsApi::add(self, Cursor::new(data))
+            .await
+            .map(|res| res.hash)
+            .map_err(|err| Error::IpfsError(err.to_string()))
+    }
+
+    async fn hash(&self, data: Vec<u8>) -> Result<String> {
+        let options: Add<'_> = Add {
+            only_hash: Some(true),
+            ..Default::default()
+        };
+
+        self.add_with_options(Cursor::new(data), options)
+            .await
+            .map(|res| res.hash)
+            .map_err(|err| Error::IpfsError(err.to_string()))
+    }
+
+    async fn cat(&self, cid: &str) -> Result<Vec<u8>> {
+        let mut stream = IpfsApi::cat(self, cid);
+        let mut buf: BytesMut = BytesMut::with_capacity(40960);
+
+        while let Some(part) = stream.next().await {
+            buf.put(part.map_err(|err| Error::IpfsError(err.to_string()))?);
+        }
+
+        Ok(buf.to_vec())
+    }
+
+    async fn pin(&self, cid: &str) -> Result<()> {
+        self.pin_add(cid, true)
+            .await
+            .map(|_| ())
+            .map_err(|err| Error::PinError(err.to_string()))
+    }
+
+    async fn unpin(&self, cid: &str) -> Result<()> {
+        self.pin_rm(cid, true)
+            .await
+            .map(|_| ())
+            .map_err(|err| Error::PinError(err.to_string()))
+    }
+
+    async fn is_pinned(&self, cid: &str) -> bool {
+        self.pin_ls(Some(cid), None).await.is_ok()
+    }
+
+    async fn contains(&self, cid: &str) -> bool {
+        self.block_stat(cid).await.is_ok()
+    }
+
+    async fn version(&self) -> Result<String> {
+        IpfsApi::version(self)
+            .await
+            .map(|res| res.version)
+            .map_err(|err| Error::IpfsError(err.to_string()))
+    }
+
+    async fn publish_topic(&self, topic: &str, data: Vec<u8>) -> Result<()> {
+        self.pubsub_pub(topic, Cursor::new(data))
+            .await
+            .map(|_| ())
+            .map_err(|err| Error::IpfsError(err.to_string()))
+    }
+
+    fn subscribe_topic(&self, topic: &str) -> LocalBoxStream<'static, Result<Vec<u8>>> {
+        self.pubsub_sub(topic)
+            .map(|message| {
+                message
+                    .map(|message| message.data)
+                    .map_err(|err| Error::IpfsError(err.to_string()))
+            })
+            .boxed_local()
+    }
+}
+
+#[cfg(test)]
+pub(crate) use self::memory::MemoryBackend;
+
+#[cfg(test)]
+mod memory {
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    use crypto::hashes::blake2b::Blake2b256;
+    use crypto::hashes::Digest;
+    use futures::channel::mpsc;
+    use futures::channel::mpsc::UnboundedSender;
+    use futures::stream::LocalBoxStream;
+    use futures::stream::StreamExt;
+    use identity_core::utils::encode_b58;
+
+    use super::IpfsBackend;
+    use crate::error::Error;
+    use crate::error::Result;
+
+    /// An in-memory [`IpfsBackend`] for tests.
+    ///
+    /// Content IDs are derived from a hash of the content, so adding the same data twice
+    /// returns the same ID, but they are not valid IPFS CIDs.
+    ///
+    /// Pubsub messages are delivered to the subscribers of the same backend only.
+    #[derive(Debug, Default)]
+    pub(crate) struct MemoryBackend {
+        blocks: Mutex<HashMap<String, Vec<u8>>>,
+        pins: Mutex<HashSet<String>>,
+        topics: Mutex<HashMap<String, Vec<UnboundedSender<Vec<u8>>>>>,
+    }
+
+    impl MemoryBackend {
+        fn cid(data: &[u8]) -> String {
+            format!("Qm{}", encode_b58(&Blake2b256::digest(data)))
+        }
+    }
+
+    #[async_trait::async_trait(?Send)]
+    impl IpfsBackend for MemoryBackend {
+        async fn add(&self, data: Vec<u8>) -> Result<String> {
+            let cid: String = Self::cid(&data);
+            self.blocks.lock().unwrap().insert(cid.clone(), data);
+            Ok(cid)
+        }
+
+        async fn hash(&self, data: Vec<u8>) -> Result<String> {
+            Ok(Self::cid(&data))
+        }
+
+        async fn cat(&self, cid: &str) -> Result<Vec<u8>> {
+            self.blocks
+                .lock()
+                .unwrap()
+                .get(cid)
+                .cloned()
+                .ok_or_else(|| Error::IpfsError(format!("block not found: {}", cid)))
+        }
+
+        async fn pin(&self, cid: &str) -> Result<()> {
+            if !self.contains(cid).await {
+                return Err(Error::PinError(format!("block not found: {}", cid)));
+            }
+
+            self.pins.lock().unwrap().insert(cid.to_owned());
+            Ok(())
+        }
+
+        async fn unpin(&self, cid: &str) -> Result<()> {
+            if self.pins.lock().unwrap().remove(cid) {
+                Ok(())
+            } else {
+                Err(Error::PinError(format!("not pinned: {}", cid)))
+            }
+        }
+
+        async fn is_pinned(&self, cid: &str) -> bool {
+            self.pins.lock().unwrap().contains(cid)
+        }
+
+        async fn contains(&self, cid: &str) -> bool {
+            self.blocks.lock().unwrap().contains_key(cid)
+        }
+
+        async fn version(&self) -> Result<String> {
+            Ok(String::from("memory"))
+        }
+
+        async fn publish_topic(&self, topic: &str, data: Vec<u8>) -> Result<()> {
+            if let Some(subscribers) = self.topics.lock().unwrap().get_mut(topic) {
+                // Forget the subscribers whose streams were dropped.
+                subscribers.retain(|subscriber| subscriber.unbounded_send(data.clone()).is_ok());
+            }
+
+            Ok(())
+        }
+
+        fn subscribe_topic(&self, topic: &str) -> LocalBoxStream<'static, Result<Vec<u8>>> {
+            let (sender, receiver) = mpsc::unbounded();
+
+            self.topics
+                .lock()
+                .unwrap()
+                .entry(topic.to_owned())
+                .or_default()
+                .push(sender);
+
+            receiver.map(Ok).boxed_local()
+        }
+    }
+}
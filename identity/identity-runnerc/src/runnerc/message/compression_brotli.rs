@@ -0,0 +1,84 @@
+// Copyright 2020-2021 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Magic bytes prepended to brotli-compressed payloads stored on IPFS.
+///
+/// JSON text never starts with a NUL byte, so compressed and plain payloads can
+/// be told apart unambiguously.
+pub(crate) const COMPRESSED_PAYLOAD_MARKER: &[u8] = b"\0BR";
+
+const BUFFER_SIZE: usize = 4096;
+const QUALITY: u32 = 5; // compression level
+const WINDOWS_SIZE: u32 = 22;
+
+pub(crate) fn compress_brotli<T: AsRef<[u8]> + ?Sized>(input: &T) -> Result<Vec<u8>> {
+  let mut buf: Vec<u8> = Vec::new();
+  let mut compressor = brotli::CompressorReader::new(input.as_ref(), BUFFER_SIZE, QUALITY, WINDOWS_SIZE);
+  compressor
+    .read_to_end(&mut buf)
+    .map_err(|_| Error::CompressionError)?;
+  Ok(buf)
+}
+
+pub(crate) fn decompress_brotli<T: AsRef<[u8]> + ?Sized>(input: &T) -> Result<Vec<u8>> {
+  let mut buf: Vec<u8> = Vec::new();
+  let mut decompressor = brotli::Decompressor::new(input.as_ref(), BUFFER_SIZE);
+  decompressor
+    .read_to_end(&mut buf)
+    .map_err(|_| Error::CompressionError)?;
+  Ok(buf)
+}
+
+/// Encodes a payload for storage, compressing it and prepending
+/// [`COMPRESSED_PAYLOAD_MARKER`] if `compress` is set.
+pub(crate) fn encode_payload(input: &[u8], compress: bool) -> Result<Vec<u8>> {
+  if !compress {
+    return Ok(input.to_vec());
+  }
+
+  let mut buf: Vec<u8> = COMPRESSED_PAYLOAD_MARKER.to_vec();
+  buf.extend(compress_brotli(input)?);
+  Ok(buf)
+}
+
+/// Decodes a stored payload, transparently decompressing it if it carries the
+/// [`COMPRESSED_PAYLOAD_MARKER`].
+pub(crate) fn decode_payload(input: &[u8]) -> Result<Vec<u8>> {
+  match input.strip_prefix(COMPRESSED_PAYLOAD_MARKER) {
+    Some(compressed) => decompress_brotli(compressed),
+    None => Ok(input.to_vec()),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_brotli() {
+    let input: &str = "abcdfg";
+    let compressed: Vec<u8> = compress_brotli(input).unwrap();
+    let decompressed: Vec<u8> = decompress_brotli(&compressed).unwrap();
+    assert_eq!(decompressed, input.as_bytes());
+  }
+
+  #[test]
+  fn test_payload_round_trip() {
+    let input: String = format!("{{\"data\":\"{}\"}}", "runnerc".repeat(256));
+
+    let encoded: Vec<u8> = encode_payload(input.as_bytes(), true).unwrap();
+    assert!(encoded.starts_with(COMPRESSED_PAYLOAD_MARKER));
+    assert!(encoded.len() < input.len());
+    assert_eq!(decode_payload(&encoded).unwrap(), input.as_bytes());
+
+    // Uncompressed payloads are passed through untouched.
+    let encoded: Vec<u8> = encode_payload(input.as_bytes(), false).unwrap();
+    assert_eq!(encoded, input.as_bytes());
+    assert_eq!(decode_payload(&encoded).unwrap(), input.as_bytes());
+  }
+}
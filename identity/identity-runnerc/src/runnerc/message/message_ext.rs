@@ -1,6 +1,7 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use super::DIDMessageVersion;
 use super::Message;
 use super::MessageId;
 use super::MESSAGE_ID_LENGTH;
@@ -11,6 +12,7 @@ use identity_did::did::DID;
 use crate::did::RunnercDID;
 use crate::document::DiffMessage;
 use crate::document::RunnercDocument;
+use crate::error::Error;
 use crate::error::Result;
 use crate::runnerc::TangleRef;
 
@@ -32,20 +34,58 @@ fn parse_message<T: FromJson + TangleRef>(message: &Message, did: &RunnercDID) -
     Some(resource)
 }
 
+/// Parses a message payload of the form `DID<version byte><JSON>`.
+///
+/// Returns `None` if the payload is missing, lacks the [`DID_MESSAGE_MARKER`],
+/// cannot be deserialized, or was written with a [`DIDMessageVersion`] other
+/// than the current one.
 fn parse_payload<T: FromJson + TangleRef>(
     message_id: MessageId,
     payload: Option<&String>,
 ) -> Option<T> {
-    match payload {
-        Some(payload_str) => {
-            if let Ok(t) = T::from_json_slice(payload_str) {
-                Some(t)
-            } else {
-                None
-            }
-        }
-        _ => None,
+    // Only payloads marked as DID messages are considered, so unrelated JSON
+    // stored under the same index is never mistaken for a document or diff.
+    let payload: &[u8] = payload?.as_bytes().strip_prefix(DID_MESSAGE_MARKER)?;
+    let (version, data): (&u8, &[u8]) = payload.split_first()?;
+
+    // Unknown or unsupported versions are skipped rather than mis-parsed.
+    if DIDMessageVersion::try_from(*version).ok()? != DIDMessageVersion::CURRENT {
+        return None;
     }
+
+    T::from_json_slice(data).ok()
+}
+
+/// Encodes `json` as a message payload of the form `DID<version byte><JSON>`, written with
+/// the current [`DIDMessageVersion`].
+pub(crate) fn encode_message(json: &str) -> String {
+    format!(
+        "{}{}{}",
+        String::from_utf8_lossy(DID_MESSAGE_MARKER),
+        char::from(DIDMessageVersion::CURRENT as u8),
+        json
+    )
+}
+
+/// Returns the JSON of a message `payload` written by [`encode_message`].
+///
+/// # Errors
+///
+/// Fails with [`Error::InvalidMessageFlags`] if the payload lacks the [`DID_MESSAGE_MARKER`]
+/// or was written with a [`DIDMessageVersion`] other than the current one.
+pub(crate) fn decode_message(payload: &str) -> Result<&str> {
+    let marked: &[u8] = payload
+        .as_bytes()
+        .strip_prefix(DID_MESSAGE_MARKER)
+        .ok_or(Error::InvalidMessageFlags)?;
+    let version: u8 = *marked.first().ok_or(Error::InvalidMessageFlags)?;
+
+    if DIDMessageVersion::try_from(version)? != DIDMessageVersion::CURRENT {
+        return Err(Error::InvalidMessageFlags);
+    }
+
+    // Both the marker and the version byte are ASCII.
+    Ok(&payload[DID_MESSAGE_MARKER.len() + 1..])
 }
 
 pub trait MessageIdExt: Sized {
@@ -101,3 +141,123 @@ impl TryFromMessage for DiffMessage {
         parse_message(message, did)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use identity_core::convert::ToJson;
+    use identity_core::crypto::KeyPair;
+
+    use super::*;
+
+    fn did_message(payload: String) -> Message {
+        Message {
+            network_id: 0,
+            message_id: String::new(),
+            payload: Some(payload),
+            nonce: 0,
+        }
+    }
+
+    fn versioned(version: u8, json: &str) -> String {
+        format!(
+            "{}{}{}",
+            String::from_utf8_lossy(DID_MESSAGE_MARKER),
+            char::from(version),
+            json
+        )
+    }
+
+    #[test]
+    fn test_extract_document_current_version() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        let json: String = document.to_json().unwrap();
+
+        let message: Message = did_message(versioned(DIDMessageVersion::CURRENT as u8, &json));
+        let extracted: RunnercDocument = message.try_extract_document(document.id()).unwrap();
+        assert_eq!(extracted.id(), document.id());
+    }
+
+    #[test]
+    fn test_extract_document_unknown_version() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        let json: String = document.to_json().unwrap();
+
+        for version in [0, DIDMessageVersion::CURRENT as u8 + 1, 0x7f] {
+            let message: Message = did_message(versioned(version, &json));
+            assert!(message.try_extract_document(document.id()).is_none());
+        }
+
+        // A payload without a version byte is skipped as well.
+        let unversioned: String =
+            format!("{}{}", String::from_utf8_lossy(DID_MESSAGE_MARKER), json);
+        assert!(did_message(unversioned)
+            .try_extract_document(document.id())
+            .is_none());
+    }
+
+    #[test]
+    fn test_extract_document_marker() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        let json: String = document.to_json().unwrap();
+
+        // Marked payload
+        let marked: String = versioned(DIDMessageVersion::CURRENT as u8, &json);
+        assert!(did_message(marked)
+            .try_extract_document(document.id())
+            .is_some());
+
+        // Unmarked payloads, with and without a version byte
+        let unmarked: String = format!("{}{}", char::from(DIDMessageVersion::CURRENT as u8), json);
+        assert!(did_message(unmarked)
+            .try_extract_document(document.id())
+            .is_none());
+        assert!(did_message(json.clone())
+            .try_extract_document(document.id())
+            .is_none());
+
+        // Wrong marker
+        let wrong: String = format!("VC{}{}", char::from(DIDMessageVersion::CURRENT as u8), json);
+        assert!(did_message(wrong)
+            .try_extract_document(document.id())
+            .is_none());
+    }
+
+    #[test]
+    fn test_encode_decode_message() {
+        let keypair: KeyPair = KeyPair::new_ed25519().unwrap();
+        let document: RunnercDocument = RunnercDocument::new(&keypair).unwrap();
+        let json: String = document.to_json().unwrap();
+
+        let encoded: String = encode_message(&json);
+        assert_eq!(encoded, versioned(DIDMessageVersion::CURRENT as u8, &json));
+        assert_eq!(decode_message(&encoded).unwrap(), json);
+        assert!(did_message(encoded)
+            .try_extract_document(document.id())
+            .is_some());
+
+        // Unmarked payloads are rejected, like by `parse_payload`.
+        assert!(matches!(
+            decode_message(&json),
+            Err(Error::InvalidMessageFlags)
+        ));
+        let unmarked: String = format!("{}{}", char::from(DIDMessageVersion::CURRENT as u8), json);
+        assert!(matches!(
+            decode_message(&unmarked),
+            Err(Error::InvalidMessageFlags)
+        ));
+
+        for version in [0, DIDMessageVersion::CURRENT as u8 + 1] {
+            assert!(matches!(
+                decode_message(&versioned(version, &json)),
+                Err(Error::InvalidMessageFlags)
+            ));
+        }
+        assert!(matches!(
+            decode_message("DID"),
+            Err(Error::InvalidMessageFlags)
+        ));
+    }
+}
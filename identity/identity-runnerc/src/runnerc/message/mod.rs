@@ -1,12 +1,17 @@
 // Copyright 2020-2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) use self::compression_brotli::decode_payload;
+pub(crate) use self::compression_brotli::encode_payload;
+pub(crate) use self::message_ext::decode_message;
+pub(crate) use self::message_ext::encode_message;
 pub use self::message_ext::MessageExt;
 pub use self::message_ext::MessageIdExt;
 pub use self::message_ext::TryFromMessage;
 pub use self::message_index::MessageIndex;
 pub use self::message_version::DIDMessageVersion;
 
+mod compression_brotli;
 mod message;
 mod message_ext;
 mod message_id;
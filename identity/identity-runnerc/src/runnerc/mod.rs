@@ -13,6 +13,8 @@ pub type BeeMessageError = std::io::Error;
 pub use self::client::Client;
 pub use self::client_map::ClientMap;
 pub use self::explorer::ExplorerUrl;
+pub use self::health::HealthStatus;
+pub use self::health::ServiceStatus;
 pub use self::message::DIDMessageVersion;
 pub use self::message::Message;
 pub use self::message::MessageId;
@@ -29,9 +31,15 @@ pub use self::receipt::Receipt;
 pub use self::traits::TangleRef;
 pub use self::traits::TangleResolve;
 
+pub(crate) use self::ipfs_backend::IpfsBackend;
+#[cfg(test)]
+pub(crate) use self::ipfs_backend::MemoryBackend;
+
 mod client;
 mod client_map;
 mod explorer;
+mod health;
+mod ipfs_backend;
 mod message;
 mod network;
 mod publish;
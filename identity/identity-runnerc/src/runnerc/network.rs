@@ -46,7 +46,8 @@ impl Network {
   /// For example, if the networkId is `"private-runnerc"`, `"runnerc"` can be used.
   ///
   /// Network names must comply with the IOTA DID Method spec, that is: be non-empty, at most
-  /// 6 characters long, and only include alphanumeric characters `0-9` and `a-z`.
+  /// 6 characters long, and only include alphanumeric characters `0-9` and `a-z`. Uppercase
+  /// characters are normalized to lowercase, so `"Main"` is the [`Mainnet`][Network::Mainnet].
   ///
   /// See [`NetworkName`].
   pub fn try_from_name<S>(name: S) -> Result<Self>
@@ -54,14 +55,13 @@ impl Network {
     // Allow String, &'static str, Cow<'static, str>, NetworkName
     S: AsRef<str> + Into<Cow<'static, str>>,
   {
-    match name.as_ref() {
+    // Validation and normalization is performed by NetworkName
+    let network_name: NetworkName = NetworkName::try_from(name)?;
+
+    match network_name.as_ref() {
       NETWORK_NAME_MAIN => Ok(Self::Mainnet),
       NETWORK_NAME_DEV => Ok(Self::Devnet),
-      _ => {
-        // Accept any other valid string - validation is performed by NetworkName
-        let network_name: NetworkName = NetworkName::try_from(name)?;
-        Ok(Self::Other(network_name))
-      }
+      _ => Ok(Self::Other(network_name)),
     }
   }
 
@@ -87,8 +87,8 @@ impl Network {
   /// Returns the [`NetworkName`] of the network.
   pub fn name(&self) -> NetworkName {
     match self {
-      Self::Mainnet => NetworkName(Cow::from(NETWORK_NAME_MAIN)),
-      Self::Devnet => NetworkName(Cow::from(NETWORK_NAME_DEV)),
+      Self::Mainnet => NetworkName::MAIN,
+      Self::Devnet => NetworkName::DEV,
       Self::Other(name) => name.clone(),
     }
   }
@@ -112,24 +112,45 @@ impl Default for Network {
 
 /// Network name compliant with the IOTA DID method specification:
 /// https://github.com/iotaledger/identity.rs/blob/dev/documentation/docs/specs/iota_did_method_spec.md
+///
+/// Network names are always lowercase, so the same logical network has a single representation.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
 #[repr(transparent)]
+#[serde(try_from = "String")]
 pub struct NetworkName(Cow<'static, str>);
 
 impl NetworkName {
   const MAX_LENGTH: usize = 6;
 
+  /// The [`NetworkName`] of the [`Mainnet`][Network::Mainnet].
+  ///
+  /// NOTE: [`NetworkName`] does not support structural matching, so compare with `==` rather than
+  /// using the constant as a `match` pattern.
+  pub const MAIN: Self = Self(Cow::Borrowed(NETWORK_NAME_MAIN));
+
+  /// The [`NetworkName`] of the [`Devnet`][Network::Devnet].
+  pub const DEV: Self = Self(Cow::Borrowed(NETWORK_NAME_DEV));
+
   /// Creates a new [`NetworkName`] if the name passes validation.
+  ///
+  /// Uppercase ASCII characters are normalized to lowercase before validation.
   pub fn try_from<T>(name: T) -> Result<Self>
   where
     T: Into<Cow<'static, str>>,
   {
-    let name_cow: Cow<'static, str> = name.into();
+    let mut name_cow: Cow<'static, str> = name.into();
+
+    if name_cow.chars().any(|ch| ch.is_ascii_uppercase()) {
+      name_cow = Cow::Owned(name_cow.to_ascii_lowercase());
+    }
+
     Self::validate_network_name(&name_cow)?;
     Ok(Self(name_cow))
   }
 
   /// Validates whether a string is a spec-compliant IOTA DID [`NetworkName`].
+  ///
+  /// Unlike [`NetworkName::try_from`], uppercase characters are rejected.
   pub fn validate_network_name(name: &str) -> Result<()> {
     if name.is_empty() {
       return Err(Error::InvalidNetworkName);
@@ -199,6 +220,19 @@ mod tests {
     assert_eq!(Network::try_from_name(NETWORK_NAME_DEV).unwrap(), Network::Devnet);
   }
 
+  #[test]
+  fn test_network_name_constants() {
+    assert_eq!(NetworkName::MAIN, Network::Mainnet.name());
+    assert_eq!(NetworkName::DEV, Network::Devnet.name());
+    assert_eq!(NetworkName::MAIN, NetworkName::try_from("main").unwrap());
+    assert_eq!(NetworkName::DEV, NetworkName::try_from("dev").unwrap());
+    assert!(NetworkName::validate_network_name(&NetworkName::MAIN).is_ok());
+    assert!(NetworkName::validate_network_name(&NetworkName::DEV).is_ok());
+
+    static DEFAULT: NetworkName = NetworkName::MAIN;
+    assert_eq!(Network::try_from_name(DEFAULT.clone()).unwrap(), Network::Mainnet);
+  }
+
   #[test]
   fn test_from_name_types() {
     let static_str = "custom";
@@ -243,23 +277,53 @@ mod tests {
       Network::try_from_name(" ").unwrap_err(),
       Error::InvalidNetworkName
     ));
+  }
+
+  #[test]
+  fn test_from_name_normalized() {
+    assert_eq!(Network::try_from_name("Main").unwrap(), Network::Mainnet);
+    assert_eq!(Network::try_from_name("DEV").unwrap(), Network::Devnet);
+    assert_eq!(
+      Network::try_from_name("Test").unwrap(),
+      Network::Other(NetworkName::try_from("test").unwrap())
+    );
+    assert_eq!(NetworkName::try_from("Custom").unwrap().as_ref(), "custom");
+
+    // Only ASCII characters are normalized.
     assert!(matches!(
-      Network::try_from_name("Test").unwrap_err(),
+      Network::try_from_name("TÄST").unwrap_err(),
       Error::InvalidNetworkName
     ));
+
+    // Strict validation still rejects uppercase characters.
+    assert!(NetworkName::validate_network_name("Test").is_err());
+  }
+
+  #[test]
+  fn test_network_name_serde() {
+    let name: NetworkName = NetworkName::try_from("Test").unwrap();
+    let json: String = serde_json::to_string(&name).unwrap();
+    assert_eq!(json, "\"test\"");
+    assert_eq!(serde_json::from_str::<NetworkName>(&json).unwrap(), name);
+
+    assert_eq!(
+      serde_json::from_str::<NetworkName>("\"Test\"").unwrap(),
+      name
+    );
+    assert!(serde_json::from_str::<NetworkName>("\"7seven7\"").is_err());
   }
 
   #[test]
   fn test_matches_did() {
-    let did: RunnercDID = RunnercDID::new(b"").unwrap();
+    let did: RunnercDID = RunnercDID::new(&[0; 32]).unwrap();
     assert!(Network::matches_did(Network::Mainnet, &did));
     assert!(!Network::matches_did(Network::Devnet, &did));
 
-    let did: RunnercDID = RunnercDID::new_with_network(b"", "main").unwrap();
+    let did: RunnercDID = RunnercDID::new_with_network(&[0; 32], "main").unwrap();
     assert!(Network::matches_did(Network::Mainnet, &did));
     assert!(!Network::matches_did(Network::Devnet, &did));
 
-    let did: RunnercDID = RunnercDID::new_with_network(b"", "dev").unwrap();
+    let did: RunnercDID = RunnercDID::new_with_network(&[0; 32], "dev").unwrap();
     assert!(Network::matches_did(Network::Devnet, &did));
     assert!(!Network::matches_did(Network::Mainnet, &did));
   }
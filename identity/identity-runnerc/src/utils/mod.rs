@@ -1,6 +1,10 @@
+use crate::error::Error;
+use crate::error::Result;
 use crate::ipfs_api::IpfsClient;
-use anyhow::{anyhow, Result};
-use hyper::{service::Service, Body, Client, Request};
+use hyper::{
+    body::Bytes, client::HttpConnector, service::Service, Body, Client, Request, StatusCode,
+};
+use std::time::Duration;
 
 //
 pub fn get_ipfs_client() -> IpfsClient {
@@ -8,25 +12,243 @@ pub fn get_ipfs_client() -> IpfsClient {
     IpfsClient::default()
 }
 
-const KVSTORE_URL: &'static str = "http://localhost:35091";
+pub(crate) const KVSTORE_URL: &'static str = "http://localhost:35091";
 
-/// 内部请求的方法
-pub(crate) async fn _ciddb_get_request(path: &str) -> Result<String> {
+/// The reserved KVStore key requested by [`kvstore_health`].
+const KVSTORE_HEALTH_KEY: &str = "__health__";
+
+/// Checks that the KVStore at `base_url` is reachable by requesting a reserved health key.
+///
+/// The KVStore is healthy if it answers, whether or not it has an entry for the key.
+pub(crate) async fn kvstore_health(base_url: &str, connect_timeout: Duration) -> Result<()> {
+    let path: String = format!("/didcid/get/{}", KVSTORE_HEALTH_KEY);
+
+    match kvstore_get(base_url, &path, connect_timeout).await {
+        Ok(_) | Err(Error::KvStoreNotFound(_)) => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
+/// Sends the request `path` to the KVStore at `base_url`.
+///
+/// Connecting to the KVStore fails after `connect_timeout`.
+///
+/// # Errors
+///
+/// Fails with [`Error::KvStoreUnreachable`] if the request cannot be sent,
+/// [`Error::KvStoreNotFound`] if the KVStore has no entry for `path` and
+/// [`Error::KvStoreBadResponse`] if the response is not a successful UTF-8 body.
+pub(crate) async fn kvstore_get(
+    base_url: &str,
+    path: &str,
+    connect_timeout: Duration,
+) -> Result<String> {
     tracing::debug!("请求路径:{}", path);
     //请求地址
-    let uri = format!("{}{}", KVSTORE_URL, path).parse::<http::Uri>()?;
-    let mut client = Client::new();
+    let uri = format!("{}{}", base_url, path)
+        .parse::<http::Uri>()
+        .map_err(|err| Error::KvStoreUnreachable(err.to_string()))?;
+    let mut connector = HttpConnector::new();
+    connector.set_connect_timeout(Some(connect_timeout));
+    let mut client = Client::builder().build::<_, Body>(connector);
     let body = Body::empty();
-    let request = Request::get(uri).body(body)?;
+    let request = Request::get(uri)
+        .body(body)
+        .map_err(|err| Error::KvStoreUnreachable(err.to_string()))?;
     //发送请求
-    let resp = client.call(request).await?;
+    let resp = client
+        .call(request)
+        .await
+        .map_err(|err| Error::KvStoreUnreachable(err.to_string()))?;
+    let status: StatusCode = resp.status();
     //
-    let result = hyper::body::to_bytes(resp).await;
-    match result {
-        Ok(plain) => {
-            tracing::debug!("response plain: {:?}", plain);
-            Ok(String::from_utf8(plain[..].to_vec())?)
+    let plain: Bytes = hyper::body::to_bytes(resp)
+        .await
+        .map_err(|err| Error::KvStoreBadResponse(err.to_string()))?;
+    tracing::debug!("response plain: {:?}", plain);
+
+    if status == StatusCode::NOT_FOUND {
+        return Err(Error::KvStoreNotFound(path.to_owned()));
+    }
+
+    if !status.is_success() {
+        return Err(Error::KvStoreBadResponse(format!("status {}", status)));
+    }
+
+    String::from_utf8(plain.to_vec())
+        .map_err(|_err| Error::KvStoreBadResponse(String::from("invalid UTF-8")))
+}
+
+#[cfg(test)]
+pub(crate) use self::memory::serve_memory_kvstore;
+
+#[cfg(test)]
+mod memory {
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+
+    use hyper::service::make_service_fn;
+    use hyper::service::service_fn;
+    use hyper::Body;
+    use hyper::Request;
+    use hyper::Response;
+    use hyper::Server;
+    use hyper::StatusCode;
+
+    type Entries = Arc<Mutex<HashMap<String, String>>>;
+
+    /// Serves an in-memory KVStore for tests, returning its URL.
+    ///
+    /// Like the KVStore service, it stores a value with `/didcid/insert/{key}/{value}` and
+    /// returns it for `/didcid/get/{key}`.
+    pub(crate) fn serve_memory_kvstore() -> String {
+        let entries: Entries = Entries::default();
+
+        let make_service = make_service_fn(move |_| {
+            let entries: Entries = entries.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let response: Response<Body> = respond(&entries, request.uri().path());
+                    async move { Ok::<_, Infallible>(response) }
+                }))
+            }
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url: String = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        url
+    }
+
+    fn respond(entries: &Entries, path: &str) -> Response<Body> {
+        let mut entries = entries.lock().unwrap();
+
+        if let Some((key, value)) = path
+            .strip_prefix("/didcid/insert/")
+            .and_then(|entry| entry.rsplit_once('/'))
+        {
+            entries.insert(key.to_owned(), value.to_owned());
+            return Response::new(Body::empty());
         }
-        Err(err) => Err(anyhow!("Fail request {}!", err)),
+
+        match path
+            .strip_prefix("/didcid/get/")
+            .and_then(|key| entries.get(key))
+        {
+            Some(value) => Response::new(Body::from(value.clone())),
+            None => Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(Body::empty())
+                .unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use hyper::service::make_service_fn;
+    use hyper::service::service_fn;
+    use hyper::Response;
+    use hyper::Server;
+    use std::convert::Infallible;
+    use std::net::TcpListener;
+
+    const PATH: &str = "/didcid/get/did:idns:H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV";
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Serves every request with `status` and `body`, returning the URL of the server.
+    fn serve(status: StatusCode, body: &'static [u8]) -> String {
+        let make_service = make_service_fn(move |_| async move {
+            Ok::<_, Infallible>(service_fn(move |_: Request<Body>| async move {
+                Ok::<_, Infallible>(
+                    Response::builder()
+                        .status(status)
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+            }))
+        });
+
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let url: String = format!("http://{}", server.local_addr());
+        tokio::spawn(server);
+        url
+    }
+
+    #[tokio::test]
+    async fn test_kvstore_get() {
+        let url: String = serve(
+            StatusCode::OK,
+            b"QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u",
+        );
+
+        assert_eq!(
+            kvstore_get(&url, PATH, TIMEOUT).await.unwrap(),
+            "QmWATWQ7fVPP2EFGu71UkfnqhYXDYH566qy47CnJDgvs8u"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_kvstore_unreachable() {
+        // Nothing listens on a port once its listener is dropped.
+        let port: u16 = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let url: String = format!("http://127.0.0.1:{}", port);
+
+        assert!(matches!(
+            kvstore_get(&url, PATH, TIMEOUT).await,
+            Err(Error::KvStoreUnreachable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_kvstore_not_found() {
+        let url: String = serve(StatusCode::NOT_FOUND, b"");
+
+        assert!(matches!(
+            kvstore_get(&url, PATH, TIMEOUT).await,
+            Err(Error::KvStoreNotFound(path)) if path == PATH
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_kvstore_bad_response() {
+        let url: String = serve(StatusCode::INTERNAL_SERVER_ERROR, b"");
+
+        assert!(matches!(
+            kvstore_get(&url, PATH, TIMEOUT).await,
+            Err(Error::KvStoreBadResponse(_))
+        ));
+
+        // Not valid UTF-8.
+        let url: String = serve(StatusCode::OK, &[0xff, 0xfe]);
+
+        assert!(matches!(
+            kvstore_get(&url, PATH, TIMEOUT).await,
+            Err(Error::KvStoreBadResponse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_kvstore_health() {
+        // A missing health entry still proves the KVStore is responding.
+        let url: String = serve(StatusCode::NOT_FOUND, b"");
+        assert!(kvstore_health(&url, TIMEOUT).await.is_ok());
+
+        let url: String = serve(StatusCode::OK, b"");
+        assert!(kvstore_health(&url, TIMEOUT).await.is_ok());
+
+        let url: String = serve(StatusCode::INTERNAL_SERVER_ERROR, b"");
+        assert!(matches!(
+            kvstore_health(&url, TIMEOUT).await,
+            Err(Error::KvStoreBadResponse(_))
+        ));
     }
 }
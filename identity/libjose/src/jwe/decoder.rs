@@ -586,15 +586,48 @@ impl<'a, 'b> EcdhDeriver<'a, 'b> {
     key_len: usize,
     key_exchange: impl Fn(&Jwk) -> Result<Vec<u8>>,
   ) -> Result<Vec<u8>> {
+    let epk: &Jwk = header.try_epk()?;
     let apu: Option<Vec<u8>> = header.apu().map(decode_b64).transpose()?;
     let apv: Option<Vec<u8>> = header.apv().map(decode_b64).transpose()?;
 
+    // The ephemeral key MUST be on the same curve as the recipient key
+    if epk.try_ecdh_curve()? != self.0.ecdh_curve {
+      return Err(Error::InvalidParam("epk (crv)"));
+    }
+
     concat_kdf(
       algorithm,
       key_len,
-      &key_exchange(header.try_epk()?)?,
+      &key_exchange(epk)?,
       apu.as_deref().unwrap_or_default(),
       apv.as_deref().unwrap_or_default(),
     )
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::jwk::EcCurve;
+
+  #[test]
+  fn test_ecdh_es_epk_curve() {
+    let recipient: Jwk = Jwk::random(EcxCurve::X25519).unwrap();
+    let decoder: Decoder<'_> = Decoder::new(&recipient);
+
+    let mut header: JweHeader = JweHeader::new(JweAlgorithm::ECDH_ES, JweEncryption::A256GCM);
+    header.set_epk(Jwk::random(EcxCurve::X25519).unwrap().to_public());
+
+    assert!(decoder.__test_decrypt_key(&header).is_ok());
+
+    // An ephemeral key on any other curve is rejected.
+    for epk in [Jwk::random(EcxCurve::X448), Jwk::random(EcCurve::P256)] {
+      header.set_epk(epk.unwrap().to_public());
+
+      assert!(matches!(
+        decoder.__test_decrypt_key(&header),
+        Err(Error::InvalidParam("epk (crv)"))
+      ));
+    }
+  }
+}
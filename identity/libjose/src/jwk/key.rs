@@ -12,6 +12,7 @@ use zeroize::Zeroize;
 use crate::error::Error;
 use crate::error::Result;
 use crate::jwk::EcCurve;
+use crate::jwk::EcdhCurve;
 use crate::jwk::EcxCurve;
 use crate::jwk::EdCurve;
 use crate::jwk::JwkOperation;
@@ -458,6 +459,14 @@ impl Jwk {
     }
   }
 
+  pub fn try_ecdh_curve(&self) -> Result<EcdhCurve> {
+    match self.params() {
+      JwkParams::Ec(inner) => inner.try_ec_curve().map(Into::into),
+      JwkParams::Okp(inner) => inner.try_ecx_curve().map(Into::into),
+      _ => Err(Error::KeyError("Ecdh Curve")),
+    }
+  }
+
   pub fn to_public(&self) -> Jwk {
     let mut public: Jwk = Jwk::from_params(self.params().to_public());
 
@@ -1,11 +1,18 @@
 // Copyright 2020-2021 Runnerc
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::de::value::Error as ValueError;
+use serde::de::Error as _;
+use serde::de::Visitor;
+use serde::Deserializer;
 use serde_json::Map;
 use serde_json::Value;
 
 use crate::lib::*;
 
+// Claims defined by the `JwtClaims` fields
+const REGISTERED: &[&str] = &["iss", "sub", "aud", "exp", "nbf", "iat", "jti", "did", "vc", "vp"];
+
 /// JSON Web Token Claims
 ///
 /// [More Info](https://tools.ietf.org/html/rfc7519#section-4)
@@ -68,7 +75,15 @@ pub struct JwtClaims<T = ()> {
   /// Public/Private Claim Names
   ///
   /// [More Info](https://tools.ietf.org/html/rfc7519#section-4.2)
-  #[serde(flatten, skip_serializing_if = "Option::is_none")]
+  ///
+  /// Deserialization fails if `T` has a field named like one of the registered claims, which
+  /// would otherwise be deserialized into the registered claim instead.
+  #[serde(
+    flatten,
+    skip_serializing_if = "Option::is_none",
+    deserialize_with = "deserialize_custom",
+    bound(deserialize = "T: Deserialize<'de>")
+  )]
   custom: Option<T>,
 }
 
@@ -205,3 +220,110 @@ impl<T> JwtClaims<T> {
     self.custom = Some(value.into());
   }
 }
+
+fn deserialize_custom<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+  D: Deserializer<'de>,
+  T: Deserialize<'de>,
+{
+  if let Some(claim) = struct_fields::<T>().iter().find(|field| REGISTERED.contains(field)) {
+    return Err(D::Error::custom(format_args!(
+      "custom claim `{}` collides with a registered claim",
+      claim
+    )));
+  }
+
+  Option::<T>::deserialize(deserializer)
+}
+
+/// Returns the names of the fields of `T` if it is deserialized as a struct.
+///
+/// Types deserialized as maps, e.g. structs with flattened fields, accept any name and return an
+/// empty slice.
+fn struct_fields<'de, T>() -> &'static [&'static str]
+where
+  T: Deserialize<'de>,
+{
+  struct FieldsDeserializer<'a>(&'a mut &'static [&'static str]);
+
+  impl<'de, 'a> Deserializer<'de> for FieldsDeserializer<'a> {
+    type Error = ValueError;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+      V: Visitor<'de>,
+    {
+      Err(ValueError::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V>(
+      self,
+      _name: &'static str,
+      fields: &'static [&'static str],
+      _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+      V: Visitor<'de>,
+    {
+      *self.0 = fields;
+      Err(ValueError::custom("fields collected"))
+    }
+
+    serde::forward_to_deserialize_any! {
+      bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+      unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+    }
+  }
+
+  let mut fields: &'static [&'static str] = &[];
+  let _ = T::deserialize(FieldsDeserializer(&mut fields));
+  fields
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[derive(Debug, PartialEq, Deserialize, Serialize)]
+  struct Custom {
+    name: String,
+  }
+
+  #[derive(Debug, PartialEq, Deserialize, Serialize)]
+  struct Colliding {
+    sub: String,
+  }
+
+  #[derive(Debug, PartialEq, Deserialize, Serialize)]
+  struct Renamed {
+    #[serde(rename = "iss")]
+    issuer: String,
+  }
+
+  #[test]
+  fn test_custom_claims() {
+    let json: &str = r#"{"sub":"alice","name":"Alice"}"#;
+    let claims: JwtClaims<Custom> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(claims.sub(), Some("alice"));
+    assert_eq!(claims.custom(), Some(&Custom { name: "Alice".into() }));
+
+    // Maps accept any claim name and only receive the unregistered claims.
+    let claims: JwtClaims<Map<String, Value>> = serde_json::from_str(json).unwrap();
+
+    assert_eq!(claims.sub(), Some("alice"));
+    assert_eq!(claims.custom().unwrap().len(), 1);
+    assert_eq!(claims.custom().unwrap()["name"], "Alice");
+  }
+
+  #[test]
+  fn test_custom_claims_collision() {
+    let error: serde_json::Error = serde_json::from_str::<JwtClaims<Colliding>>(r#"{"sub":"alice"}"#).unwrap_err();
+
+    assert!(error.to_string().contains("custom claim `sub` collides"));
+
+    let error: serde_json::Error = serde_json::from_str::<JwtClaims<Renamed>>(r#"{"iss":"alice"}"#).unwrap_err();
+
+    assert!(error.to_string().contains("custom claim `iss` collides"));
+  }
+}
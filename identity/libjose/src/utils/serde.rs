@@ -7,6 +7,7 @@ use crate::error::Error;
 use crate::error::Result;
 use crate::jose::JoseHeader;
 use crate::jwe::JweHeader;
+use crate::jwk::JwkParams;
 use crate::jws::JwsHeader;
 use crate::lib::*;
 use crate::utils::decode_b64;
@@ -119,6 +120,8 @@ pub fn validate_jwe_headers<'a>(
     };
 
     validate_crit(protected, unprotected.as_deref(), permitted)?;
+    validate_key_agreement(protected)?;
+    validate_key_agreement(unprotected.as_deref())?;
 
     // The "zip" parameter MUST be integrity protected
     if unprotected.map(|header| header.has("zip")).unwrap_or_default() {
@@ -129,6 +132,34 @@ pub fn validate_jwe_headers<'a>(
   Ok(())
 }
 
+pub fn validate_key_agreement(header: Option<&JweHeader>) -> Result<()> {
+  let header: &JweHeader = match header {
+    Some(header) => header,
+    None => return Ok(()),
+  };
+
+  // The "epk" parameter MUST be an elliptic curve public key
+  match header.epk().map(|epk| epk.params()) {
+    Some(JwkParams::Ec(params)) if params.d.is_some() => return Err(Error::InvalidParam("epk (private)")),
+    Some(JwkParams::Okp(params)) if params.d.is_some() => return Err(Error::InvalidParam("epk (private)")),
+    // An octet key pair MUST be on an ECDH curve (X25519 or X448), not a signature curve
+    Some(JwkParams::Okp(params)) if params.try_ecx_curve().is_err() => return Err(Error::InvalidParam("epk (crv)")),
+    Some(JwkParams::Ec(_)) | Some(JwkParams::Okp(_)) | None => {}
+    Some(_) => return Err(Error::InvalidParam("epk (kty)")),
+  }
+
+  // The "apu" and "apv" parameters MUST be base64url-encoded
+  if header.apu().map(decode_b64).transpose().is_err() {
+    return Err(Error::InvalidParam("apu"));
+  }
+
+  if header.apv().map(decode_b64).transpose().is_err() {
+    return Err(Error::InvalidParam("apv"));
+  }
+
+  Ok(())
+}
+
 pub fn validate_crit<T>(protected: Option<&T>, unprotected: Option<&T>, permitted: Option<&[String]>) -> Result<()>
 where
   T: JoseHeader,
@@ -189,3 +220,74 @@ pub fn validate_b64(protected: Option<&JwsHeader>, unprotected: Option<&JwsHeade
     _ => Ok(()),
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::jwe::JweAlgorithm;
+  use crate::jwe::JweEncryption;
+  use crate::jwk::EcxCurve;
+  use crate::jwk::EdCurve;
+  use crate::jwk::Jwk;
+  use crate::utils::encode_b64;
+
+  fn header() -> JweHeader {
+    JweHeader::new(JweAlgorithm::ECDH_ES, JweEncryption::A256GCM)
+  }
+
+  #[test]
+  fn test_validate_epk_x25519() {
+    let epk: Jwk = Jwk::random(EcxCurve::X25519).unwrap();
+
+    let mut protected: JweHeader = header();
+    protected.set_epk(epk.to_public());
+    protected.set_apu(encode_b64(b"Alice"));
+    protected.set_apv(encode_b64(b"Bob"));
+
+    assert_eq!(protected.epk(), Some(&epk.to_public()));
+    assert!(validate_jwe_headers(Some(&protected), None, Some(None).into_iter(), None).is_ok());
+
+    // The ephemeral key may also be provided per-recipient.
+    let mut recipient: JweHeader = header();
+    recipient.set_epk(epk.to_public());
+
+    assert!(validate_jwe_headers(Some(&header()), None, Some(Some(&recipient)).into_iter(), None).is_ok());
+  }
+
+  #[test]
+  fn test_validate_epk_invalid() {
+    let epk: Jwk = Jwk::random(EcxCurve::X25519).unwrap();
+
+    let mut protected: JweHeader = header();
+    protected.set_epk(epk);
+
+    assert!(matches!(
+      validate_key_agreement(Some(&protected)),
+      Err(Error::InvalidParam("epk (private)"))
+    ));
+
+    let mut protected: JweHeader = header();
+    protected.set_epk(Jwk::random(32).unwrap());
+
+    assert!(matches!(
+      validate_key_agreement(Some(&protected)),
+      Err(Error::InvalidParam("epk (kty)"))
+    ));
+
+    let mut protected: JweHeader = header();
+    protected.set_epk(Jwk::random(EdCurve::Ed25519).unwrap().to_public());
+
+    assert!(matches!(
+      validate_key_agreement(Some(&protected)),
+      Err(Error::InvalidParam("epk (crv)"))
+    ));
+
+    let mut protected: JweHeader = header();
+    protected.set_apu("not base64!");
+
+    assert!(matches!(
+      validate_key_agreement(Some(&protected)),
+      Err(Error::InvalidParam("apu"))
+    ));
+  }
+}